@@ -0,0 +1,74 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+mod legacy;
+
+/// Builds a document with `n` members using every kind of value.
+fn yojson_document(n: usize) -> String {
+  let mut s = String::from("{\n");
+  for i in 0..n {
+    if i > 0 {
+      s.push_str(",\n");
+    }
+    s.push_str(&format!(
+      "  // comment\n  item{} : {{ id : {}, name : \"name \\\"{}\\\"\", score : {}.5, tags : [\"a\", \"b\", null, true], \
+       pos : ({}, -{}), state : <Active: {}> }}",
+      i, i, i, i, i, i, i
+    ));
+  }
+  s.push_str("\n}");
+  s
+}
+
+/// Builds a standard JSON document with `n` members.
+fn json_document(n: usize) -> String {
+  let mut s = String::from("{\n");
+  for i in 0..n {
+    if i > 0 {
+      s.push_str(",\n");
+    }
+    s.push_str(&format!(
+      "  \"item{}\" : {{ \"id\" : {}, \"name\" : \"name \\\"{}\\\"\", \"score\" : {}.5, \
+       \"tags\" : [\"a\", \"b\", null, true], \"pos\" : [{}, -{}] }}",
+      i, i, i, i, i, i
+    ));
+  }
+  s.push_str("\n}");
+  s
+}
+
+fn bench_yojson(c: &mut Criterion) {
+  let text = yojson_document(1000);
+  assert_eq!(
+    legacy::parse(&text).unwrap(),
+    yojson_rs::parser::parse(&text).unwrap()
+  );
+  let mut group = c.benchmark_group("yojson");
+  group.bench_function("hand-written", |b| {
+    b.iter(|| yojson_rs::parser::parse(black_box(&text)).unwrap())
+  });
+  group.bench_function("borrowed", |b| {
+    b.iter(|| yojson_rs::parser::parse_borrowed(black_box(&text)).unwrap())
+  });
+  group.bench_function("pest", |b| {
+    b.iter(|| legacy::parse(black_box(&text)).unwrap())
+  });
+  group.finish();
+}
+
+fn bench_json(c: &mut Criterion) {
+  let text = json_document(1000);
+  let mut group = c.benchmark_group("json");
+  group.bench_function("hand-written", |b| {
+    b.iter(|| yojson_rs::parser::parse(black_box(&text)).unwrap())
+  });
+  group.bench_function("pest", |b| {
+    b.iter(|| legacy::parse(black_box(&text)).unwrap())
+  });
+  group.bench_function("serde_json", |b| {
+    b.iter(|| serde_json::from_str::<serde_json::Value>(black_box(&text)).unwrap())
+  });
+  group.finish();
+}
+
+criterion_group!(benches, bench_yojson, bench_json);
+criterion_main!(benches);
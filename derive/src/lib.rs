@@ -0,0 +1,372 @@
+//! Derive macros of `yojson_rs::convert::ToYojson` and `yojson_rs::convert::FromYojson`.
+//!
+//! `YojsonSerialize` implements `ToYojson`, and `YojsonDeserialize` implements `FromYojson`.
+//! The encoding is chosen by the container attribute `#[yojson(encoding = "...")]`:
+//!
+//! | Rust                 | `"yojson"` (default), as `Yojson.Safe` | `"ppx"`, as `ppx_deriving_yojson` |
+//! |----------------------|----------------------------------------|-----------------------------------|
+//! | `struct S { a: T }`  | `{"a": t}`                             | `{"a": t}`                        |
+//! | `struct S(A, B)`     | `(a, b)`                               | `[a, b]`                          |
+//! | `struct S(A)`        | `a`                                    | `a`                               |
+//! | `struct S`           | `null`                                 | `null`                            |
+//! | `E::C`               | `<"C">`                                | `["C"]`                           |
+//! | `E::C(A)`            | `<"C": a>`                             | `["C", a]`                        |
+//! | `E::C(A, B)`         | `<"C": (a, b)>`                        | `["C", a, b]`                     |
+//! | `E::C { a: T }`      | `<"C": {"a": t}>`                      | `["C", {"a": t}]`                 |
+//!
+//! `Option` is `null` or the value in both encodings. With `"ppx"`, the values of fields and
+//! arguments are converted to standard JSON by `yojson_rs::convert::to_std`, so tuples are
+//! written as arrays.
+//!
+//! Other attributes:
+//! - `#[yojson(name = "...")]` on a variant renames the constructor, as `[@name]`.
+//! - `#[yojson(key = "...")]` on a field renames the member, as `[@key]`.
+//! - `#[yojson(default)]` on a field reads a missing member as `Default::default()`, as `[@default]`.
+
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{
+  parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, Generics, Ident,
+  LitStr, Result,
+};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Encoding {
+  Yojson,
+  Ppx,
+}
+
+#[derive(Default)]
+struct Attrs {
+  encoding: Option<Encoding>,
+  name: Option<String>,
+  key: Option<String>,
+  default: bool,
+}
+
+fn attrs(attributes: &[Attribute]) -> Result<Attrs> {
+  let mut attrs = Attrs::default();
+  for attribute in attributes.iter().filter(|a| a.path().is_ident("yojson")) {
+    attribute.parse_nested_meta(|meta| {
+      if meta.path.is_ident("encoding") {
+        let value = meta.value()?.parse::<LitStr>()?;
+        attrs.encoding = Some(match value.value().as_str() {
+          "yojson" => Encoding::Yojson,
+          "ppx" => Encoding::Ppx,
+          _ => return Err(Error::new(value.span(), "expected \"yojson\" or \"ppx\"")),
+        });
+      } else if meta.path.is_ident("name") {
+        attrs.name = Some(meta.value()?.parse::<LitStr>()?.value());
+      } else if meta.path.is_ident("key") {
+        attrs.key = Some(meta.value()?.parse::<LitStr>()?.value());
+      } else if meta.path.is_ident("default") {
+        attrs.default = true;
+      } else {
+        return Err(meta.error("unknown yojson attribute"));
+      }
+      Ok(())
+    })?;
+  }
+  Ok(attrs)
+}
+
+/// Adds the bound `trait_` to every type parameter.
+fn bounded(generics: &Generics, trait_: TokenStream) -> Generics {
+  let mut generics = generics.clone();
+  for param in generics.type_params_mut() {
+    param.bounds.push(parse_quote!(#trait_));
+  }
+  generics
+}
+
+/// Member names of named fields.
+fn keys(fields: &syn::FieldsNamed) -> Result<Vec<(Ident, String, bool)>> {
+  fields
+    .named
+    .iter()
+    .map(|field| {
+      let ident = field.ident.clone().unwrap();
+      let attrs = attrs(&field.attrs)?;
+      let key = attrs
+        .key
+        .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+      Ok((ident, key, attrs.default))
+    })
+    .collect()
+}
+
+fn write_value(encoding: Encoding, x: TokenStream) -> TokenStream {
+  match encoding {
+    Encoding::Yojson => quote!(::yojson_rs::convert::ToYojson::to_yojson(#x)),
+    Encoding::Ppx => {
+      quote!(::yojson_rs::convert::to_std(::yojson_rs::convert::ToYojson::to_yojson(#x)))
+    }
+  }
+}
+
+/// Binding of a field in a pattern, which cannot clash with the locals of the generated code.
+fn binding(ident: &Ident) -> Ident {
+  format_ident!("__yojson_field_{}", ident.unraw())
+}
+
+/// Writes named fields as an assoc. `field` returns a reference to a field.
+fn write_assoc(
+  encoding: Encoding,
+  keys: &[(Ident, String, bool)],
+  field: impl Fn(&Ident) -> TokenStream,
+) -> TokenStream {
+  let inserts = keys.iter().map(|(ident, key, _)| {
+    let value = write_value(encoding, field(ident));
+    quote!(__yojson_assoc.insert(#key.to_string(), #value);)
+  });
+  quote!({
+    #[allow(unused_mut)]
+    let mut __yojson_assoc = ::std::collections::HashMap::new();
+    #(#inserts)*
+    ::yojson_rs::value::Value::Assoc(__yojson_assoc)
+  })
+}
+
+/// Reads named fields from the assoc `v` into the constructor `path`.
+///
+/// `outer` is applied to every error, to locate it in the parent.
+fn read_assoc(
+  path: TokenStream,
+  keys: &[(Ident, String, bool)],
+  outer: &TokenStream,
+) -> TokenStream {
+  let fields = keys.iter().map(|(ident, key, default)| {
+    let missing = if *default {
+      quote!(::std::default::Default::default())
+    } else {
+      quote!(return Err(::yojson_rs::convert::ConvertError::missing(#key)) #outer)
+    };
+    quote!(#ident: match assoc.get(#key) {
+      Some(v) => ::yojson_rs::convert::FromYojson::from_yojson(v).map_err(|e| e.at(#key)) #outer?,
+      None => #missing,
+    })
+  });
+  quote!({
+    #[allow(unused_variables)]
+    let assoc = ::yojson_rs::convert::expect_assoc(v) #outer?;
+    Ok(#path { #(#fields),* })
+  })
+}
+
+/// Reads `len` elements of the tuple, or array, `v` into the constructor `path`.
+fn read_tuple(path: TokenStream, len: usize, outer: &TokenStream) -> TokenStream {
+  let elements = (0..len).map(
+    |i| quote!(::yojson_rs::convert::FromYojson::from_yojson(&t[#i]).map_err(|e| e.at(#i)) #outer?),
+  );
+  quote!({
+    let t = ::yojson_rs::convert::expect_tuple(v, #len) #outer?;
+    Ok(#path(#(#elements),*))
+  })
+}
+
+fn serialize(input: &DeriveInput) -> Result<TokenStream> {
+  let encoding = attrs(&input.attrs)?.encoding.unwrap_or(Encoding::Yojson);
+  let std = encoding == Encoding::Ppx;
+  let body = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => write_assoc(encoding, &keys(fields)?, |ident| quote!(&self.#ident)),
+      Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+        write_value(encoding, quote!(&self.0))
+      }
+      Fields::Unnamed(fields) => {
+        let values = (0..fields.unnamed.len()).map(|i| {
+          let i = syn::Index::from(i);
+          write_value(encoding, quote!(&self.#i))
+        });
+        if std {
+          quote!(::yojson_rs::value::Value::Array(vec![#(#values),*]))
+        } else {
+          quote!(::yojson_rs::value::Value::Tuple(vec![#(#values),*]))
+        }
+      }
+      Fields::Unit => quote!(::yojson_rs::value::Value::Null),
+    },
+    Data::Enum(data) => {
+      let mut arms = Vec::new();
+      for variant in &data.variants {
+        let ident = &variant.ident;
+        let name = attrs(&variant.attrs)?
+          .name
+          .unwrap_or_else(|| ident.to_string());
+        let (pattern, arguments) = match &variant.fields {
+          Fields::Unit => (quote!(Self::#ident), Vec::new()),
+          Fields::Unnamed(fields) => {
+            let xs = (0..fields.unnamed.len())
+              .map(|i| format_ident!("x{}", i))
+              .collect::<Vec<_>>();
+            let values = xs
+              .iter()
+              .map(|x| write_value(encoding, quote!(#x)))
+              .collect();
+            (quote!(Self::#ident(#(#xs),*)), values)
+          }
+          Fields::Named(fields) => {
+            let keys = keys(fields)?;
+            let bindings = keys.iter().map(|(ident, _, _)| {
+              let binding = binding(ident);
+              quote!(#ident: #binding)
+            });
+            let assoc = write_assoc(encoding, &keys, |ident| {
+              let binding = binding(ident);
+              quote!(#binding)
+            });
+            (quote!(Self::#ident { #(#bindings),* }), vec![assoc])
+          }
+        };
+        let value = match (encoding, arguments.len()) {
+          (Encoding::Ppx, _) => quote!(::yojson_rs::value::Value::Array(vec![
+            ::yojson_rs::value::Value::String(#name.to_string()),
+            #(#arguments),*
+          ])),
+          (Encoding::Yojson, 0) => quote!(::yojson_rs::convert::write_variant(#name, None, false)),
+          (Encoding::Yojson, 1) => quote!(::yojson_rs::convert::write_variant(
+            #name,
+            Some(#(#arguments)*),
+            false
+          )),
+          (Encoding::Yojson, _) => quote!(::yojson_rs::convert::write_variant(
+            #name,
+            Some(::yojson_rs::value::Value::Tuple(vec![#(#arguments),*])),
+            false
+          )),
+        };
+        arms.push(quote!(#pattern => #value,));
+      }
+      quote!(match self { #(#arms)* })
+    }
+    Data::Union(_) => return Err(Error::new(Span::call_site(), "unions are not supported")),
+  };
+  let ident = &input.ident;
+  let generics = bounded(&input.generics, quote!(::yojson_rs::convert::ToYojson));
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  Ok(quote! {
+    impl #impl_generics ::yojson_rs::convert::ToYojson for #ident #ty_generics #where_clause {
+      fn to_yojson(&self) -> ::yojson_rs::value::Value {
+        #body
+      }
+    }
+  })
+}
+
+fn deserialize(input: &DeriveInput) -> Result<TokenStream> {
+  let encoding = attrs(&input.attrs)?.encoding.unwrap_or(Encoding::Yojson);
+  let payload = quote!(.map_err(|e| e.at(::yojson_rs::pointer::PAYLOAD)));
+  let body = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => read_assoc(quote!(Self), &keys(fields)?, &quote!()),
+      Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+        quote!(::yojson_rs::convert::FromYojson::from_yojson(v).map(Self))
+      }
+      Fields::Unnamed(fields) => read_tuple(quote!(Self), fields.unnamed.len(), &quote!()),
+      Fields::Unit => {
+        quote!(<() as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(|()| Self))
+      }
+    },
+    Data::Enum(data) => {
+      let mut arms = Vec::new();
+      for variant in &data.variants {
+        let ident = &variant.ident;
+        let name = attrs(&variant.attrs)?
+          .name
+          .unwrap_or_else(|| ident.to_string());
+        let arm = match (encoding, &variant.fields) {
+          (_, Fields::Unit) => quote!((#name, []) => Ok(Self::#ident),),
+          (Encoding::Ppx, Fields::Unnamed(fields)) => {
+            let xs = (0..fields.unnamed.len())
+              .map(|i| format_ident!("x{}", i))
+              .collect::<Vec<_>>();
+            let reads = xs.iter().enumerate().map(|(i, x)| {
+              let index = i + 1;
+              quote!(::yojson_rs::convert::FromYojson::from_yojson(#x).map_err(|e| e.at(#index))?)
+            });
+            quote!((#name, [#(#xs),*]) => Ok(Self::#ident(#(#reads),*)),)
+          }
+          (Encoding::Ppx, Fields::Named(fields)) => {
+            let outer = quote!(.map_err(|e| e.at(1)));
+            let read = read_assoc(quote!(Self::#ident), &keys(fields)?, &outer);
+            quote!((#name, [v]) => #read,)
+          }
+          (Encoding::Yojson, fields) => {
+            let read = match fields {
+              Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                quote!(::yojson_rs::convert::FromYojson::from_yojson(v).map(Self::#ident) #payload)
+              }
+              Fields::Unnamed(fields) => {
+                read_tuple(quote!(Self::#ident), fields.unnamed.len(), &payload)
+              }
+              Fields::Named(fields) => read_assoc(quote!(Self::#ident), &keys(fields)?, &payload),
+              Fields::Unit => unreachable!(),
+            };
+            quote!((#name, [v]) => #read,)
+          }
+        };
+        arms.push(arm);
+      }
+      let split = match encoding {
+        Encoding::Yojson => quote!(
+          let (name, argument) = ::yojson_rs::convert::read_variant(v)?;
+          let arguments = argument.map(::std::slice::from_ref).unwrap_or(&[]);
+        ),
+        Encoding::Ppx => quote!(
+          let (name, arguments) = match v {
+            ::yojson_rs::value::Value::Array(elements) => match elements.split_first() {
+              Some((::yojson_rs::value::Value::String(name), arguments)) => (name.as_str(), arguments),
+              _ => return Err(::yojson_rs::convert::ConvertError::expected("variant", v)),
+            },
+            _ => return Err(::yojson_rs::convert::ConvertError::expected("variant", v)),
+          };
+        ),
+      };
+      quote!(
+        #split
+        match (name, arguments) {
+          #(#arms)*
+          (name, _) => Err(::yojson_rs::convert::ConvertError::new(format!(
+            "unexpected constructor \"{}\" with {} arguments",
+            name,
+            arguments.len()
+          ))),
+        }
+      )
+    }
+    Data::Union(_) => return Err(Error::new(Span::call_site(), "unions are not supported")),
+  };
+  let ident = &input.ident;
+  let generics = bounded(&input.generics, quote!(::yojson_rs::convert::FromYojson));
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  Ok(quote! {
+    impl #impl_generics ::yojson_rs::convert::FromYojson for #ident #ty_generics #where_clause {
+      fn from_yojson(
+        v: &::yojson_rs::value::Value,
+      ) -> ::std::result::Result<Self, ::yojson_rs::convert::ConvertError> {
+        #body
+      }
+    }
+  })
+}
+
+/// Derives `yojson_rs::convert::ToYojson`.
+#[proc_macro_derive(YojsonSerialize, attributes(yojson))]
+pub fn derive_serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  serialize(&input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+/// Derives `yojson_rs::convert::FromYojson`.
+#[proc_macro_derive(YojsonDeserialize, attributes(yojson))]
+pub fn derive_deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  deserialize(&input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
@@ -0,0 +1,204 @@
+//! Definition of typed accessors of nested values.
+//!
+//! A path is a member name, such as `"timeout"`, or a sequence of segments, such as
+//! `&["servers", "0", "port"]`. Each segment is a member name of an assoc, an index of an array
+//! or a tuple, or `-payload` for the argument of a variant, as the tokens of `pointer`.
+//!
+//! The accessors distinguish a missing value from a value of the wrong type:
+//!
+//! ```
+//! use yojson_rs::access::AccessError;
+//! let config = yojson_rs::parser::parse("{server : {timeout : \"30\"}}").unwrap();
+//! assert_eq!(config.get_i64_or(&["server", "retries"], 3), Ok(3));
+//! assert!(matches!(config.get_i64_or(&["server", "timeout"], 30), Err(AccessError::WrongType(_))));
+//! ```
+//!
+//! Values are converted by `convert::FromYojson`, so a float can be read from an integer,
+//! which loses precision beyond 2^53, but an integer is never read from a float.
+
+use std::fmt;
+
+use super::convert::{ConvertError, FromYojson};
+use super::pointer;
+use super::value::Value;
+
+/// Error of a typed access.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum AccessError {
+  /// Nothing is found at the path. Holds the JSON pointer of the first missing value.
+  Missing(String),
+  /// A value on the path has an unexpected type. The path of the error is a JSON pointer.
+  WrongType(ConvertError),
+}
+
+impl AccessError {
+  /// Tests whether nothing is found at the path.
+  pub fn is_missing(&self) -> bool {
+    matches!(self, AccessError::Missing(_))
+  }
+}
+
+impl fmt::Display for AccessError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      AccessError::Missing(pointer) => write!(f, "\"{}\" is missing", pointer),
+      AccessError::WrongType(error) => write!(f, "{}", error),
+    }
+  }
+}
+
+impl std::error::Error for AccessError {}
+
+/// Path of a nested value.
+pub trait Path {
+  /// Returns the segments of the path.
+  fn segments(&self) -> Vec<&str>;
+}
+
+impl Path for str {
+  fn segments(&self) -> Vec<&str> {
+    vec![self]
+  }
+}
+
+impl<S: AsRef<str>> Path for [S] {
+  fn segments(&self) -> Vec<&str> {
+    self.iter().map(AsRef::as_ref).collect()
+  }
+}
+
+impl<S: AsRef<str>, const N: usize> Path for [S; N] {
+  fn segments(&self) -> Vec<&str> {
+    self.iter().map(AsRef::as_ref).collect()
+  }
+}
+
+impl<S: AsRef<str>> Path for Vec<S> {
+  fn segments(&self) -> Vec<&str> {
+    self.iter().map(AsRef::as_ref).collect()
+  }
+}
+
+/// Prefixes the path of an error with the segments.
+pub(crate) fn at(error: ConvertError, segments: &[&str]) -> ConvertError {
+  segments
+    .iter()
+    .rev()
+    .fold(error, |error, segment| error.at(segment))
+}
+
+/// Creates an error of the last segment applied to a value which it cannot descend into.
+pub(crate) fn cannot_apply(found: &str, segments: &[&str]) -> AccessError {
+  let (segment, parents) = segments.split_last().unwrap();
+  let error = ConvertError::new(format!(
+    "cannot apply \"{}\" to a value of type {}",
+    segment, found
+  ));
+  AccessError::WrongType(at(error, parents))
+}
+
+impl Value {
+  /// Looks up a nested value.
+  ///
+  /// It fails with `AccessError::WrongType` if a segment is applied to a value which it cannot
+  /// descend into, such as a member name to an integer.
+  pub fn get_path<P: Path + ?Sized>(&self, path: &P) -> Result<&Value, AccessError> {
+    let segments = path.segments();
+    let mut value = self;
+    for (i, segment) in segments.iter().enumerate() {
+      let child = match (value, pointer::index(segment)) {
+        (Value::Assoc(assoc), _) => assoc.get(*segment),
+        (Value::Array(elements), Some(index)) | (Value::Tuple(elements), Some(index)) => {
+          elements.get(index)
+        }
+        (Value::Variant((_, argument)), _) if *segment == pointer::PAYLOAD => argument.as_deref(),
+        _ => return Err(cannot_apply(value.type_str(), &segments[..=i])),
+      };
+      value = child.ok_or_else(|| AccessError::Missing(pointer::join(&segments[..=i])))?;
+    }
+    Ok(value)
+  }
+
+  /// Looks up a nested value mutably.
+  pub fn get_path_mut<P: Path + ?Sized>(&mut self, path: &P) -> Result<&mut Value, AccessError> {
+    let segments = path.segments();
+    let mut value = self;
+    for (i, segment) in segments.iter().enumerate() {
+      let found = value.type_str();
+      let child = match (value, pointer::index(segment)) {
+        (Value::Assoc(assoc), _) => assoc.get_mut(*segment),
+        (Value::Array(elements), Some(index)) | (Value::Tuple(elements), Some(index)) => {
+          elements.get_mut(index)
+        }
+        (Value::Variant((_, argument)), _) if *segment == pointer::PAYLOAD => {
+          argument.as_deref_mut()
+        }
+        _ => return Err(cannot_apply(found, &segments[..=i])),
+      };
+      value = child.ok_or_else(|| AccessError::Missing(pointer::join(&segments[..=i])))?;
+    }
+    Ok(value)
+  }
+
+  /// Converts a nested value.
+  ///
+  /// ```
+  /// let config = yojson_rs::parser::parse("{ports : [80, 443]}").unwrap();
+  /// assert_eq!(config.get_as::<Vec<i64>, _>("ports"), Ok(vec![80, 443]));
+  /// ```
+  pub fn get_as<T: FromYojson, P: Path + ?Sized>(&self, path: &P) -> Result<T, AccessError> {
+    let value = self.get_path(path)?;
+    T::from_yojson(value).map_err(|error| AccessError::WrongType(at(error, &path.segments())))
+  }
+
+  /// Converts a nested value, or returns a default if it is missing or `null`.
+  pub fn get_or<T: FromYojson, P: Path + ?Sized>(
+    &self,
+    path: &P,
+    default: T,
+  ) -> Result<T, AccessError> {
+    match self.get_path(path) {
+      Ok(Value::Null) | Err(AccessError::Missing(_)) => Ok(default),
+      Ok(_) => self.get_as(path),
+      Err(error) => Err(error),
+    }
+  }
+
+  /// Reads a nested integer, or returns a default if it is missing or `null`.
+  pub fn get_i64_or<P: Path + ?Sized>(&self, path: &P, default: i64) -> Result<i64, AccessError> {
+    self.get_or(path, default)
+  }
+
+  /// Reads a nested float, or returns a default if it is missing or `null`.
+  ///
+  /// An integer is read as a float.
+  pub fn get_f64_or<P: Path + ?Sized>(&self, path: &P, default: f64) -> Result<f64, AccessError> {
+    self.get_or(path, default)
+  }
+
+  /// Reads a nested boolean, or returns a default if it is missing or `null`.
+  pub fn get_bool_or<P: Path + ?Sized>(
+    &self,
+    path: &P,
+    default: bool,
+  ) -> Result<bool, AccessError> {
+    self.get_or(path, default)
+  }
+
+  /// Reads a nested string, or returns a default if it is missing or `null`.
+  pub fn get_str_or<'a, P: Path + ?Sized>(
+    &'a self,
+    path: &P,
+    default: &'a str,
+  ) -> Result<&'a str, AccessError> {
+    match self.get_path(path) {
+      Ok(Value::Null) | Err(AccessError::Missing(_)) => Ok(default),
+      Ok(Value::String(s)) => Ok(s),
+      Ok(value) => Err(AccessError::WrongType(at(
+        ConvertError::expected("string", value),
+        &path.segments(),
+      ))),
+      Err(error) => Err(error),
+    }
+  }
+}
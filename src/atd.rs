@@ -0,0 +1,814 @@
+//! Definition of a parser of ATD type definitions, as read by atdgen.
+//!
+//! ```
+//! let module = yojson_rs::atd::parse(r#"
+//!   type mode = [ Dev | Prod of string ]
+//!   type config = {
+//!     port : int;
+//!     ?host : string option;
+//!     ~tags : string list;
+//!     mode : mode;
+//!   }
+//! "#).unwrap();
+//! assert_eq!(module.types.len(), 2);
+//! ```
+//!
+//! Supported are records, variants, tuples, `list`, `option`, `nullable`, the base types
+//! `unit`, `bool`, `int`, `float`, `string` and `abstract`, parametric types,
+//! and annotations such as `<json name="...">` and `<json repr="object">`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::parser::Position;
+use super::schema::{Additional, Node, Schema, Type};
+
+/// Error of an ATD definition.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct AtdError {
+  message: String,
+  position: Option<Position>,
+}
+
+impl AtdError {
+  pub(crate) fn new(message: impl Into<String>, position: Option<Position>) -> Self {
+    AtdError {
+      message: message.into(),
+      position,
+    }
+  }
+
+  /// Description of the error.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Position of the error in the ATD text, if any.
+  pub fn position(&self) -> Option<Position> {
+    self.position
+  }
+}
+
+impl fmt::Display for AtdError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.position {
+      Some(position) => write!(
+        f,
+        "{} at line {}, column {}",
+        self.message, position.line, position.column
+      ),
+      None => write!(f, "{}", self.message),
+    }
+  }
+}
+
+impl std::error::Error for AtdError {}
+
+/// Annotation such as `<json name="foo" repr="object">`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Annotation {
+  /// Section of the annotation, such as `json` or `ocaml`.
+  pub section: String,
+  /// Fields of the annotation, with their values.
+  pub fields: Vec<(String, Option<String>)>,
+}
+
+/// Finds the value of a field of an annotation.
+pub fn annotation<'a>(
+  annotations: &'a [Annotation],
+  section: &str,
+  field: &str,
+) -> Option<&'a str> {
+  annotations
+    .iter()
+    .filter(|a| a.section == section)
+    .flat_map(|a| a.fields.iter())
+    .find(|(name, _)| name == field)
+    .and_then(|(_, value)| value.as_deref())
+}
+
+/// Type expression with its annotations.
+#[derive(PartialEq, Clone, Debug)]
+pub struct TypeExpr {
+  /// Kind of the type.
+  pub kind: TypeKind,
+  /// Annotations of the type.
+  pub annotations: Vec<Annotation>,
+}
+
+/// Kind of a type expression.
+#[derive(PartialEq, Clone, Debug)]
+pub enum TypeKind {
+  /// Named type applied to arguments, including the base types: `int`, `(string, int) table`.
+  Name(String, Vec<TypeExpr>),
+  /// Type variable: `'a`.
+  Var(String),
+  /// `t list`
+  List(Box<TypeExpr>),
+  /// `t option`
+  Option(Box<TypeExpr>),
+  /// `t nullable`
+  Nullable(Box<TypeExpr>),
+  /// `t1 * t2`
+  Tuple(Vec<TypeExpr>),
+  /// `{ a : t; ... }`
+  Record(Vec<Field>),
+  /// `[ A | B of t ]`
+  Variant(Vec<Constructor>),
+}
+
+/// Kind of a field of a record.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FieldKind {
+  /// `name : t`
+  Required,
+  /// `?name : t option`, omitted when `None`.
+  Optional,
+  /// `~name : t`, taking a default value when missing.
+  WithDefault,
+}
+
+/// Field of a record.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Field {
+  /// Name of the field.
+  pub name: String,
+  /// Kind of the field.
+  pub kind: FieldKind,
+  /// Type of the field.
+  pub expr: TypeExpr,
+  /// Annotations of the field name.
+  pub annotations: Vec<Annotation>,
+}
+
+/// Constructor of a variant.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Constructor {
+  /// Name of the constructor.
+  pub name: String,
+  /// Type of the argument.
+  pub argument: Option<TypeExpr>,
+  /// Annotations of the constructor.
+  pub annotations: Vec<Annotation>,
+}
+
+/// Type definition: `type 'a name = expr`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct TypeDef {
+  /// Name of the type.
+  pub name: String,
+  /// Type parameters, without `'`.
+  pub params: Vec<String>,
+  /// Definition of the type.
+  pub expr: TypeExpr,
+  /// Annotations of the type name.
+  pub annotations: Vec<Annotation>,
+  /// Position of the definition.
+  pub position: Position,
+}
+
+/// ATD module: a list of type definitions.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Module {
+  /// Annotations at the top of the module.
+  pub annotations: Vec<Annotation>,
+  /// Type definitions.
+  pub types: Vec<TypeDef>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum Token {
+  Ident(String),
+  Var(String),
+  Str(String),
+  Punct(char),
+  Eof,
+}
+
+struct Parser<'a> {
+  text: &'a str,
+  pos: usize,
+  token: Token,
+  start: usize,
+}
+
+/// Parses an ATD module.
+pub fn parse(text: &str) -> Result<Module, AtdError> {
+  let mut parser = Parser {
+    text,
+    pos: 0,
+    token: Token::Eof,
+    start: 0,
+  };
+  parser.advance()?;
+  let annotations = parser.annotations()?;
+  let mut types = Vec::new();
+  while parser.token != Token::Eof {
+    types.push(parser.type_def()?);
+  }
+  let module = Module { annotations, types };
+  module.check()?;
+  Ok(module)
+}
+
+impl<'a> Parser<'a> {
+  fn position(&self, offset: usize) -> Position {
+    Position::new(self.text, offset)
+  }
+
+  fn error(&self, message: impl Into<String>) -> AtdError {
+    AtdError::new(message, Some(self.position(self.start)))
+  }
+
+  fn skip_trivia(&mut self) -> Result<(), AtdError> {
+    loop {
+      let rest = &self.text[self.pos..];
+      let trimmed = rest.trim_start();
+      self.pos += rest.len() - trimmed.len();
+      if !trimmed.starts_with("(*") {
+        return Ok(());
+      }
+      // Comments are nested as in OCaml.
+      let start = self.pos;
+      let mut depth = 0;
+      loop {
+        let rest = &self.text[self.pos..];
+        if rest.starts_with("(*") {
+          depth += 1;
+          self.pos += 2;
+        } else if rest.starts_with("*)") {
+          depth -= 1;
+          self.pos += 2;
+          if depth == 0 {
+            break;
+          }
+        } else if let Some(c) = rest.chars().next() {
+          self.pos += c.len_utf8();
+        } else {
+          return Err(AtdError::new(
+            "unterminated comment",
+            Some(self.position(start)),
+          ));
+        }
+      }
+    }
+  }
+
+  fn advance(&mut self) -> Result<(), AtdError> {
+    self.skip_trivia()?;
+    self.start = self.pos;
+    let rest = &self.text[self.pos..];
+    let ident_len = |s: &str| {
+      s.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '\''))
+        .unwrap_or(s.len())
+    };
+    self.token = match rest.chars().next() {
+      None => Token::Eof,
+      Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+        let len = ident_len(rest);
+        self.pos += len;
+        Token::Ident(rest[..len].to_string())
+      }
+      Some('\'') => {
+        let len = ident_len(&rest[1..]);
+        if len == 0 {
+          return Err(self.error("expected a type variable"));
+        }
+        self.pos += len + 1;
+        Token::Var(rest[1..len + 1].to_string())
+      }
+      Some('"') => {
+        let mut s = String::new();
+        let mut chars = rest[1..].char_indices();
+        loop {
+          match chars.next() {
+            Some((i, '"')) => {
+              self.pos += i + 2;
+              break;
+            }
+            Some((_, '\\')) => match chars.next() {
+              Some((_, 'n')) => s.push('\n'),
+              Some((_, 't')) => s.push('\t'),
+              Some((_, c)) => s.push(c),
+              None => return Err(self.error("unterminated string")),
+            },
+            Some((_, c)) => s.push(c),
+            None => return Err(self.error("unterminated string")),
+          }
+        }
+        Token::Str(s)
+      }
+      Some(c) if "=<>{}[]()*;:?~|,.".contains(c) => {
+        self.pos += 1;
+        Token::Punct(c)
+      }
+      Some(c) => return Err(self.error(format!("unexpected character '{}'", c))),
+    };
+    Ok(())
+  }
+
+  fn eat(&mut self, c: char) -> Result<bool, AtdError> {
+    if self.token == Token::Punct(c) {
+      self.advance()?;
+      Ok(true)
+    } else {
+      Ok(false)
+    }
+  }
+
+  fn expect(&mut self, c: char) -> Result<(), AtdError> {
+    if self.eat(c)? {
+      Ok(())
+    } else {
+      Err(self.error(format!("expected '{}'", c)))
+    }
+  }
+
+  fn ident(&mut self) -> Result<String, AtdError> {
+    match &self.token {
+      Token::Ident(name) => {
+        let name = name.clone();
+        self.advance()?;
+        Ok(name)
+      }
+      _ => Err(self.error("expected a name")),
+    }
+  }
+
+  fn annotations(&mut self) -> Result<Vec<Annotation>, AtdError> {
+    let mut annotations = Vec::new();
+    while self.eat('<')? {
+      let section = self.ident()?;
+      let mut fields = Vec::new();
+      while let Token::Ident(_) = self.token {
+        let name = self.ident()?;
+        let value = if self.eat('=')? {
+          match &self.token {
+            Token::Str(s) => {
+              let s = s.clone();
+              self.advance()?;
+              Some(s)
+            }
+            _ => return Err(self.error("expected a string")),
+          }
+        } else {
+          None
+        };
+        fields.push((name, value));
+      }
+      self.expect('>')?;
+      annotations.push(Annotation { section, fields });
+    }
+    Ok(annotations)
+  }
+
+  fn type_def(&mut self) -> Result<TypeDef, AtdError> {
+    let position = self.position(self.start);
+    if self.token != Token::Ident("type".to_string()) {
+      return Err(self.error("expected \"type\""));
+    }
+    self.advance()?;
+    let mut params = Vec::new();
+    if let Token::Var(var) = &self.token {
+      params.push(var.clone());
+      self.advance()?;
+    } else if self.eat('(')? {
+      loop {
+        match &self.token {
+          Token::Var(var) => {
+            params.push(var.clone());
+            self.advance()?;
+          }
+          _ => return Err(self.error("expected a type variable")),
+        }
+        if !self.eat(',')? {
+          break;
+        }
+      }
+      self.expect(')')?;
+    }
+    let name = self.ident()?;
+    let annotations = self.annotations()?;
+    self.expect('=')?;
+    let expr = self.type_expr()?;
+    Ok(TypeDef {
+      name,
+      params,
+      expr,
+      annotations,
+      position,
+    })
+  }
+
+  fn type_expr(&mut self) -> Result<TypeExpr, AtdError> {
+    let mut elements = vec![self.app_expr()?];
+    while self.eat('*')? {
+      elements.push(self.app_expr()?);
+    }
+    if elements.len() == 1 {
+      Ok(elements.pop().unwrap())
+    } else {
+      Ok(TypeExpr {
+        kind: TypeKind::Tuple(elements),
+        annotations: Vec::new(),
+      })
+    }
+  }
+
+  fn app_expr(&mut self) -> Result<TypeExpr, AtdError> {
+    let mut args = self.atom()?;
+    while let Token::Ident(name) = &self.token {
+      if name == "type" {
+        break;
+      }
+      let name = name.clone();
+      self.advance()?;
+      let kind = match (name.as_str(), args.len()) {
+        ("list", 1) => TypeKind::List(Box::new(args.pop().unwrap())),
+        ("option", 1) => TypeKind::Option(Box::new(args.pop().unwrap())),
+        ("nullable", 1) => TypeKind::Nullable(Box::new(args.pop().unwrap())),
+        ("list", _) | ("option", _) | ("nullable", _) => {
+          return Err(self.error(format!("\"{}\" takes one argument", name)))
+        }
+        _ => TypeKind::Name(name, args),
+      };
+      args = vec![TypeExpr {
+        kind,
+        annotations: self.annotations()?,
+      }];
+    }
+    match args.len() {
+      1 => Ok(args.pop().unwrap()),
+      _ => Err(self.error("expected a type name after the type arguments")),
+    }
+  }
+
+  /// Parses an atomic type, or the arguments `(t1, t2)` of a type name.
+  fn atom(&mut self) -> Result<Vec<TypeExpr>, AtdError> {
+    let kind = match self.token.clone() {
+      Token::Punct('(') => {
+        self.advance()?;
+        let mut exprs = vec![self.type_expr()?];
+        while self.eat(',')? {
+          exprs.push(self.type_expr()?);
+        }
+        self.expect(')')?;
+        if exprs.len() > 1 {
+          return Ok(exprs);
+        }
+        let mut expr = exprs.pop().unwrap();
+        expr.annotations.extend(self.annotations()?);
+        return Ok(vec![expr]);
+      }
+      Token::Var(var) => {
+        self.advance()?;
+        TypeKind::Var(var)
+      }
+      Token::Ident(name) => {
+        self.advance()?;
+        TypeKind::Name(name, Vec::new())
+      }
+      Token::Punct('{') => {
+        self.advance()?;
+        let mut fields = Vec::new();
+        while !self.eat('}')? {
+          fields.push(self.field()?);
+          if !self.eat(';')? {
+            self.expect('}')?;
+            break;
+          }
+        }
+        TypeKind::Record(fields)
+      }
+      Token::Punct('[') => {
+        self.advance()?;
+        self.eat('|')?;
+        let mut constructors = Vec::new();
+        while !self.eat(']')? {
+          let name = self.ident()?;
+          let annotations = self.annotations()?;
+          let argument = if self.token == Token::Ident("of".to_string()) {
+            self.advance()?;
+            Some(self.type_expr()?)
+          } else {
+            None
+          };
+          constructors.push(Constructor {
+            name,
+            argument,
+            annotations,
+          });
+          if !self.eat('|')? {
+            self.expect(']')?;
+            break;
+          }
+        }
+        TypeKind::Variant(constructors)
+      }
+      _ => return Err(self.error("expected a type")),
+    };
+    Ok(vec![TypeExpr {
+      kind,
+      annotations: self.annotations()?,
+    }])
+  }
+
+  fn field(&mut self) -> Result<Field, AtdError> {
+    let kind = if self.eat('?')? {
+      FieldKind::Optional
+    } else if self.eat('~')? {
+      FieldKind::WithDefault
+    } else {
+      FieldKind::Required
+    };
+    let name = self.ident()?;
+    let annotations = self.annotations()?;
+    self.expect(':')?;
+    let expr = self.type_expr()?;
+    if kind == FieldKind::Optional && !matches!(expr.kind, TypeKind::Option(_)) {
+      return Err(self.error(format!(
+        "optional field \"{}\" must have an option type",
+        name
+      )));
+    }
+    Ok(Field {
+      name,
+      kind,
+      expr,
+      annotations,
+    })
+  }
+}
+
+/// Base types of ATD.
+pub(crate) const BASE_TYPES: [&str; 6] = ["unit", "bool", "int", "float", "string", "abstract"];
+
+impl Module {
+  /// Finds a type definition by name.
+  pub fn get(&self, name: &str) -> Option<&TypeDef> {
+    self.types.iter().find(|def| def.name == name)
+  }
+
+  /// Checks that every type name is defined with the right number of arguments,
+  /// and that every type variable is a parameter.
+  fn check(&self) -> Result<(), AtdError> {
+    let mut arity = HashMap::new();
+    for def in &self.types {
+      if BASE_TYPES.contains(&def.name.as_str())
+        || arity.insert(&def.name, def.params.len()).is_some()
+      {
+        return Err(AtdError::new(
+          format!("type \"{}\" is defined twice", def.name),
+          Some(def.position),
+        ));
+      }
+    }
+    for def in &self.types {
+      let mut stack = vec![&def.expr];
+      while let Some(expr) = stack.pop() {
+        match &expr.kind {
+          TypeKind::Name(name, args) => {
+            let expected = if BASE_TYPES.contains(&name.as_str()) {
+              Some(0)
+            } else {
+              arity.get(name).copied()
+            };
+            match expected {
+              None => {
+                return Err(AtdError::new(
+                  format!("type \"{}\" is not defined", name),
+                  Some(def.position),
+                ))
+              }
+              Some(n) if n != args.len() => {
+                return Err(AtdError::new(
+                  format!("type \"{}\" takes {} arguments", name, n),
+                  Some(def.position),
+                ))
+              }
+              _ => stack.extend(args),
+            }
+          }
+          TypeKind::Var(var) if !def.params.contains(var) => {
+            return Err(AtdError::new(
+              format!("type variable '{} is not a parameter", var),
+              Some(def.position),
+            ))
+          }
+          TypeKind::Var(_) => (),
+          TypeKind::List(expr) | TypeKind::Option(expr) | TypeKind::Nullable(expr) => {
+            stack.push(expr)
+          }
+          TypeKind::Tuple(exprs) => stack.extend(exprs),
+          TypeKind::Record(fields) => stack.extend(fields.iter().map(|f| &f.expr)),
+          TypeKind::Variant(constructors) => {
+            stack.extend(constructors.iter().filter_map(|c| c.argument.as_ref()))
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Builds a schema of the Yojson encoding of a type.
+  ///
+  /// Every type without parameters becomes a definition of the schema.
+  /// Records accept unknown members as atdgen does.
+  pub fn to_schema(&self, root: &str) -> Result<Schema, AtdError> {
+    match self.get(root) {
+      Some(def) if def.params.is_empty() => (),
+      Some(_) => {
+        return Err(AtdError::new(
+          format!("type \"{}\" has parameters", root),
+          None,
+        ))
+      }
+      None => {
+        return Err(AtdError::new(
+          format!("type \"{}\" is not defined", root),
+          None,
+        ))
+      }
+    }
+    let mut definitions = HashMap::new();
+    for def in self.types.iter().filter(|def| def.params.is_empty()) {
+      definitions.insert(def.name.clone(), schema_node(self, &def.expr, 0)?);
+    }
+    let root = Node {
+      reference: Some(root.to_string()),
+      ..Node::default()
+    };
+    Ok(Schema { root, definitions })
+  }
+}
+
+/// Name of a field or a constructor in JSON.
+pub(crate) fn json_name<'a>(name: &'a str, annotations: &'a [Annotation]) -> &'a str {
+  annotation(annotations, "json", "name").unwrap_or(name)
+}
+
+/// Tests whether a list is written as an assoc with `<json repr="object">`.
+pub(crate) fn is_object_list(expr: &TypeExpr) -> bool {
+  annotation(&expr.annotations, "json", "repr") == Some("object")
+}
+
+/// Replaces type variables with arguments.
+pub(crate) fn substitute(expr: &TypeExpr, args: &HashMap<&str, &TypeExpr>) -> TypeExpr {
+  let kind = match &expr.kind {
+    TypeKind::Var(var) => match args.get(var.as_str()) {
+      Some(arg) => return (*arg).clone(),
+      None => TypeKind::Var(var.clone()),
+    },
+    TypeKind::Name(name, exprs) => TypeKind::Name(
+      name.clone(),
+      exprs.iter().map(|e| substitute(e, args)).collect(),
+    ),
+    TypeKind::List(e) => TypeKind::List(Box::new(substitute(e, args))),
+    TypeKind::Option(e) => TypeKind::Option(Box::new(substitute(e, args))),
+    TypeKind::Nullable(e) => TypeKind::Nullable(Box::new(substitute(e, args))),
+    TypeKind::Tuple(exprs) => TypeKind::Tuple(exprs.iter().map(|e| substitute(e, args)).collect()),
+    TypeKind::Record(fields) => TypeKind::Record(
+      fields
+        .iter()
+        .map(|field| Field {
+          expr: substitute(&field.expr, args),
+          ..field.clone()
+        })
+        .collect(),
+    ),
+    TypeKind::Variant(constructors) => TypeKind::Variant(
+      constructors
+        .iter()
+        .map(|constructor| Constructor {
+          argument: constructor.argument.as_ref().map(|e| substitute(e, args)),
+          ..constructor.clone()
+        })
+        .collect(),
+    ),
+  };
+  TypeExpr {
+    kind,
+    annotations: expr.annotations.clone(),
+  }
+}
+
+fn typed(t: Type) -> Node {
+  Node {
+    types: Some(vec![t]),
+    ..Node::default()
+  }
+}
+
+fn schema_node(module: &Module, expr: &TypeExpr, depth: usize) -> Result<Node, AtdError> {
+  if depth > 64 {
+    return Err(AtdError::new(
+      "too deep expansion of parametric types",
+      None,
+    ));
+  }
+  let node = |expr: &TypeExpr| schema_node(module, expr, depth + 1);
+  Ok(match &expr.kind {
+    TypeKind::Name(name, args) => match name.as_str() {
+      "unit" => typed(Type::Null),
+      "bool" => typed(Type::Boolean),
+      "int" => typed(Type::Integer),
+      "float" => typed(Type::Number),
+      "string" => typed(Type::String),
+      "abstract" => Node::default(),
+      _ if args.is_empty() => Node {
+        reference: Some(name.clone()),
+        ..Node::default()
+      },
+      _ => {
+        // Applications of parametric types are expanded, since schemas have no parameters.
+        let def = module.get(name).unwrap();
+        let args = def
+          .params
+          .iter()
+          .map(String::as_str)
+          .zip(args)
+          .collect::<HashMap<_, _>>();
+        node(&substitute(&def.expr, &args))?
+      }
+    },
+    TypeKind::Var(var) => {
+      return Err(AtdError::new(
+        format!("type variable '{} has no schema", var),
+        None,
+      ))
+    }
+    TypeKind::List(element) if is_object_list(expr) => match &element.kind {
+      TypeKind::Tuple(pair) if pair.len() == 2 => Node {
+        types: Some(vec![Type::Object]),
+        additional: Additional::Schema(Box::new(node(&pair[1])?)),
+        ..Node::default()
+      },
+      _ => {
+        return Err(AtdError::new(
+          "<json repr=\"object\"> requires a list of pairs",
+          None,
+        ))
+      }
+    },
+    TypeKind::List(element) => Node {
+      types: Some(vec![Type::Array]),
+      items: Some(Box::new(node(element)?)),
+      ..Node::default()
+    },
+    TypeKind::Option(element) => Node {
+      types: Some(vec![Type::Variant]),
+      constructors: Some(vec![
+        ("None".to_string(), None),
+        ("Some".to_string(), Some(node(element)?)),
+      ]),
+      ..Node::default()
+    },
+    TypeKind::Nullable(element) => Node {
+      any_of: vec![typed(Type::Null), node(element)?],
+      ..Node::default()
+    },
+    TypeKind::Tuple(elements) => Node {
+      types: Some(vec![Type::Tuple]),
+      prefix_items: Some(elements.iter().map(node).collect::<Result<_, _>>()?),
+      ..Node::default()
+    },
+    TypeKind::Record(fields) => {
+      let mut properties = Vec::new();
+      let mut required = Vec::new();
+      for field in fields {
+        let name = json_name(&field.name, &field.annotations).to_string();
+        let node = match (&field.kind, &field.expr.kind) {
+          (FieldKind::Optional, TypeKind::Option(element)) => node(element)?,
+          _ => node(&field.expr)?,
+        };
+        if field.kind == FieldKind::Required {
+          required.push(name.clone());
+        }
+        properties.push((name, node));
+      }
+      properties.sort_by(|a, b| a.0.cmp(&b.0));
+      Node {
+        types: Some(vec![Type::Object]),
+        properties,
+        required,
+        ..Node::default()
+      }
+    }
+    TypeKind::Variant(constructors) => {
+      let mut schemas = Vec::new();
+      for c in constructors {
+        let argument = match &c.argument {
+          Some(argument) => Some(node(argument)?),
+          None => None,
+        };
+        schemas.push((json_name(&c.name, &c.annotations).to_string(), argument));
+      }
+      schemas.sort_by(|a, b| a.0.cmp(&b.0));
+      Node {
+        types: Some(vec![Type::Variant]),
+        constructors: Some(schemas),
+        ..Node::default()
+      }
+    }
+  })
+}
@@ -0,0 +1,181 @@
+//! Command-line tool to format, validate and query Yojson files, as `ydump` of OCaml Yojson.
+
+use std::io::{Read, Write};
+use std::process::exit;
+
+use yojson_rs::parser::{self, ParseOptions};
+use yojson_rs::query::Query;
+use yojson_rs::value::Value;
+use yojson_rs::PrintOptions;
+
+const USAGE: &str = "\
+usage: yojson [OPTIONS] [FILE]...
+       yojson get [OPTIONS] PATH [FILE]...
+
+Reads Yojson documents from the files, or from the standard input if no file or `-` is given,
+and writes them to the standard output. Input may be UTF-8, UTF-16 or UTF-32, with or without
+a byte order mark.
+
+`get` writes the values selected by PATH instead, one per line. PATH is a query such as
+`.servers[0].port` or `.status<Active:>`, or a JSON pointer such as `/servers/0/port`.
+It exits with 3 if PATH selects nothing in a document.
+
+options:
+  --compact   write each value on one line
+  --pretty    write each value indented (default)
+  --std       write standard JSON
+  --check     validate only, and report the location of the first error
+  -r, --raw   write selected strings without quotes (get only)
+  -s          read a stream of documents from each input
+  -h, --help  print this help";
+
+/// Exit code of an invalid document.
+const INVALID: i32 = 1;
+/// Exit code of a wrong usage or an I/O error.
+const FAILURE: i32 = 2;
+/// Exit code of a path which selects nothing.
+const MISSING: i32 = 3;
+
+struct Args {
+  query: Option<Query>,
+  options: PrintOptions,
+  check: bool,
+  raw: bool,
+  stream: bool,
+  files: Vec<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+  let mut args = std::env::args().skip(1).peekable();
+  let get = args.peek().map(String::as_str) == Some("get");
+  if get {
+    args.next();
+  }
+  let mut std = false;
+  let mut pretty = true;
+  let mut check = false;
+  let mut raw = false;
+  let mut stream = false;
+  let mut operands = Vec::new();
+  let mut only_operands = false;
+  for arg in args {
+    match arg.as_str() {
+      _ if only_operands => operands.push(arg),
+      "--" => only_operands = true,
+      "--compact" => pretty = false,
+      "--pretty" => pretty = true,
+      "--std" => std = true,
+      "--check" if !get => check = true,
+      "-r" | "--raw" if get => raw = true,
+      "-s" => stream = true,
+      "-h" | "--help" => {
+        println!("{}", USAGE);
+        exit(0)
+      }
+      _ if arg.starts_with('-') && arg != "-" => return Err(format!("unknown option {}", arg)),
+      _ => operands.push(arg),
+    }
+  }
+  let query = if get {
+    if operands.is_empty() {
+      return Err("missing PATH".to_string());
+    }
+    let path = operands.remove(0);
+    let query = Query::parse(&path).map_err(|e| format!("invalid path {}: {}", path, e))?;
+    Some(query)
+  } else {
+    None
+  };
+  if operands.is_empty() {
+    operands.push("-".to_string());
+  }
+  Ok(Args {
+    query,
+    options: PrintOptions::new().pretty(pretty).std(std),
+    check,
+    raw,
+    stream,
+    files: operands,
+  })
+}
+
+fn read(file: &str) -> std::io::Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  if file == "-" {
+    std::io::stdin().read_to_end(&mut bytes)?;
+  } else {
+    bytes = std::fs::read(file)?;
+  }
+  Ok(bytes)
+}
+
+/// Reads the documents of a file, and reports an error as an exit code.
+fn documents(file: &str, name: &str, stream: bool) -> Result<Vec<Value>, i32> {
+  let bytes = read(file).map_err(|e| {
+    eprintln!("yojson: {}: {}", name, e);
+    FAILURE
+  })?;
+  let options = ParseOptions::new().allow_bom(true).detect_encoding(true);
+  let values = if stream {
+    parser::parse_stream_slice_with_options(&bytes, &options)
+  } else {
+    parser::parse_slice_with_options(&bytes, &options).map(|value| vec![value])
+  };
+  values.map_err(|e| {
+    eprintln!("yojson: {}: {}", name, e);
+    INVALID
+  })
+}
+
+fn main() {
+  let args = parse_args().unwrap_or_else(|message| {
+    eprintln!("yojson: {}\n\n{}", message, USAGE);
+    exit(FAILURE)
+  });
+  let stdout = std::io::stdout();
+  let mut out = stdout.lock();
+  let mut code = 0;
+  for file in &args.files {
+    let name = if file == "-" { "<stdin>" } else { file };
+    let values = match documents(file, name, args.stream) {
+      Ok(values) => values,
+      Err(FAILURE) => exit(FAILURE),
+      Err(e) => {
+        code = e;
+        continue;
+      }
+    };
+    if args.check {
+      continue;
+    }
+    for value in values {
+      let selected = match &args.query {
+        Some(query) => {
+          let selected = query.select(&value);
+          if selected.is_empty() && code == 0 {
+            code = MISSING;
+          }
+          selected.into_iter().cloned().collect()
+        }
+        None => vec![value],
+      };
+      for value in selected {
+        let s = match value {
+          Value::String(s) if args.raw => s,
+          value => match yojson_rs::to_string_with_options(value, &args.options) {
+            Ok(s) => s,
+            Err(e) => {
+              eprintln!("yojson: {}: {}", name, e);
+              code = INVALID;
+              continue;
+            }
+          },
+        };
+        if writeln!(out, "{}", s).is_err() {
+          exit(FAILURE)
+        }
+      }
+    }
+  }
+  exit(code)
+}
@@ -0,0 +1,472 @@
+//! Definition of the conversion between Yojson values and Biniou, the binary format of OCaml
+//! Biniou.
+//!
+//! Yojson values are encoded as follows:
+//! - `null` is a unit, booleans are bools, integers are svints, and floats are float64s.
+//! - Assocs are records, and variants are variants. Both identify names by their 31-bit hashes,
+//!   computed by `hash_name`.
+//! - Arrays are arrays, whose elements must have the same tag, and tuples are tuples.
+//!
+//! Decoding also accepts the other integers and floats, num variants and tables, which are
+//! read as arrays of assocs. Hashes are read back as names with the names given in
+//! `BiniouOptions`, and as `#` followed by 8 hexadecimal digits otherwise, as `bdump` writes
+//! them. Shared values are not supported.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::parser::DEFAULT_MAX_DEPTH;
+use super::pointer;
+use super::value::Value;
+
+const BOOL: u8 = 0;
+const INT8: u8 = 1;
+const INT16: u8 = 2;
+const INT32: u8 = 3;
+const INT64: u8 = 4;
+const INT128: u8 = 5;
+const FLOAT32: u8 = 11;
+const FLOAT64: u8 = 12;
+const UVINT: u8 = 16;
+const SVINT: u8 = 17;
+const STRING: u8 = 18;
+const ARRAY: u8 = 19;
+const TUPLE: u8 = 20;
+const RECORD: u8 = 21;
+const NUM_VARIANT: u8 = 22;
+const VARIANT: u8 = 23;
+const UNIT: u8 = 24;
+const TABLE: u8 = 25;
+const SHARED: u8 = 26;
+
+/// Computes the 31-bit hash of a name of a field or a constructor, as `Bi_io.hash_name`.
+///
+/// ```
+/// assert_eq!(yojson_rs::biniou::hash_name("foo"), 5097222);
+/// ```
+pub fn hash_name(name: &str) -> u32 {
+  let mut hash = 0u32;
+  for b in name.bytes() {
+    hash = hash.wrapping_mul(223).wrapping_add(b as u32);
+  }
+  hash & 0x7fff_ffff
+}
+
+/// Options of decoding.
+///
+/// Names are looked up by their hashes. Of names with the same hash, the last one is used.
+/// The nesting depth is limited to `parser::DEFAULT_MAX_DEPTH` by default.
+///
+/// ```
+/// use yojson_rs::biniou::BiniouOptions;
+/// let options = BiniouOptions::new().names(&["id", "name", "Active"]);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BiniouOptions {
+  pub(crate) names: HashMap<u32, String>,
+  pub(crate) max_depth: Option<usize>,
+}
+
+impl Default for BiniouOptions {
+  fn default() -> Self {
+    BiniouOptions {
+      names: HashMap::new(),
+      max_depth: Some(DEFAULT_MAX_DEPTH),
+    }
+  }
+}
+
+impl BiniouOptions {
+  /// Creates the default options, without names.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds names of fields and constructors.
+  pub fn names<S: AsRef<str>>(mut self, names: &[S]) -> Self {
+    for name in names {
+      let name = name.as_ref();
+      self.names.insert(hash_name(name), name.to_string());
+    }
+    self
+  }
+
+  /// Limits the nesting depth of records, arrays, tuples, variants and tables.
+  /// `None` disables the limit.
+  pub fn max_depth(mut self, limit: Option<usize>) -> Self {
+    self.max_depth = limit;
+    self
+  }
+}
+
+/// Error of encoding or decoding.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BiniouError {
+  path: String,
+  message: String,
+}
+
+impl BiniouError {
+  fn new(path: &str, message: impl Into<String>) -> Self {
+    BiniouError {
+      path: path.to_string(),
+      message: message.into(),
+    }
+  }
+
+  /// JSON pointer of the value.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Description of the error.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl fmt::Display for BiniouError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} at \"{}\"", self.message, self.path)
+  }
+}
+
+impl std::error::Error for BiniouError {}
+
+fn child(path: &str, token: &str) -> String {
+  format!("{}/{}", path, pointer::escape(token))
+}
+
+fn tag(value: &Value) -> u8 {
+  match value {
+    Value::Null => UNIT,
+    Value::Bool(_) => BOOL,
+    Value::Integer(_) => SVINT,
+    Value::Float(_) => FLOAT64,
+    Value::String(_) => STRING,
+    Value::Assoc(_) => RECORD,
+    Value::Array(_) => ARRAY,
+    Value::Tuple(_) => TUPLE,
+    Value::Variant(_) => VARIANT,
+  }
+}
+
+fn write_uvint(bytes: &mut Vec<u8>, mut n: u64) {
+  while n >= 0x80 {
+    bytes.push((n & 0x7f) as u8 | 0x80);
+    n >>= 7;
+  }
+  bytes.push(n as u8);
+}
+
+fn write_svint(bytes: &mut Vec<u8>, i: i64) {
+  let n = if i >= 0 {
+    (i as u64) << 1
+  } else {
+    ((!i as u64) << 1) | 1
+  };
+  write_uvint(bytes, n)
+}
+
+fn write_hash(bytes: &mut Vec<u8>, hash: u32, high: bool) {
+  let hash = if high { hash | 0x8000_0000 } else { hash };
+  bytes.extend_from_slice(&hash.to_be_bytes());
+}
+
+fn write_value(
+  bytes: &mut Vec<u8>,
+  value: &Value,
+  path: &str,
+  tagged: bool,
+) -> Result<(), BiniouError> {
+  if tagged {
+    bytes.push(tag(value));
+  }
+  match value {
+    Value::Null => bytes.push(0),
+    Value::Bool(b) => bytes.push(*b as u8),
+    Value::Integer(i) => write_svint(bytes, *i),
+    Value::Float(f) => bytes.extend_from_slice(&f.to_bits().to_be_bytes()),
+    Value::String(s) => {
+      write_uvint(bytes, s.len() as u64);
+      bytes.extend_from_slice(s.as_bytes());
+    }
+    Value::Assoc(assoc) => {
+      write_uvint(bytes, assoc.len() as u64);
+      let mut names = HashMap::new();
+      for (name, value) in assoc {
+        let path = child(path, name);
+        let hash = hash_name(name);
+        if let Some(other) = names.insert(hash, name) {
+          return Err(BiniouError::new(
+            &path,
+            format!("names {} and {} have the same hash", other, name),
+          ));
+        }
+        write_hash(bytes, hash, true);
+        write_value(bytes, value, &path, true)?;
+      }
+    }
+    Value::Array(elements) => {
+      write_uvint(bytes, elements.len() as u64);
+      if let Some(first) = elements.first() {
+        let first = tag(first);
+        bytes.push(first);
+        for (i, value) in elements.iter().enumerate() {
+          let path = child(path, &i.to_string());
+          if tag(value) != first {
+            return Err(BiniouError::new(
+              &path,
+              "elements of an array must have the same kind",
+            ));
+          }
+          write_value(bytes, value, &path, false)?;
+        }
+      }
+    }
+    Value::Tuple(elements) => {
+      write_uvint(bytes, elements.len() as u64);
+      for (i, value) in elements.iter().enumerate() {
+        write_value(bytes, value, &child(path, &i.to_string()), true)?;
+      }
+    }
+    Value::Variant((name, argument)) => {
+      write_hash(bytes, hash_name(name), argument.is_some());
+      if let Some(argument) = argument {
+        write_value(bytes, argument, &child(path, pointer::PAYLOAD), true)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Encodes a value to Biniou.
+///
+/// ```
+/// let value = yojson_rs::parser::parse("{id : 1}").unwrap();
+/// assert_eq!(yojson_rs::biniou::to_biniou(&value).unwrap(), b"\x15\x01\x80\x00\x5b\xdb\x11\x02");
+/// ```
+pub fn to_biniou(value: &Value) -> Result<Vec<u8>, BiniouError> {
+  let mut bytes = Vec::new();
+  write_value(&mut bytes, value, "", true)?;
+  Ok(bytes)
+}
+
+struct Decoder<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+  depth: usize,
+  options: &'a BiniouOptions,
+}
+
+impl<'a> Decoder<'a> {
+  fn error(&self, path: &str, message: impl fmt::Display) -> BiniouError {
+    BiniouError::new(path, format!("{} at byte {}", message, self.offset))
+  }
+
+  fn read(&mut self, n: usize, path: &str) -> Result<&'a [u8], BiniouError> {
+    if self.bytes.len() - self.offset < n {
+      return Err(self.error(path, "unexpected end of input"));
+    }
+    let bytes = &self.bytes[self.offset..self.offset + n];
+    self.offset += n;
+    Ok(bytes)
+  }
+
+  fn read_array<const N: usize>(&mut self, path: &str) -> Result<[u8; N], BiniouError> {
+    let mut array = [0; N];
+    array.copy_from_slice(self.read(N, path)?);
+    Ok(array)
+  }
+
+  fn read_u8(&mut self, path: &str) -> Result<u8, BiniouError> {
+    Ok(self.read(1, path)?[0])
+  }
+
+  fn read_uvint(&mut self, path: &str) -> Result<u64, BiniouError> {
+    let mut n = 0u64;
+    let mut shift = 0;
+    loop {
+      let b = self.read_u8(path)?;
+      if shift >= 64 || (shift == 63 && b & 0x7e != 0) {
+        return Err(self.error(path, "vint overflows 64 bits"));
+      }
+      n |= ((b & 0x7f) as u64) << shift;
+      if b & 0x80 == 0 {
+        return Ok(n);
+      }
+      shift += 7;
+    }
+  }
+
+  fn read_length(&mut self, path: &str) -> Result<usize, BiniouError> {
+    let n = self.read_uvint(path)?;
+    // Every element takes at least one byte, which bounds allocations by the input.
+    if n > (self.bytes.len() - self.offset) as u64 {
+      return Err(self.error(path, format!("length {} exceeds the input", n)));
+    }
+    Ok(n as usize)
+  }
+
+  fn read_hash(&mut self, path: &str) -> Result<(u32, bool), BiniouError> {
+    let hash = u32::from_be_bytes(self.read_array(path)?);
+    Ok((hash & 0x7fff_ffff, hash & 0x8000_0000 != 0))
+  }
+
+  fn name(&self, hash: u32) -> String {
+    match self.options.names.get(&hash) {
+      Some(name) => name.clone(),
+      None => format!("#{:08x}", hash),
+    }
+  }
+
+  fn read_value(&mut self, tag: Option<u8>, path: &str) -> Result<Value, BiniouError> {
+    let tag = match tag {
+      Some(tag) => tag,
+      None => self.read_u8(path)?,
+    };
+    if !matches!(tag, ARRAY | TUPLE | RECORD | NUM_VARIANT | VARIANT | TABLE) {
+      return self.read_tagged(tag, path);
+    }
+    if Some(self.depth) == self.options.max_depth {
+      return Err(self.error(path, "nesting depth exceeds the limit"));
+    }
+    self.depth += 1;
+    let value = self.read_tagged(tag, path);
+    self.depth -= 1;
+    value
+  }
+
+  fn read_tagged(&mut self, tag: u8, path: &str) -> Result<Value, BiniouError> {
+    Ok(match tag {
+      BOOL => match self.read_u8(path)? {
+        0 => Value::Bool(false),
+        1 => Value::Bool(true),
+        b => return Err(self.error(path, format!("invalid bool {}", b))),
+      },
+      INT8 => Value::Integer(self.read_u8(path)? as i8 as i64),
+      INT16 => Value::Integer(i16::from_be_bytes(self.read_array(path)?) as i64),
+      INT32 => Value::Integer(i32::from_be_bytes(self.read_array(path)?) as i64),
+      INT64 => Value::Integer(i64::from_be_bytes(self.read_array(path)?)),
+      FLOAT32 => Value::Float(f32::from_be_bytes(self.read_array(path)?) as f64),
+      FLOAT64 => Value::Float(f64::from_be_bytes(self.read_array(path)?)),
+      UVINT => {
+        let n = self.read_uvint(path)?;
+        if n > i64::MAX as u64 {
+          return Err(self.error(path, format!("uvint {} is out of the range of i64", n)));
+        }
+        Value::Integer(n as i64)
+      }
+      SVINT => {
+        let n = self.read_uvint(path)?;
+        let i = (n >> 1) as i64;
+        Value::Integer(if n & 1 == 0 { i } else { !i })
+      }
+      STRING => {
+        let n = self.read_length(path)?;
+        let bytes = self.read(n, path)?;
+        match std::str::from_utf8(bytes) {
+          Ok(s) => Value::String(s.to_string()),
+          Err(_) => return Err(self.error(path, "invalid UTF-8 in a string")),
+        }
+      }
+      ARRAY => {
+        let n = self.read_length(path)?;
+        let mut elements = Vec::with_capacity(n);
+        if n > 0 {
+          let tag = self.read_u8(path)?;
+          for i in 0..n {
+            elements.push(self.read_value(Some(tag), &child(path, &i.to_string()))?);
+          }
+        }
+        Value::Array(elements)
+      }
+      TUPLE => {
+        let n = self.read_length(path)?;
+        let mut elements = Vec::with_capacity(n);
+        for i in 0..n {
+          elements.push(self.read_value(None, &child(path, &i.to_string()))?);
+        }
+        Value::Tuple(elements)
+      }
+      RECORD => {
+        let n = self.read_length(path)?;
+        let mut assoc = HashMap::with_capacity(n);
+        for _ in 0..n {
+          let (hash, _) = self.read_hash(path)?;
+          let name = self.name(hash);
+          let value = self.read_value(None, &child(path, &name))?;
+          assoc.insert(name, value);
+        }
+        Value::Assoc(assoc)
+      }
+      NUM_VARIANT => {
+        let b = self.read_u8(path)?;
+        let name = format!("#{}", b & 0x7f);
+        let argument = if b & 0x80 != 0 {
+          Some(Box::new(
+            self.read_value(None, &child(path, pointer::PAYLOAD))?,
+          ))
+        } else {
+          None
+        };
+        Value::Variant((name, argument))
+      }
+      VARIANT => {
+        let (hash, has_argument) = self.read_hash(path)?;
+        let name = self.name(hash);
+        let argument = if has_argument {
+          Some(Box::new(
+            self.read_value(None, &child(path, pointer::PAYLOAD))?,
+          ))
+        } else {
+          None
+        };
+        Value::Variant((name, argument))
+      }
+      UNIT => match self.read_u8(path)? {
+        0 => Value::Null,
+        b => return Err(self.error(path, format!("invalid unit {}", b))),
+      },
+      TABLE => {
+        let rows = self.read_length(path)?;
+        let mut elements = Vec::with_capacity(rows);
+        if rows > 0 {
+          let n = self.read_length(path)?;
+          let mut columns = Vec::with_capacity(n);
+          for _ in 0..n {
+            let (hash, _) = self.read_hash(path)?;
+            columns.push((self.name(hash), self.read_u8(path)?));
+          }
+          for i in 0..rows {
+            let path = child(path, &i.to_string());
+            let mut assoc = HashMap::with_capacity(n);
+            for (name, tag) in &columns {
+              let value = self.read_value(Some(*tag), &child(&path, name))?;
+              assoc.insert(name.clone(), value);
+            }
+            elements.push(Value::Assoc(assoc));
+          }
+        }
+        Value::Array(elements)
+      }
+      INT128 | SHARED => return Err(self.error(path, format!("unsupported tag {}", tag))),
+      tag => return Err(self.error(path, format!("unknown tag {}", tag))),
+    })
+  }
+}
+
+/// Decodes a value from Biniou.
+pub fn from_biniou(bytes: &[u8], options: &BiniouOptions) -> Result<Value, BiniouError> {
+  let mut decoder = Decoder {
+    bytes,
+    offset: 0,
+    depth: 0,
+    options,
+  };
+  let value = decoder.read_value(None, "")?;
+  if decoder.offset < bytes.len() {
+    return Err(decoder.error("", "trailing bytes"));
+  }
+  Ok(value)
+}
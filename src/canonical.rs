@@ -0,0 +1,211 @@
+//! Definition of the canonical form of values, for hashing and signing.
+//!
+//! The canonical form follows RFC 8785 (JSON Canonicalization Scheme), extended for Yojson:
+//! - There is no whitespace, and names of members and constructors are always quoted.
+//! - Members are sorted by the UTF-16 code units of their names.
+//! - Strings escape only `"`, `\` and control characters, with `\b`, `\t`, `\n`, `\f`, `\r`
+//!   or `\u00xx`.
+//! - Integers are written in decimal. Floats are written as ECMAScript `Number.prototype.toString`,
+//!   with `.0` added to an integral mantissa, such as `1.0` and `1.0e+21`, so that they are read
+//!   back as floats. `-0.0` is written as `0.0`, and non-finite floats as `NaN`, `Infinity` and
+//!   `-Infinity`.
+//! - Tuples are `(a,b)`, and variants are `<"Name">` and `<"Name":x>`.
+//!
+//! Values which are equal by `diff::diff` have the same canonical form.
+
+use super::value::Value;
+
+/// Writes a float as ECMAScript `Number.prototype.toString`, keeping it a float.
+fn float_to_string(f: f64) -> String {
+  if f.is_nan() {
+    return "NaN".to_string();
+  } else if f.is_infinite() {
+    return if f > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+  } else if f == 0.0 {
+    return "0.0".to_string();
+  }
+  // `{:e}` writes the shortest digits which are read back as the same float.
+  let exp = format!("{:e}", f.abs());
+  let (mantissa, e) = exp.split_once('e').unwrap();
+  let digits = mantissa.replace('.', "");
+  let k = digits.len() as i32;
+  let n = e.parse::<i32>().unwrap() + 1;
+  let mut s = String::new();
+  if f < 0.0 {
+    s.push('-');
+  }
+  if k <= n && n <= 21 {
+    s.push_str(&digits);
+    s.push_str(&"0".repeat((n - k) as usize));
+    s.push_str(".0");
+  } else if 0 < n && n <= 21 {
+    s.push_str(&digits[..n as usize]);
+    s.push('.');
+    s.push_str(&digits[n as usize..]);
+  } else if -6 < n && n <= 0 {
+    s.push_str("0.");
+    s.push_str(&"0".repeat(-n as usize));
+    s.push_str(&digits);
+  } else {
+    s.push_str(&digits[..1]);
+    s.push('.');
+    s.push_str(if k > 1 { &digits[1..] } else { "0" });
+    s.push('e');
+    if n > 0 {
+      s.push('+');
+    }
+    s.push_str(&(n - 1).to_string());
+  }
+  s
+}
+
+/// Converts a value to its canonical form.
+///
+/// ```
+/// let a = yojson_rs::parser::parse("{b : 1.0, a : [1.0e21, 0.5]}").unwrap();
+/// assert_eq!(yojson_rs::canonical::to_string(&a), r#"{"a":[1.0e+21,0.5],"b":1.0}"#);
+/// ```
+pub fn to_string(value: &Value) -> String {
+  enum Task<'a> {
+    Value(&'a Value),
+    Str(&'static str),
+    Name(&'a str),
+  }
+  let mut s = String::new();
+  let mut tasks = vec![Task::Value(value)];
+  while let Some(task) = tasks.pop() {
+    let value = match task {
+      Task::Value(value) => value,
+      Task::Str(str) => {
+        s.push_str(str);
+        continue;
+      }
+      Task::Name(name) => {
+        s.push_str(&super::quote_string(name));
+        continue;
+      }
+    };
+    match value {
+      Value::Null => s.push_str("null"),
+      Value::Bool(b) => s.push_str(&b.to_string()),
+      Value::Integer(i) => s.push_str(&i.to_string()),
+      Value::Float(f) => s.push_str(&float_to_string(*f)),
+      Value::String(str) => s.push_str(&super::quote_string(str)),
+      Value::Assoc(assoc) => {
+        s.push('{');
+        tasks.push(Task::Str("}"));
+        let mut members = assoc.iter().collect::<Vec<_>>();
+        members.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+        for (i, (name, value)) in members.into_iter().enumerate().rev() {
+          tasks.push(Task::Value(value));
+          tasks.push(Task::Str(":"));
+          tasks.push(Task::Name(name));
+          if i > 0 {
+            tasks.push(Task::Str(","));
+          }
+        }
+      }
+      Value::Array(elements) | Value::Tuple(elements) => {
+        let (open, close) = match value {
+          Value::Tuple(_) => ("(", ")"),
+          _ => ("[", "]"),
+        };
+        s.push_str(open);
+        tasks.push(Task::Str(close));
+        for (i, value) in elements.iter().enumerate().rev() {
+          tasks.push(Task::Value(value));
+          if i > 0 {
+            tasks.push(Task::Str(","));
+          }
+        }
+      }
+      Value::Variant((name, argument)) => {
+        s.push('<');
+        s.push_str(&super::quote_string(name));
+        tasks.push(Task::Str(">"));
+        if let Some(argument) = argument {
+          s.push(':');
+          tasks.push(Task::Value(argument));
+        }
+      }
+    }
+  }
+  s
+}
+
+const K: [u32; 64] = [
+  0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+  0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+  0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+  0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+  0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+  0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+  0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+  0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Computes the SHA-256 digest of bytes.
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+  let mut h: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+  ];
+  let mut message = bytes.to_vec();
+  message.push(0x80);
+  while message.len() % 64 != 56 {
+    message.push(0);
+  }
+  message.extend_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+  for block in message.chunks(64) {
+    let mut w = [0u32; 64];
+    for (i, word) in block.chunks(4).enumerate() {
+      w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    for i in 16..64 {
+      let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+      let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+      w[i] = w[i - 16]
+        .wrapping_add(s0)
+        .wrapping_add(w[i - 7])
+        .wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+    for i in 0..64 {
+      let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+      let ch = (e & f) ^ (!e & g);
+      let t1 = hh
+        .wrapping_add(s1)
+        .wrapping_add(ch)
+        .wrapping_add(K[i])
+        .wrapping_add(w[i]);
+      let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+      let maj = (a & b) ^ (a & c) ^ (b & c);
+      let t2 = s0.wrapping_add(maj);
+      hh = g;
+      g = f;
+      f = e;
+      e = d.wrapping_add(t1);
+      d = c;
+      c = b;
+      b = a;
+      a = t1.wrapping_add(t2);
+    }
+    for (x, y) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+      *x = x.wrapping_add(y);
+    }
+  }
+  let mut digest = [0; 32];
+  for (chunk, x) in digest.chunks_mut(4).zip(h) {
+    chunk.copy_from_slice(&x.to_be_bytes());
+  }
+  digest
+}
+
+impl Value {
+  /// Returns the SHA-256 digest of the canonical form of the value.
+  ///
+  /// The digest is stable across runs and platforms, and is the same for values which have
+  /// the same canonical form.
+  pub fn content_hash(&self) -> [u8; 32] {
+    sha256(to_string(self).as_bytes())
+  }
+}
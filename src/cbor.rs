@@ -0,0 +1,192 @@
+//! Definition of the conversion between Yojson values and `ciborium` values.
+//!
+//! This module is enabled by the feature `ciborium`.
+//!
+//! Yojson values are converted to CBOR as follows:
+//! - Assocs become maps with text keys.
+//! - Tuples become arrays.
+//! - Variants become arrays of the constructor and the argument tagged 27, the tag of
+//!   objects with a type name and constructor arguments, such as `27(["Circle", 1.5])` and
+//!   `27(["Point"])`.
+//! - Floats, including non-finite ones, are kept as they are.
+//!
+//! CBOR values are converted back with the same mapping. Byte strings become arrays of
+//! integers, keys which are not text become strings, other tags are dropped, and integers
+//! beyond the range of `i64` become floats.
+//!
+//! Everything which is not read back as the same value is reported as a `loss::Loss`.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use super::loss::{child, Loss, Lossy};
+use super::value::Value;
+use ciborium::value::Value as Cbor;
+
+/// Tag of variants.
+pub const VARIANT: u64 = 27;
+
+fn to_cbor_at(value: &Value, path: &str, losses: &mut Vec<Loss>) -> Cbor {
+  match value {
+    Value::Null => Cbor::Null,
+    Value::Bool(b) => Cbor::Bool(*b),
+    Value::Integer(i) => Cbor::Integer((*i).into()),
+    Value::Float(f) => Cbor::Float(*f),
+    Value::String(s) => Cbor::Text(s.clone()),
+    Value::Assoc(assoc) => Cbor::Map(
+      assoc
+        .iter()
+        .map(|(name, value)| {
+          let value = to_cbor_at(value, &child(path, name), losses);
+          (Cbor::Text(name.clone()), value)
+        })
+        .collect(),
+    ),
+    Value::Array(elements) | Value::Tuple(elements) => {
+      if let Value::Tuple(_) = value {
+        losses.push(Loss::new(path, "tuple is read back as an array"));
+      }
+      Cbor::Array(
+        elements
+          .iter()
+          .enumerate()
+          .map(|(i, value)| to_cbor_at(value, &child(path, &i.to_string()), losses))
+          .collect(),
+      )
+    }
+    Value::Variant((name, argument)) => {
+      let mut elements = vec![Cbor::Text(name.clone())];
+      if let Some(argument) = argument {
+        elements.push(to_cbor_at(
+          argument,
+          &child(path, super::pointer::PAYLOAD),
+          losses,
+        ));
+      }
+      Cbor::Tag(VARIANT, Box::new(Cbor::Array(elements)))
+    }
+  }
+}
+
+/// Converts a Yojson value to a `ciborium` value.
+///
+/// ```
+/// let value = yojson_rs::parser::parse("{shape : <Circle: 1.5>}").unwrap();
+/// let cbor = yojson_rs::cbor::to_cbor(&value).into_value();
+/// let mut bytes = Vec::new();
+/// ciborium::ser::into_writer(&cbor, &mut bytes).unwrap();
+/// ```
+pub fn to_cbor(value: &Value) -> Lossy<Cbor> {
+  let mut losses = Vec::new();
+  let cbor = to_cbor_at(value, "", &mut losses);
+  Lossy::new(cbor, losses)
+}
+
+fn key_to_string(key: &Cbor) -> Option<String> {
+  match key {
+    Cbor::Integer(i) => Some(i128::from(*i).to_string()),
+    Cbor::Bool(b) => Some(b.to_string()),
+    Cbor::Null => Some("null".to_string()),
+    Cbor::Float(f) => Some(super::float_to_string(*f)),
+    Cbor::Text(s) => Some(s.clone()),
+    Cbor::Tag(_, key) => key_to_string(key),
+    _ => None,
+  }
+}
+
+fn from_cbor_at(cbor: &Cbor, path: &str, losses: &mut Vec<Loss>) -> Value {
+  match cbor {
+    Cbor::Null => Value::Null,
+    Cbor::Bool(b) => Value::Bool(*b),
+    Cbor::Integer(i) => match i64::try_from(*i) {
+      Ok(i) => Value::Integer(i),
+      Err(_) => {
+        let i = i128::from(*i);
+        losses.push(Loss::new(
+          path,
+          format!(
+            "integer {} is out of the range of i64 and read as a float",
+            i
+          ),
+        ));
+        Value::Float(i as f64)
+      }
+    },
+    Cbor::Float(f) => Value::Float(*f),
+    Cbor::Text(s) => Value::String(s.clone()),
+    Cbor::Bytes(bytes) => {
+      losses.push(Loss::new(path, "byte string is read as an array"));
+      Value::Array(bytes.iter().map(|b| Value::Integer(*b as i64)).collect())
+    }
+    Cbor::Array(elements) => Value::Array(
+      elements
+        .iter()
+        .enumerate()
+        .map(|(i, cbor)| from_cbor_at(cbor, &child(path, &i.to_string()), losses))
+        .collect(),
+    ),
+    Cbor::Map(map) => {
+      let mut assoc = HashMap::new();
+      for (key, cbor) in map {
+        let name = match (key_to_string(key), key) {
+          (Some(name), Cbor::Text(_)) => name,
+          (Some(name), _) => {
+            losses.push(Loss::new(
+              &child(path, &name),
+              format!("key {} is read as a string", name),
+            ));
+            name
+          }
+          (None, _) => {
+            losses.push(Loss::new(
+              path,
+              "member with a byte string, an array or a map as key is dropped",
+            ));
+            continue;
+          }
+        };
+        let path = child(path, &name);
+        let value = from_cbor_at(cbor, &path, losses);
+        if assoc.insert(name.clone(), value).is_some() {
+          losses.push(Loss::new(
+            &path,
+            format!("duplicate member {} replaces the previous one", name),
+          ));
+        }
+      }
+      Value::Assoc(assoc)
+    }
+    Cbor::Tag(VARIANT, content) => match &**content {
+      Cbor::Array(elements) => match elements.as_slice() {
+        [Cbor::Text(name)] => Value::Variant((name.clone(), None)),
+        [Cbor::Text(name), argument] => {
+          let argument = from_cbor_at(argument, &child(path, super::pointer::PAYLOAD), losses);
+          Value::Variant((name.clone(), Some(Box::new(argument))))
+        }
+        _ => {
+          losses.push(Loss::new(path, "tag 27 of an object is dropped"));
+          from_cbor_at(content, path, losses)
+        }
+      },
+      _ => {
+        losses.push(Loss::new(path, "tag 27 of an object is dropped"));
+        from_cbor_at(content, path, losses)
+      }
+    },
+    Cbor::Tag(tag, content) => {
+      losses.push(Loss::new(path, format!("tag {} is dropped", tag)));
+      from_cbor_at(content, path, losses)
+    }
+    _ => {
+      losses.push(Loss::new(path, "unknown value is read as null"));
+      Value::Null
+    }
+  }
+}
+
+/// Converts a `ciborium` value to a Yojson value.
+pub fn from_cbor(cbor: &Cbor) -> Lossy<Value> {
+  let mut losses = Vec::new();
+  let value = from_cbor_at(cbor, "", &mut losses);
+  Lossy::new(value, losses)
+}
@@ -0,0 +1,773 @@
+//! Definition of the generation of Rust code from ATD type definitions.
+//!
+//! Records become structs, variants become enums, and other definitions become type aliases.
+//! Structs and enums implement `convert::ToYojson` and `convert::FromYojson` with the encoding
+//! of atdgen:
+//! - Records are assocs. `?name : t option` is omitted when `None`, and `~name : t` takes
+//!   its default value when missing, which is given by `<ocaml default="...">` or implied by
+//!   the type. Fields with default values are always written, as with `atdgen -j-defaults`.
+//! - Variants are `<"Foo">` and `<"Foo": x>`, or `"Foo"` and `["Foo", x]` with
+//!   `CodegenOptions::std_json`. `t option` is written as the variant `None` or `Some`.
+//! - Tuples are tuples, or arrays with `CodegenOptions::std_json`.
+//! - `(string * t) list <json repr="object">` is an assoc.
+//! - `<json name="...">` renames fields and constructors.
+//! - Fields named after Rust keywords are raw identifiers, such as `r#type`, except `crate`,
+//!   `self`, `Self` and `super`, which take a trailing `_`. Their JSON names are unchanged.
+//!
+//! Both encodings of variants and tuples are accepted when reading.
+//!
+//! In a build script, `build` writes the code for `types.atd` to `$OUT_DIR/types.rs`:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!   yojson_rs::codegen::build("types.atd").unwrap();
+//! }
+//!
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/types.rs"));
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::atd::{self, AtdError, FieldKind, Module, TypeDef, TypeExpr, TypeKind};
+
+/// Options of code generation.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CodegenOptions {
+  pub(crate) std_json: bool,
+  pub(crate) derives: Vec<String>,
+}
+
+impl Default for CodegenOptions {
+  fn default() -> Self {
+    CodegenOptions {
+      std_json: false,
+      derives: vec![
+        "Clone".to_string(),
+        "Debug".to_string(),
+        "PartialEq".to_string(),
+      ],
+    }
+  }
+}
+
+impl CodegenOptions {
+  /// Creates the default options, which write the Yojson encoding and derive
+  /// `Clone`, `Debug` and `PartialEq`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Writes variants and tuples in standard JSON, as `atdgen -j-std`.
+  pub fn std_json(mut self, std_json: bool) -> Self {
+    self.std_json = std_json;
+    self
+  }
+
+  /// Sets the traits derived by the generated structs and enums.
+  pub fn derives(mut self, derives: &[&str]) -> Self {
+    self.derives = derives.iter().map(|s| s.to_string()).collect();
+    self
+  }
+}
+
+const Y: &str = "::yojson_rs";
+
+/// Keywords and reserved words, which are written as raw identifiers.
+const KEYWORDS: [&str; 48] = [
+  "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+  "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+  "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+  "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+  "virtual", "where", "while", "yield",
+];
+
+/// Keywords which cannot be raw identifiers, and are written with a trailing `_` instead.
+const PATH_KEYWORDS: [&str; 4] = ["crate", "self", "Self", "super"];
+
+/// Converts `snake_case` and `Snake_case` to `CamelCase`, with a trailing `_` for `Self`.
+fn camel_case(name: &str) -> String {
+  let name = name
+    .split('_')
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      let first = chars.next().unwrap().to_ascii_uppercase();
+      std::iter::once(first)
+        .chain(chars.map(|c| if c == '\'' { '_' } else { c }))
+        .collect::<String>()
+    })
+    .collect::<String>();
+  if name == "Self" {
+    name + "_"
+  } else {
+    name
+  }
+}
+
+fn field_name(name: &str) -> String {
+  let name = name.replace('\'', "_");
+  if PATH_KEYWORDS.contains(&name.as_str()) {
+    format!("{}_", name)
+  } else if KEYWORDS.contains(&name.as_str()) {
+    format!("r#{}", name)
+  } else {
+    name
+  }
+}
+
+/// Tests whether a default value is a finite decimal literal which is also valid in Rust.
+fn is_float_literal(literal: &str) -> bool {
+  let digits = literal.strip_prefix('-').unwrap_or(literal);
+  digits.starts_with(|c: char| c.is_ascii_digit())
+    && digits
+      .chars()
+      .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+    && !digits.contains(".e")
+    && !digits.contains(".E")
+    && literal.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// Removes the borrow of an expression used as the receiver of a method call.
+fn receiver(x: &str) -> &str {
+  x.strip_prefix('&').unwrap_or(x)
+}
+
+fn doc(out: &mut String, annotations: &[atd::Annotation], indent: &str) {
+  if let Some(text) = atd::annotation(annotations, "doc", "text") {
+    for line in text.lines() {
+      out.push_str(&format!("{}/// {}\n", indent, line.trim()).replace("///  \n", "///\n"));
+    }
+  }
+}
+
+struct Generator<'a> {
+  module: &'a Module,
+  options: &'a CodegenOptions,
+  /// Name of the definition being generated, whose direct references are boxed.
+  current: &'a str,
+  fresh: usize,
+}
+
+impl<'a> Generator<'a> {
+  fn error(&self, message: impl Into<String>) -> AtdError {
+    let position = self.module.get(self.current).map(|def| def.position);
+    AtdError::new(message, position)
+  }
+
+  /// Returns the definition of an alias, with the arguments substituted.
+  fn alias(&self, name: &str, args: &[TypeExpr]) -> Option<TypeExpr> {
+    let def = self.module.get(name)?;
+    if matches!(def.expr.kind, TypeKind::Record(_) | TypeKind::Variant(_)) {
+      return None;
+    }
+    let args = def
+      .params
+      .iter()
+      .map(String::as_str)
+      .zip(args)
+      .collect::<HashMap<_, _>>();
+    Some(atd::substitute(&def.expr, &args))
+  }
+
+  fn rust_type(&self, expr: &TypeExpr, boxed: bool) -> Result<String, AtdError> {
+    Ok(match &expr.kind {
+      TypeKind::Name(name, args) => match name.as_str() {
+        "unit" => "()".to_string(),
+        "bool" => "bool".to_string(),
+        "int" => "i64".to_string(),
+        "float" => "f64".to_string(),
+        "string" => "String".to_string(),
+        "abstract" => format!("{}::value::Value", Y),
+        _ => {
+          let mut s = camel_case(name);
+          if !args.is_empty() {
+            let args = args
+              .iter()
+              .map(|arg| self.rust_type(arg, boxed))
+              .collect::<Result<Vec<_>, _>>()?;
+            s = format!("{}<{}>", s, args.join(", "));
+          }
+          if boxed && name == self.current {
+            s = format!("Box<{}>", s);
+          }
+          s
+        }
+      },
+      TypeKind::Var(var) => camel_case(var),
+      TypeKind::List(element) => format!("Vec<{}>", self.rust_type(element, false)?),
+      TypeKind::Option(element) | TypeKind::Nullable(element) => {
+        format!("Option<{}>", self.rust_type(element, boxed)?)
+      }
+      TypeKind::Tuple(elements) => {
+        let elements = elements
+          .iter()
+          .map(|e| self.rust_type(e, boxed))
+          .collect::<Result<Vec<_>, _>>()?;
+        format!("({})", elements.join(", "))
+      }
+      TypeKind::Record(_) | TypeKind::Variant(_) => {
+        return Err(self.error("records and variants must be defined at the top of a type"))
+      }
+    })
+  }
+
+  /// Returns the expression converting the reference `x` to a Yojson value.
+  fn write(&mut self, expr: &TypeExpr, x: &str, depth: usize) -> Result<String, AtdError> {
+    if depth > 64 {
+      return Err(self.error("cyclic type abbreviation"));
+    }
+    let std = self.options.std_json;
+    Ok(match &expr.kind {
+      TypeKind::Name(name, args) => match self.alias(name, args) {
+        Some(expr) => self.write(&expr, x, depth + 1)?,
+        None => format!("{}::convert::ToYojson::to_yojson({})", Y, x),
+      },
+      TypeKind::Var(_) => format!("{}::convert::ToYojson::to_yojson({})", Y, x),
+      TypeKind::List(element) if atd::is_object_list(expr) => {
+        let value = self.object_value(element)?;
+        format!(
+          "{}::value::Value::Assoc({}.iter().map(|(k, x)| (k.clone(), {})).collect())",
+          Y,
+          receiver(x),
+          self.write(&value, "x", depth + 1)?
+        )
+      }
+      TypeKind::List(element) => {
+        let write = self.write(element, "x", depth + 1)?;
+        let function = match write.strip_suffix("(x)") {
+          Some(function) if !function.contains(' ') => function.to_string(),
+          _ => format!("|x| {}", write),
+        };
+        format!(
+          "{}::value::Value::Array({}.iter().map({}).collect())",
+          Y,
+          receiver(x),
+          function
+        )
+      }
+      TypeKind::Option(element) => format!(
+        "match {x} {{ None => {y}::convert::write_variant(\"None\", None, {std}), Some(x) => \
+         {y}::convert::write_variant(\"Some\", Some({w}), {std}) }}",
+        x = x,
+        y = Y,
+        std = std,
+        w = self.write(element, "x", depth + 1)?
+      ),
+      TypeKind::Nullable(element) => format!(
+        "match {} {{ None => {}::value::Value::Null, Some(x) => {} }}",
+        x,
+        Y,
+        self.write(element, "x", depth + 1)?
+      ),
+      TypeKind::Tuple(elements) => {
+        self.fresh += 1;
+        let names = (0..elements.len())
+          .map(|i| format!("x{}_{}", self.fresh, i))
+          .collect::<Vec<_>>();
+        let values = elements
+          .iter()
+          .zip(&names)
+          .map(|(e, name)| self.write(e, name, depth + 1))
+          .collect::<Result<Vec<_>, _>>()?;
+        format!(
+          "{{ let ({},) = {}; {} }}",
+          names.join(", "),
+          x,
+          self.tuple(&values)
+        )
+      }
+      TypeKind::Record(_) | TypeKind::Variant(_) => {
+        return Err(self.error("records and variants must be defined at the top of a type"))
+      }
+    })
+  }
+
+  fn tuple(&self, values: &[String]) -> String {
+    let kind = if self.options.std_json {
+      "Array"
+    } else {
+      "Tuple"
+    };
+    format!("{}::value::Value::{}(vec![{}])", Y, kind, values.join(", "))
+  }
+
+  /// Returns the type of the values of `(string * t) list <json repr="object">`.
+  fn object_value(&self, element: &TypeExpr) -> Result<TypeExpr, AtdError> {
+    match &element.kind {
+      TypeKind::Tuple(pair)
+        if pair.len() == 2 && pair[0].kind == TypeKind::Name("string".to_string(), Vec::new()) =>
+      {
+        Ok(pair[1].clone())
+      }
+      _ => Err(self.error("<json repr=\"object\"> requires a list of (string * t)")),
+    }
+  }
+
+  /// Returns the expression reading the Yojson value `v` into a `Result`.
+  fn read(&mut self, expr: &TypeExpr, v: &str, depth: usize) -> Result<String, AtdError> {
+    if depth > 64 {
+      return Err(self.error("cyclic type abbreviation"));
+    }
+    Ok(match &expr.kind {
+      TypeKind::Name(name, args) => match self.alias(name, args) {
+        Some(expr) => self.read(&expr, v, depth + 1)?,
+        None => format!(
+          "<{} as {}::convert::FromYojson>::from_yojson({})",
+          self.rust_type(expr, true)?,
+          Y,
+          v
+        ),
+      },
+      TypeKind::Var(var) => format!(
+        "<{} as {}::convert::FromYojson>::from_yojson({})",
+        camel_case(var),
+        Y,
+        v
+      ),
+      TypeKind::List(element) if atd::is_object_list(expr) => {
+        let value = self.object_value(element)?;
+        format!(
+          "{}::convert::expect_assoc({}).and_then(|a| a.iter().map(|(k, v)| {}.map(|x| (k.clone(), x)).map_err(|e| e.at(k))).collect::<Result<Vec<_>, _>>())",
+          Y,
+          v,
+          self.read(&value, "v", depth + 1)?
+        )
+      }
+      TypeKind::List(element) => format!(
+        "{}::convert::expect_array({}).and_then(|a| a.iter().enumerate().map(|(i, v)| {}.map_err(|e| e.at(i))).collect::<Result<Vec<_>, _>>())",
+        Y,
+        v,
+        self.read(element, "v", depth + 1)?
+      ),
+      TypeKind::Option(element) => format!(
+        "{y}::convert::read_variant({v}).and_then(|c| match c {{ (\"None\", None) => Ok(None), (\"Some\", Some(v)) => {r}.map(Some).map_err(|e| e.at(\"{payload}\")), _ => Err({y}::convert::ConvertError::expected(\"option\", {v})) }})",
+        y = Y,
+        v = v,
+        r = self.read(element, "v", depth + 1)?,
+        payload = super::pointer::PAYLOAD
+      ),
+      TypeKind::Nullable(element) => format!(
+        "match {} {{ {}::value::Value::Null => Ok(None), v => {}.map(Some) }}",
+        v,
+        Y,
+        self.read(element, "v", depth + 1)?
+      ),
+      TypeKind::Tuple(elements) => {
+        let values = self.read_elements(elements, "t", depth)?;
+        format!(
+          "{}::convert::expect_tuple({}, {}).and_then(|t| Ok(({},)))",
+          Y,
+          v,
+          elements.len(),
+          values.join(", ")
+        )
+      }
+      TypeKind::Record(_) | TypeKind::Variant(_) => {
+        return Err(self.error("records and variants must be defined at the top of a type"))
+      }
+    })
+  }
+
+  /// Returns the expressions reading the elements of the slice `t`, propagating errors.
+  fn read_elements(
+    &mut self,
+    elements: &[TypeExpr],
+    t: &str,
+    depth: usize,
+  ) -> Result<Vec<String>, AtdError> {
+    elements
+      .iter()
+      .enumerate()
+      .map(|(i, e)| {
+        Ok(format!(
+          "{{ let v = &{}[{}]; {} }}.map_err(|e| e.at({}))?",
+          t,
+          i,
+          self.read(e, "v", depth + 1)?,
+          i
+        ))
+      })
+      .collect()
+  }
+
+  fn default(&self, field: &atd::Field) -> Result<String, AtdError> {
+    if let Some(text) = atd::annotation(&field.annotations, "ocaml", "default")
+      .or_else(|| atd::annotation(&field.expr.annotations, "ocaml", "default"))
+    {
+      let text = text.trim();
+      let mut expr = field.expr.clone();
+      for _ in 0..64 {
+        match &expr.kind {
+          TypeKind::Name(name, args) => match self.alias(name, args) {
+            Some(alias) => expr = alias,
+            None => break,
+          },
+          _ => break,
+        }
+      }
+      let rust_type = self.rust_type(&expr, false)?;
+      let literal = if text == "()" {
+        text
+      } else {
+        text.trim_start_matches('(').trim_end_matches(')').trim()
+      };
+      return match (literal, rust_type.as_str()) {
+        ("()", "()") => Ok("()".to_string()),
+        ("[]", _) if rust_type.starts_with("Vec<") => Ok("Vec::new()".to_string()),
+        ("None", _) if rust_type.starts_with("Option<") => Ok("None".to_string()),
+        ("true" | "false", "bool") => Ok(literal.to_string()),
+        (_, "String") if literal.starts_with('"') => Ok(format!("String::from({})", literal)),
+        (_, "i64") if literal.parse::<i64>().is_ok() => Ok(literal.to_string()),
+        (_, "f64") if is_float_literal(literal) => {
+          if literal.contains(['.', 'e', 'E']) {
+            Ok(literal.to_string())
+          } else {
+            Ok(format!("{}.0", literal))
+          }
+        }
+        _ => Err(self.error(format!(
+          "unsupported default value \"{}\" of field \"{}\" of type {}",
+          text, field.name, rust_type
+        ))),
+      };
+    }
+    let mut expr = field.expr.clone();
+    for _ in 0..64 {
+      match &expr.kind {
+        TypeKind::Name(name, args) => match (name.as_str(), self.alias(name, args)) {
+          ("unit", _) => return Ok("()".to_string()),
+          ("bool", _) => return Ok("false".to_string()),
+          ("int", _) => return Ok("0".to_string()),
+          ("float", _) => return Ok("0.0".to_string()),
+          ("string", _) => return Ok("String::new()".to_string()),
+          (_, Some(alias)) => expr = alias,
+          (_, None) => break,
+        },
+        TypeKind::List(_) => return Ok("Vec::new()".to_string()),
+        TypeKind::Option(_) | TypeKind::Nullable(_) => return Ok("None".to_string()),
+        _ => break,
+      }
+    }
+    Err(self.error(format!(
+      "field \"{}\" needs <ocaml default=\"...\">",
+      field.name
+    )))
+  }
+
+  fn generics(&self, def: &TypeDef, bound: &str) -> (String, String) {
+    if def.params.is_empty() {
+      return (String::new(), String::new());
+    }
+    let params = def.params.iter().map(|p| camel_case(p)).collect::<Vec<_>>();
+    let bounded = params
+      .iter()
+      .map(|p| format!("{}: {}::convert::{}", p, Y, bound))
+      .collect::<Vec<_>>();
+    (
+      format!("<{}>", bounded.join(", ")),
+      format!("<{}>", params.join(", ")),
+    )
+  }
+
+  fn derive(&self) -> String {
+    if self.options.derives.is_empty() {
+      String::new()
+    } else {
+      format!("#[derive({})]\n", self.options.derives.join(", "))
+    }
+  }
+
+  fn record(
+    &mut self,
+    def: &TypeDef,
+    fields: &[atd::Field],
+    out: &mut String,
+  ) -> Result<(), AtdError> {
+    let name = camel_case(&def.name);
+    let (_, params) = self.generics(def, "");
+    let (to_bounds, _) = self.generics(def, "ToYojson");
+    let (from_bounds, _) = self.generics(def, "FromYojson");
+    out.push_str(&self.derive());
+    out.push_str(&format!("pub struct {}{} {{\n", name, params));
+    for field in fields {
+      doc(out, &field.annotations, "  ");
+      out.push_str(&format!(
+        "  pub {}: {},\n",
+        field_name(&field.name),
+        self.rust_type(&field.expr, true)?
+      ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!(
+      "impl{} {}::convert::ToYojson for {}{} {{\n  fn to_yojson(&self) -> {}::value::Value {{\n",
+      to_bounds, Y, name, params, Y
+    ));
+    out.push_str(
+      "    #[allow(unused_mut)]\n    let mut assoc = ::std::collections::HashMap::new();\n",
+    );
+    for field in fields {
+      let json_name = atd::json_name(&field.name, &field.annotations);
+      match (&field.kind, &field.expr.kind) {
+        (FieldKind::Optional, TypeKind::Option(element)) => out.push_str(&format!(
+          "    if let Some(x) = &self.{} {{\n      assoc.insert({:?}.to_string(), {});\n    }}\n",
+          field_name(&field.name),
+          json_name,
+          self.write(element, "x", 0)?
+        )),
+        _ => out.push_str(&format!(
+          "    assoc.insert({:?}.to_string(), {});\n",
+          json_name,
+          self.write(
+            &field.expr,
+            &format!("&self.{}", field_name(&field.name)),
+            0
+          )?
+        )),
+      }
+    }
+    out.push_str(&format!(
+      "    {}::value::Value::Assoc(assoc)\n  }}\n}}\n\n",
+      Y
+    ));
+
+    out.push_str(&format!(
+      "impl{} {}::convert::FromYojson for {}{} {{\n  fn from_yojson(v: &{}::value::Value) -> Result<Self, {}::convert::ConvertError> {{\n",
+      from_bounds, Y, name, params, Y, Y
+    ));
+    if fields.is_empty() {
+      out.push_str(&format!(
+        "    {}::convert::expect_assoc(v)?;\n    Ok({} {{}})\n  }}\n}}\n\n",
+        Y, name
+      ));
+      return Ok(());
+    }
+    out.push_str(&format!(
+      "    let assoc = {}::convert::expect_assoc(v)?;\n    Ok({} {{\n",
+      Y, name
+    ));
+    for field in fields {
+      let json_name = atd::json_name(&field.name, &field.annotations);
+      let (read, missing) = match (&field.kind, &field.expr.kind) {
+        (FieldKind::Optional, TypeKind::Option(element)) => (
+          format!(
+            "Some({}.map_err(|e| e.at({:?}))?)",
+            self.read(element, "v", 0)?,
+            json_name
+          ),
+          "None".to_string(),
+        ),
+        (FieldKind::WithDefault, _) => (
+          format!(
+            "{}.map_err(|e| e.at({:?}))?",
+            self.read(&field.expr, "v", 0)?,
+            json_name
+          ),
+          self.default(field)?,
+        ),
+        _ => (
+          format!(
+            "{}.map_err(|e| e.at({:?}))?",
+            self.read(&field.expr, "v", 0)?,
+            json_name
+          ),
+          format!(
+            "return Err({}::convert::ConvertError::missing({:?}))",
+            Y, json_name
+          ),
+        ),
+      };
+      out.push_str(&format!(
+        "      {}: match assoc.get({:?}) {{\n        Some(v) => {},\n        None => {},\n      }},\n",
+        field_name(&field.name),
+        json_name,
+        read,
+        missing
+      ));
+    }
+    out.push_str("    })\n  }\n}\n\n");
+    Ok(())
+  }
+
+  fn variant(
+    &mut self,
+    def: &TypeDef,
+    constructors: &[atd::Constructor],
+    out: &mut String,
+  ) -> Result<(), AtdError> {
+    let name = camel_case(&def.name);
+    let (_, params) = self.generics(def, "");
+    let (to_bounds, _) = self.generics(def, "ToYojson");
+    let (from_bounds, _) = self.generics(def, "FromYojson");
+    let arguments = |c: &atd::Constructor| match &c.argument {
+      None => Vec::new(),
+      Some(TypeExpr {
+        kind: TypeKind::Tuple(elements),
+        ..
+      }) => elements.clone(),
+      Some(argument) => vec![argument.clone()],
+    };
+    out.push_str(&self.derive());
+    out.push_str(&format!("pub enum {}{} {{\n", name, params));
+    for c in constructors {
+      doc(out, &c.annotations, "  ");
+      let args = arguments(c)
+        .iter()
+        .map(|a| self.rust_type(a, true))
+        .collect::<Result<Vec<_>, _>>()?;
+      if args.is_empty() {
+        out.push_str(&format!("  {},\n", camel_case(&c.name)));
+      } else {
+        out.push_str(&format!(
+          "  {}({}),\n",
+          camel_case(&c.name),
+          args.join(", ")
+        ));
+      }
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!(
+      "impl{} {}::convert::ToYojson for {}{} {{\n  fn to_yojson(&self) -> {}::value::Value {{\n    match self {{\n",
+      to_bounds, Y, name, params, Y
+    ));
+    for c in constructors {
+      let json_name = atd::json_name(&c.name, &c.annotations);
+      let args = arguments(c);
+      let names = (0..args.len())
+        .map(|i| format!("x{}", i))
+        .collect::<Vec<_>>();
+      let pattern = if args.is_empty() {
+        format!("{}::{}", name, camel_case(&c.name))
+      } else {
+        format!("{}::{}({})", name, camel_case(&c.name), names.join(", "))
+      };
+      let argument = match &c.argument {
+        None => "None".to_string(),
+        Some(TypeExpr {
+          kind: TypeKind::Tuple(_),
+          ..
+        }) => {
+          let values = args
+            .iter()
+            .zip(&names)
+            .map(|(a, n)| self.write(a, n, 0))
+            .collect::<Result<Vec<_>, _>>()?;
+          format!("Some({})", self.tuple(&values))
+        }
+        Some(argument) => format!("Some({})", self.write(argument, "x0", 0)?),
+      };
+      out.push_str(&format!(
+        "      {} => {}::convert::write_variant({:?}, {}, {}),\n",
+        pattern, Y, json_name, argument, self.options.std_json
+      ));
+    }
+    out.push_str("    }\n  }\n}\n\n");
+
+    out.push_str(&format!(
+      "impl{} {}::convert::FromYojson for {}{} {{\n  fn from_yojson(v: &{}::value::Value) -> Result<Self, {}::convert::ConvertError> {{\n    match {}::convert::read_variant(v)? {{\n",
+      from_bounds, Y, name, params, Y, Y, Y
+    ));
+    for c in constructors {
+      let json_name = atd::json_name(&c.name, &c.annotations);
+      let constructor = format!("{}::{}", name, camel_case(&c.name));
+      match &c.argument {
+        None => out.push_str(&format!(
+          "      ({:?}, None) => Ok({}),\n",
+          json_name, constructor
+        )),
+        Some(TypeExpr {
+          kind: TypeKind::Tuple(elements),
+          ..
+        }) => {
+          let values = self.read_elements(elements, "t", 0)?;
+          out.push_str(&format!(
+            "      ({:?}, Some(v)) => {}::convert::expect_tuple(v, {}).and_then(|t| Ok({}({}))).map_err(|e| e.at({:?})),\n",
+            json_name,
+            Y,
+            elements.len(),
+            constructor,
+            values.join(", "),
+            super::pointer::PAYLOAD
+          ))
+        }
+        Some(argument) => out.push_str(&format!(
+          "      ({:?}, Some(v)) => {}.map({}).map_err(|e| e.at({:?})),\n",
+          json_name,
+          self.read(argument, "v", 0)?,
+          constructor,
+          super::pointer::PAYLOAD
+        )),
+      }
+    }
+    out.push_str(&format!(
+      "      (name, _) => Err({}::convert::ConvertError::new(format!(\"unexpected constructor \\\"{{}}\\\"\", name))),\n    }}\n  }}\n}}\n\n",
+      Y
+    ));
+    Ok(())
+  }
+
+  fn type_def(&mut self, def: &'a TypeDef, out: &mut String) -> Result<(), AtdError> {
+    self.current = &def.name;
+    doc(out, &def.annotations, "");
+    match &def.expr.kind {
+      TypeKind::Record(fields) => self.record(def, fields, out),
+      TypeKind::Variant(constructors) => self.variant(def, constructors, out),
+      _ => {
+        let (_, params) = self.generics(def, "");
+        out.push_str(&format!(
+          "pub type {}{} = {};\n\n",
+          camel_case(&def.name),
+          params,
+          self.rust_type(&def.expr, false)?
+        ));
+        Ok(())
+      }
+    }
+  }
+}
+
+/// Generates Rust code from an ATD module.
+pub fn generate(module: &Module, options: &CodegenOptions) -> Result<String, AtdError> {
+  let mut generator = Generator {
+    module,
+    options,
+    current: "",
+    fresh: 0,
+  };
+  let mut out =
+    String::from("// This file is generated by yojson_rs::codegen. Do not edit it by hand.\n\n");
+  for def in &module.types {
+    generator.type_def(def, &mut out)?;
+  }
+  out.truncate(out.trim_end().len());
+  out.push('\n');
+  Ok(out)
+}
+
+/// Generates Rust code from an ATD file into `$OUT_DIR` in a build script.
+///
+/// The output has the name of the input with the extension `rs`.
+pub fn build(path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+  build_with_options(path, &CodegenOptions::new())
+}
+
+/// Generates Rust code from an ATD file into `$OUT_DIR` with options in a build script.
+pub fn build_with_options(
+  path: impl AsRef<Path>,
+  options: &CodegenOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+  let path = path.as_ref();
+  println!("cargo:rerun-if-changed={}", path.display());
+  let text = std::fs::read_to_string(path)?;
+  let module = atd::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+  let code = generate(&module, options)?;
+  let out_dir = std::env::var_os("OUT_DIR").ok_or("OUT_DIR is not set")?;
+  let stem = path.file_stem().ok_or("the path has no file name")?;
+  let output = Path::new(&out_dir).join(stem).with_extension("rs");
+  std::fs::write(output, code)?;
+  Ok(())
+}
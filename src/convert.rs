@@ -0,0 +1,327 @@
+//! Definition of the conversion between Rust values and Yojson values.
+//!
+//! `ToYojson` and `FromYojson` are implemented by generated code (see the `codegen` module), and
+//! the helper functions of this module are used by that code.
+//!
+//! The implementations for the standard types use the Yojson encoding:
+//! integers, floats, strings and booleans are scalars, `()` is `null`, `Vec` is an array,
+//! `Option` is `null` or the value, and tuples are tuples.
+//! Floats can be read from integers, and tuples from arrays.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::pointer;
+use super::value::Value;
+
+/// Error of reading a Rust value from a Yojson value.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ConvertError {
+  path: String,
+  message: String,
+}
+
+impl ConvertError {
+  /// Creates an error at the current value.
+  pub fn new(message: impl Into<String>) -> Self {
+    ConvertError {
+      path: String::new(),
+      message: message.into(),
+    }
+  }
+
+  /// Creates an error of a value of an unexpected type.
+  pub fn expected(expected: &str, found: &Value) -> Self {
+    ConvertError::new(format!("expected {}, found {}", expected, found.type_str()))
+  }
+
+  /// Creates an error of a missing member.
+  pub fn missing(name: &str) -> Self {
+    ConvertError::new(format!("missing member \"{}\"", name))
+  }
+
+  /// Prefixes the path of the error with a token, as the error is propagated to the parent.
+  pub fn at(mut self, token: impl fmt::Display) -> Self {
+    self.path = format!("/{}{}", pointer::escape(&token.to_string()), self.path);
+    self
+  }
+
+  /// JSON pointer of the value.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Description of the error.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl fmt::Display for ConvertError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.path.is_empty() {
+      write!(f, "{}", self.message)
+    } else {
+      write!(f, "{} at \"{}\"", self.message, self.path)
+    }
+  }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// Conversion of a Rust value to a Yojson value.
+pub trait ToYojson {
+  /// Converts to a Yojson value.
+  fn to_yojson(&self) -> Value;
+}
+
+/// Conversion of a Yojson value to a Rust value.
+pub trait FromYojson: Sized {
+  /// Converts from a Yojson value.
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError>;
+}
+
+impl ToYojson for Value {
+  fn to_yojson(&self) -> Value {
+    self.clone()
+  }
+}
+
+impl FromYojson for Value {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    Ok(value.clone())
+  }
+}
+
+impl ToYojson for () {
+  fn to_yojson(&self) -> Value {
+    Value::Null
+  }
+}
+
+impl FromYojson for () {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    match value {
+      Value::Null => Ok(()),
+      _ => Err(ConvertError::expected("null", value)),
+    }
+  }
+}
+
+impl ToYojson for bool {
+  fn to_yojson(&self) -> Value {
+    Value::Bool(*self)
+  }
+}
+
+impl FromYojson for bool {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    value
+      .as_bool()
+      .ok_or_else(|| ConvertError::expected("bool", value))
+  }
+}
+
+impl ToYojson for i64 {
+  fn to_yojson(&self) -> Value {
+    Value::Integer(*self)
+  }
+}
+
+impl FromYojson for i64 {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    value
+      .as_integer()
+      .ok_or_else(|| ConvertError::expected("integer", value))
+  }
+}
+
+impl ToYojson for f64 {
+  fn to_yojson(&self) -> Value {
+    Value::Float(*self)
+  }
+}
+
+impl FromYojson for f64 {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    match value {
+      Value::Float(f) => Ok(*f),
+      Value::Integer(i) => Ok(*i as f64),
+      _ => Err(ConvertError::expected("float", value)),
+    }
+  }
+}
+
+impl ToYojson for String {
+  fn to_yojson(&self) -> Value {
+    Value::String(self.clone())
+  }
+}
+
+impl FromYojson for String {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    value
+      .as_str()
+      .map(String::from)
+      .ok_or_else(|| ConvertError::expected("string", value))
+  }
+}
+
+impl<T: ToYojson> ToYojson for Box<T> {
+  fn to_yojson(&self) -> Value {
+    (**self).to_yojson()
+  }
+}
+
+impl<T: FromYojson> FromYojson for Box<T> {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    T::from_yojson(value).map(Box::new)
+  }
+}
+
+impl<T: ToYojson> ToYojson for Option<T> {
+  fn to_yojson(&self) -> Value {
+    match self {
+      Some(x) => x.to_yojson(),
+      None => Value::Null,
+    }
+  }
+}
+
+impl<T: FromYojson> FromYojson for Option<T> {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    match value {
+      Value::Null => Ok(None),
+      value => T::from_yojson(value).map(Some),
+    }
+  }
+}
+
+impl<T: ToYojson> ToYojson for Vec<T> {
+  fn to_yojson(&self) -> Value {
+    Value::Array(self.iter().map(ToYojson::to_yojson).collect())
+  }
+}
+
+impl<T: FromYojson> FromYojson for Vec<T> {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    expect_array(value)?
+      .iter()
+      .enumerate()
+      .map(|(i, v)| T::from_yojson(v).map_err(|e| e.at(i)))
+      .collect()
+  }
+}
+
+impl<T: ToYojson> ToYojson for HashMap<String, T> {
+  fn to_yojson(&self) -> Value {
+    Value::Assoc(
+      self
+        .iter()
+        .map(|(k, v)| (k.clone(), v.to_yojson()))
+        .collect(),
+    )
+  }
+}
+
+impl<T: FromYojson> FromYojson for HashMap<String, T> {
+  fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+    expect_assoc(value)?
+      .iter()
+      .map(|(k, v)| Ok((k.clone(), T::from_yojson(v).map_err(|e| e.at(k))?)))
+      .collect()
+  }
+}
+
+macro_rules! tuple_impls {
+  ($($len:expr => ($($n:tt $t:ident),+))+) => {
+    $(
+      impl<$($t: ToYojson),+> ToYojson for ($($t,)+) {
+        fn to_yojson(&self) -> Value {
+          Value::Tuple(vec![$(self.$n.to_yojson()),+])
+        }
+      }
+
+      impl<$($t: FromYojson),+> FromYojson for ($($t,)+) {
+        fn from_yojson(value: &Value) -> Result<Self, ConvertError> {
+          let elements = expect_tuple(value, $len)?;
+          Ok(($($t::from_yojson(&elements[$n]).map_err(|e| e.at($n))?,)+))
+        }
+      }
+    )+
+  };
+}
+
+tuple_impls! {
+  1 => (0 A)
+  2 => (0 A, 1 B)
+  3 => (0 A, 1 B, 2 C)
+  4 => (0 A, 1 B, 2 C, 3 D)
+  5 => (0 A, 1 B, 2 C, 3 D, 4 E)
+  6 => (0 A, 1 B, 2 C, 3 D, 4 E, 5 F)
+}
+
+/// Extracts the elements of an array.
+pub fn expect_array(value: &Value) -> Result<&Vec<Value>, ConvertError> {
+  value
+    .as_array()
+    .ok_or_else(|| ConvertError::expected("array", value))
+}
+
+/// Extracts the members of an assoc.
+pub fn expect_assoc(value: &Value) -> Result<&HashMap<String, Value>, ConvertError> {
+  value
+    .as_assoc()
+    .ok_or_else(|| ConvertError::expected("assoc", value))
+}
+
+/// Extracts the elements of a tuple, or an array, of a given length.
+pub fn expect_tuple(value: &Value, len: usize) -> Result<&[Value], ConvertError> {
+  match value {
+    Value::Tuple(elements) | Value::Array(elements) if elements.len() == len => Ok(elements),
+    Value::Tuple(elements) | Value::Array(elements) => Err(ConvertError::new(format!(
+      "expected a tuple of {} elements, found {}",
+      len,
+      elements.len()
+    ))),
+    _ => Err(ConvertError::expected("tuple", value)),
+  }
+}
+
+/// Converts a value to standard JSON, as `Yojson.Safe.to_basic`.
+///
+/// Tuples become arrays, and variants become `"Name"` or `["Name", argument]`.
+pub fn to_std(value: Value) -> Value {
+  match value {
+    Value::Tuple(elements) | Value::Array(elements) => {
+      Value::Array(elements.into_iter().map(to_std).collect())
+    }
+    Value::Assoc(assoc) => Value::Assoc(assoc.into_iter().map(|(k, v)| (k, to_std(v))).collect()),
+    Value::Variant((name, argument)) => write_variant(&name, argument.map(|a| to_std(*a)), true),
+    value => value,
+  }
+}
+
+/// Writes a variant as `<Name: argument>`, or as `["Name", argument]` in standard JSON.
+pub fn write_variant(name: &str, argument: Option<Value>, std: bool) -> Value {
+  match (argument, std) {
+    (argument, false) => Value::Variant((name.to_string(), argument.map(Box::new))),
+    (None, true) => Value::String(name.to_string()),
+    (Some(argument), true) => Value::Array(vec![Value::String(name.to_string()), argument]),
+  }
+}
+
+/// Reads the constructor and the argument of a variant written as `<Name>`, `<Name: argument>`,
+/// `"Name"`, `["Name"]` or `["Name", argument]`.
+pub fn read_variant(value: &Value) -> Result<(&str, Option<&Value>), ConvertError> {
+  match value {
+    Value::Variant((name, argument)) => Ok((name, argument.as_deref())),
+    Value::String(name) => Ok((name, None)),
+    Value::Array(elements) => match elements.as_slice() {
+      [Value::String(name)] => Ok((name, None)),
+      [Value::String(name), argument] => Ok((name, Some(argument))),
+      _ => Err(ConvertError::expected("variant", value)),
+    },
+    _ => Err(ConvertError::expected("variant", value)),
+  }
+}
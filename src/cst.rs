@@ -0,0 +1,499 @@
+//! Definition of a lossless document for editing Yojson text.
+//!
+//! A `Document` keeps the original text, so comments, whitespace, key order and quoting style
+//! survive edits. Only the parts of the text touched by an edit are rewritten.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::lexer::{self, Lexer, Token, TokenKind};
+use super::parser;
+use super::value::Value;
+
+/// Yojson text which can be edited without losing its formatting.
+#[derive(Clone, Debug)]
+pub struct Document {
+  text: String,
+  root: Node,
+}
+
+/// Error of an edit on a `Document`.
+#[derive(PartialEq, Debug)]
+pub enum EditError {
+  /// The path does not point into the document.
+  NotFound(String),
+  /// A segment of the path does not match the type of the value it is applied to.
+  TypeMismatch {
+    /// Segment of the path.
+    segment: String,
+    /// Type of the value, as returned by `Value::type_str`.
+    found: &'static str,
+  },
+  /// The edit would produce text which is not a Yojson document.
+  Invalid(parser::Error),
+}
+
+impl fmt::Display for EditError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      EditError::NotFound(segment) => write!(f, "\"{}\" is not found", segment),
+      EditError::TypeMismatch { segment, found } => {
+        write!(
+          f,
+          "cannot apply \"{}\" to a value of type {}",
+          segment, found
+        )
+      }
+      EditError::Invalid(err) => write!(f, "{}", err),
+    }
+  }
+}
+
+impl std::error::Error for EditError {}
+
+#[derive(Clone, Debug)]
+struct Node {
+  start: usize,
+  end: usize,
+  kind: NodeKind,
+}
+
+#[derive(Clone, Debug)]
+enum NodeKind {
+  Scalar,
+  Assoc(Vec<Item>),
+  Array(Vec<Item>),
+  Tuple(Vec<Item>),
+  Variant,
+}
+
+/// Member of an assoc, or element of an array or a tuple.
+#[derive(Clone, Debug)]
+struct Item {
+  key: Option<Key>,
+  value: Node,
+  /// Position of the comma following this item.
+  comma: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Key {
+  name: String,
+  quoted: bool,
+  start: usize,
+  end: usize,
+}
+
+impl Item {
+  fn start(&self) -> usize {
+    match &self.key {
+      Some(key) => key.start,
+      None => self.value.start,
+    }
+  }
+}
+
+impl Node {
+  fn type_str(&self) -> &'static str {
+    match self.kind {
+      NodeKind::Scalar => "scalar",
+      NodeKind::Assoc(_) => "assoc",
+      NodeKind::Array(_) => "array",
+      NodeKind::Tuple(_) => "tuple",
+      NodeKind::Variant => "variant",
+    }
+  }
+
+  /// Finds the index of the item named by `segment`.
+  fn find(&self, segment: &str) -> Result<Option<usize>, EditError> {
+    match &self.kind {
+      NodeKind::Assoc(items) => Ok(
+        items
+          .iter()
+          .rposition(|item| item.key.as_ref().map(|key| key.name.as_str()) == Some(segment)),
+      ),
+      NodeKind::Array(items) | NodeKind::Tuple(items) => match segment.parse::<usize>() {
+        Ok(i) if i < items.len() => Ok(Some(i)),
+        Ok(_) => Ok(None),
+        Err(_) => Err(EditError::TypeMismatch {
+          segment: segment.to_string(),
+          found: self.type_str(),
+        }),
+      },
+      _ => Err(EditError::TypeMismatch {
+        segment: segment.to_string(),
+        found: self.type_str(),
+      }),
+    }
+  }
+
+  fn items(&self) -> &[Item] {
+    match &self.kind {
+      NodeKind::Assoc(items) | NodeKind::Array(items) | NodeKind::Tuple(items) => items,
+      _ => &[],
+    }
+  }
+}
+
+struct Builder<'a> {
+  text: &'a str,
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl<'a> Builder<'a> {
+  fn peek(&self) -> Token {
+    self.tokens[self.pos]
+  }
+
+  fn next(&mut self) -> Token {
+    let token = self.tokens[self.pos];
+    self.pos += 1;
+    token
+  }
+
+  fn node(&mut self) -> Node {
+    let token = self.next();
+    match token.kind {
+      TokenKind::LBrace => self.items(token.start, TokenKind::RBrace, NodeKind::Assoc),
+      TokenKind::LBracket => self.items(token.start, TokenKind::RBracket, NodeKind::Array),
+      TokenKind::LParen => self.items(token.start, TokenKind::RParen, NodeKind::Tuple),
+      TokenKind::LAngle => {
+        if !matches!(self.peek().kind, TokenKind::RAngle | TokenKind::Colon) {
+          self.next();
+        }
+        if self.next().kind == TokenKind::Colon {
+          self.node();
+          self.next();
+        }
+        Node {
+          start: token.start,
+          end: self.tokens[self.pos - 1].end,
+          kind: NodeKind::Variant,
+        }
+      }
+      _ => Node {
+        start: token.start,
+        end: token.end,
+        kind: NodeKind::Scalar,
+      },
+    }
+  }
+
+  fn items(&mut self, start: usize, close: TokenKind, f: fn(Vec<Item>) -> NodeKind) -> Node {
+    let keyed = close == TokenKind::RBrace;
+    let mut items = Vec::new();
+    loop {
+      let token = self.peek();
+      if token.kind == close {
+        self.next();
+        return Node {
+          start,
+          end: token.end,
+          kind: f(items),
+        };
+      }
+      let key = if keyed {
+        let token = self.next();
+        if token.kind != TokenKind::Colon {
+          self.next();
+        }
+        Some(self.key(token))
+      } else {
+        None
+      };
+      let value = self.node();
+      let comma = if self.peek().kind == TokenKind::Comma {
+        Some(self.next().start)
+      } else {
+        None
+      };
+      items.push(Item { key, value, comma })
+    }
+  }
+
+  fn key(&self, token: Token) -> Key {
+    let str = &self.text[token.start..token.end];
+    match token.kind {
+      TokenKind::String => Key {
+        name: lexer::unescape(&str[1..str.len() - 1]),
+        quoted: true,
+        start: token.start,
+        end: token.end,
+      },
+      // An empty unquoted name is accepted by the grammar; the token is the colon then.
+      TokenKind::Colon => Key {
+        name: String::new(),
+        quoted: false,
+        start: token.start,
+        end: token.start,
+      },
+      _ => Key {
+        name: str.to_string(),
+        quoted: false,
+        start: token.start,
+        end: token.end,
+      },
+    }
+  }
+}
+
+impl Document {
+  /// Parses a Yojson text into a document.
+  pub fn parse(text: &str) -> Result<Document, parser::Error> {
+    parser::parse(text)?;
+    let tokens = Lexer::new(text)
+      .filter(|token| !token.kind.is_trivia())
+      .collect::<Vec<_>>();
+    let mut builder = Builder {
+      text,
+      tokens,
+      pos: 0,
+    };
+    let root = builder.node();
+    Ok(Document {
+      text: text.to_string(),
+      root,
+    })
+  }
+
+  /// Returns the text of this document.
+  pub fn as_str(&self) -> &str {
+    &self.text
+  }
+
+  /// Returns the value represented by this document.
+  pub fn value(&self) -> Value {
+    parser::parse(&self.text).unwrap()
+  }
+
+  /// Returns the value at the path.
+  ///
+  /// Each segment of the path is a member name of an assoc or an index of an array or a tuple.
+  pub fn get(&self, path: &[&str]) -> Option<Value> {
+    let mut value = self.value();
+    for segment in path {
+      value = match value {
+        Value::Assoc(mut assoc) => assoc.remove(*segment)?,
+        Value::Array(mut array) | Value::Tuple(mut array) => {
+          let i = segment.parse::<usize>().ok()?;
+          if i < array.len() {
+            array.swap_remove(i)
+          } else {
+            return None;
+          }
+        }
+        _ => return None,
+      }
+    }
+    Some(value)
+  }
+
+  /// Sets the value at the path.
+  ///
+  /// A missing member is appended to its assoc, and missing assocs on the way are created.
+  /// An index equal to the length of an array appends to the array.
+  pub fn set(&mut self, path: &[&str], value: &Value) -> Result<(), EditError> {
+    let mut node = &self.root;
+    for (i, segment) in path.iter().enumerate() {
+      match node.find(segment)? {
+        Some(index) => node = &node.items()[index].value,
+        None => {
+          let mut value = value.clone();
+          for segment in path[i + 1..].iter().rev() {
+            let mut assoc = std::collections::HashMap::new();
+            assoc.insert(segment.to_string(), value);
+            value = Value::Assoc(assoc);
+          }
+          let (pos, str) = self.append(node, segment, &value)?;
+          return self.splice(pos, pos, &str);
+        }
+      }
+    }
+    let (start, end) = (node.start, node.end);
+    self.splice(start, end, &crate::to_string(value.clone()))
+  }
+
+  /// Removes the value at the path and returns it.
+  pub fn remove(&mut self, path: &[&str]) -> Result<Value, EditError> {
+    let (last, parents) = match path.split_last() {
+      Some(split) => split,
+      None => return Err(EditError::NotFound(String::new())),
+    };
+    let mut node = &self.root;
+    for segment in parents {
+      match node.find(segment)? {
+        Some(index) => node = &node.items()[index].value,
+        None => return Err(EditError::NotFound(segment.to_string())),
+      }
+    }
+    let index = match node.find(last)? {
+      Some(index) => index,
+      None => return Err(EditError::NotFound(last.to_string())),
+    };
+    let items = node.items();
+    let item = &items[index];
+    let (start, end) = match item.comma {
+      Some(_) => (item.start(), items[index + 1].start()),
+      None if index > 0 => (items[index - 1].comma.unwrap(), item.value.end),
+      None => (item.start(), item.value.end),
+    };
+    let removed = self.get(path).unwrap();
+    self.splice(start, end, "")?;
+    Ok(removed)
+  }
+
+  /// Sorts the members of every assoc by name.
+  ///
+  /// Each member moves with its value and with a comment on the same line after it. Other text
+  /// between members, such as a comment on its own line, stays in place. Members with the same
+  /// name keep their order. Assocs in the arguments of variants are not sorted.
+  pub fn sort_keys(&mut self) {
+    let text = self.sorted(&self.root);
+    let (start, end) = (self.root.start, self.root.end);
+    // Reordering members keeps the text a valid document.
+    self.splice(start, end, &text).unwrap();
+  }
+
+  /// Returns the text of a node with the members of its assocs sorted.
+  fn sorted(&self, node: &Node) -> String {
+    let items = node.items();
+    let texts = items
+      .iter()
+      .map(|item| {
+        let key = &self.text[item.start()..item.value.start];
+        format!("{}{}", key, self.sorted(&item.value))
+      })
+      .collect::<Vec<_>>();
+    let mut order = (0..items.len()).collect::<Vec<_>>();
+    if let NodeKind::Assoc(_) = node.kind {
+      order.sort_by_key(|&i| &items[i].key.as_ref().unwrap().name);
+    }
+    let trailing = items
+      .iter()
+      .map(|item| self.trailing(item))
+      .collect::<Vec<_>>();
+    let mut text = String::new();
+    let mut pos = node.start;
+    for ((item, &(anchor, end)), &i) in items.iter().zip(&trailing).zip(&order) {
+      text.push_str(&self.text[pos..item.start()]);
+      text.push_str(&texts[i]);
+      text.push_str(&self.text[item.value.end..anchor]);
+      pos = end.unwrap_or(anchor);
+      if let (start, Some(end)) = trailing[i] {
+        let comment = &self.text[start..end];
+        text.push_str(comment);
+        let rest = self.text[pos..].split('\n').next().unwrap();
+        if comment.trim_start().starts_with("//") && !rest.trim().is_empty() {
+          text.push('\n');
+        }
+      }
+    }
+    text.push_str(&self.text[pos..node.end]);
+    text
+  }
+
+  /// Returns the position after an item and its comma on the same line, and the end of the
+  /// comment which follows on that line, if any.
+  fn trailing(&self, item: &Item) -> (usize, Option<usize>) {
+    let skip = |pos: usize| {
+      let rest = &self.text[pos..];
+      pos + rest.len() - rest.trim_start_matches([' ', '\t']).len()
+    };
+    let mut anchor = item.value.end;
+    if let Some(comma) = item.comma {
+      if skip(anchor) == comma {
+        anchor = comma + 1;
+      }
+    }
+    let start = skip(anchor);
+    let rest = &self.text[start..];
+    let line = rest[..rest.find('\n').unwrap_or(rest.len())].trim_end_matches('\r');
+    let end = if line.starts_with("//") {
+      Some(start + line.len())
+    } else if let Some(comment) = line.strip_prefix("/*") {
+      comment.find("*/").map(|i| start + i + 4)
+    } else {
+      None
+    };
+    (anchor, end)
+  }
+
+  /// Returns the position and the text to insert for a new member or element.
+  fn append(
+    &self,
+    node: &Node,
+    segment: &str,
+    value: &Value,
+  ) -> Result<(usize, String), EditError> {
+    let value_str = crate::to_string(value.clone());
+    match &node.kind {
+      NodeKind::Assoc(items) => {
+        let quoted = items
+          .last()
+          .is_some_and(|item| item.key.as_ref().unwrap().quoted)
+          || !crate::is_unquoted_name(segment);
+        let name = if quoted {
+          crate::quote_string(segment)
+        } else {
+          segment.to_string()
+        };
+        match items.last() {
+          Some(item) => {
+            let key = item.key.as_ref().unwrap();
+            let sep = &self.text[key.end..item.value.start];
+            let sep = if sep.contains('/') { ": " } else { sep };
+            Ok((
+              item.value.end,
+              format!(",{}{}{}{}", self.indent(item), name, sep, value_str),
+            ))
+          }
+          None => Ok((node.start + 1, format!("{}: {}", name, value_str))),
+        }
+      }
+      NodeKind::Array(items) if segment.parse::<usize>() == Ok(items.len()) => match items.last() {
+        Some(item) => Ok((
+          item.value.end,
+          format!(",{}{}", self.indent(item), value_str),
+        )),
+        None => Ok((node.start + 1, value_str)),
+      },
+      _ => Err(EditError::NotFound(segment.to_string())),
+    }
+  }
+
+  /// Returns the whitespace preceding an item, or a space if it is on the same line.
+  fn indent(&self, item: &Item) -> &str {
+    let before = &self.text[..item.start()];
+    let ws = before.trim_end_matches([' ', '\t', '\r', '\n']);
+    let ws = &before[ws.len()..];
+    if ws.contains('\n') {
+      ws
+    } else {
+      " "
+    }
+  }
+
+  fn splice(&mut self, start: usize, end: usize, str: &str) -> Result<(), EditError> {
+    let mut text = String::with_capacity(self.text.len() + str.len());
+    text.push_str(&self.text[..start]);
+    text.push_str(str);
+    text.push_str(&self.text[end..]);
+    *self = Document::parse(&text).map_err(EditError::Invalid)?;
+    Ok(())
+  }
+}
+
+impl FromStr for Document {
+  type Err = parser::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Document::parse(s)
+  }
+}
+
+impl fmt::Display for Document {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(&self.text)
+  }
+}
@@ -0,0 +1,216 @@
+//! Definition of the structural difference between two values.
+//!
+//! Changes are located by JSON pointers (see the `pointer` module): members of assocs are
+//! compared by name regardless of their order, elements of arrays and of tuples of the same
+//! length by position, and the argument of a variant with the same constructor is compared at
+//! `-payload`. Tuples of different lengths are changed as a whole.
+
+use std::fmt;
+
+use super::pointer;
+use super::value::Value;
+
+/// Change from a value to another.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Change {
+  /// The value at the path exists only in the right value.
+  Added {
+    /// JSON pointer of the value.
+    path: String,
+    /// Added value.
+    value: Value,
+  },
+  /// The value at the path exists only in the left value.
+  Removed {
+    /// JSON pointer of the value.
+    path: String,
+    /// Removed value.
+    value: Value,
+  },
+  /// The value at the path differs.
+  Changed {
+    /// JSON pointer of the value.
+    path: String,
+    /// Value in the left value.
+    old: Value,
+    /// Value in the right value.
+    new: Value,
+  },
+}
+
+impl Change {
+  /// JSON pointer of the change.
+  pub fn path(&self) -> &str {
+    match self {
+      Change::Added { path, .. } | Change::Removed { path, .. } | Change::Changed { path, .. } => {
+        path
+      }
+    }
+  }
+}
+
+/// Tests whether two scalars are the same, where NaN is the same as NaN.
+fn same_scalar(left: &Value, right: &Value) -> bool {
+  match (left, right) {
+    (Value::Float(a), Value::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+    _ => left == right,
+  }
+}
+
+/// Computes the changes from `left` to `right`.
+///
+/// Changes are listed in the order of a depth-first walk, with the members of assocs sorted by name.
+///
+/// ```
+/// let left = yojson_rs::parser::parse("{a : 1, b : [1, 2]}").unwrap();
+/// let right = yojson_rs::parser::parse("{a : 2, b : [1]}").unwrap();
+/// let changes = yojson_rs::diff::diff(&left, &right);
+/// assert_eq!(changes[0].path(), "/a");
+/// assert_eq!(changes[1].path(), "/b/1");
+/// ```
+pub fn diff(left: &Value, right: &Value) -> Vec<Change> {
+  enum Task<'a> {
+    Compare(String, &'a Value, &'a Value),
+    Emit(Change),
+  }
+  let added = |path, value: &Value| {
+    Task::Emit(Change::Added {
+      path,
+      value: value.clone(),
+    })
+  };
+  let removed = |path, value: &Value| {
+    Task::Emit(Change::Removed {
+      path,
+      value: value.clone(),
+    })
+  };
+  let mut changes = Vec::new();
+  let mut stack = vec![Task::Compare(String::new(), left, right)];
+  while let Some(task) = stack.pop() {
+    let (path, left, right) = match task {
+      Task::Compare(path, left, right) => (path, left, right),
+      Task::Emit(change) => {
+        changes.push(change);
+        continue;
+      }
+    };
+    let child = |token: &str| format!("{}/{}", path, pointer::escape(token));
+    let mut tasks = Vec::new();
+    match (left, right) {
+      (Value::Assoc(l), Value::Assoc(r)) => {
+        let mut names = l.keys().chain(r.keys()).collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        for name in names {
+          match (l.get(name), r.get(name)) {
+            (Some(l), Some(r)) => tasks.push(Task::Compare(child(name), l, r)),
+            (Some(value), None) => tasks.push(removed(child(name), value)),
+            (None, Some(value)) => tasks.push(added(child(name), value)),
+            (None, None) => (),
+          }
+        }
+      }
+      (Value::Array(l), Value::Array(r)) | (Value::Tuple(l), Value::Tuple(r))
+        if matches!(left, Value::Array(_)) || l.len() == r.len() =>
+      {
+        for (i, (l, r)) in l.iter().zip(r).enumerate() {
+          tasks.push(Task::Compare(child(&i.to_string()), l, r));
+        }
+        for (i, value) in l.iter().enumerate().skip(r.len()) {
+          tasks.push(removed(child(&i.to_string()), value));
+        }
+        for (i, value) in r.iter().enumerate().skip(l.len()) {
+          tasks.push(added(child(&i.to_string()), value));
+        }
+      }
+      (Value::Variant((l_name, l)), Value::Variant((r_name, r))) if l_name == r_name => {
+        let path = child(pointer::PAYLOAD);
+        match (l, r) {
+          (Some(l), Some(r)) => tasks.push(Task::Compare(path, l, r)),
+          (Some(value), None) => tasks.push(removed(path, value)),
+          (None, Some(value)) => tasks.push(added(path, value)),
+          (None, None) => (),
+        }
+      }
+      (left, right) => {
+        if !same_scalar(left, right) {
+          changes.push(Change::Changed {
+            path,
+            old: left.clone(),
+            new: right.clone(),
+          })
+        }
+      }
+    }
+    stack.extend(tasks.into_iter().rev());
+  }
+  changes
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(&render_change(self, false))
+  }
+}
+
+fn display_path(path: &str) -> &str {
+  if path.is_empty() {
+    "(root)"
+  } else {
+    path
+  }
+}
+
+fn render_change(change: &Change, color: bool) -> String {
+  let paint = |code: &str, s: String| {
+    if color {
+      format!("\x1b[{}m{}\x1b[0m", code, s)
+    } else {
+      s
+    }
+  };
+  let value = |value: &Value| super::to_string(value.clone());
+  match change {
+    Change::Added { path, value: v } => {
+      paint("32", format!("+ {}: {}", display_path(path), value(v)))
+    }
+    Change::Removed { path, value: v } => {
+      paint("31", format!("- {}: {}", display_path(path), value(v)))
+    }
+    Change::Changed { path, old, new } => format!(
+      "~ {}: {} -> {}",
+      display_path(path),
+      paint("31", value(old)),
+      paint("32", value(new))
+    ),
+  }
+}
+
+/// Renders changes one per line, prefixed with `+`, `-` or `~`,
+/// and colored with ANSI escape sequences if `color` is true.
+pub fn render(changes: &[Change], color: bool) -> String {
+  let mut s = String::new();
+  for change in changes {
+    s.push_str(&render_change(change, color));
+    s.push('\n');
+  }
+  s
+}
+
+/// Asserts that two values are equal, and panics with the rendered differences otherwise.
+///
+/// The output is colored unless the `NO_COLOR` environment variable is set.
+#[macro_export]
+macro_rules! assert_value_eq {
+  ($left:expr, $right:expr $(,)?) => {{
+    let changes = $crate::diff::diff(&$left, &$right);
+    if !changes.is_empty() {
+      let color = ::std::env::var_os("NO_COLOR").is_none();
+      panic!(
+        "assertion failed: values differ\n{}",
+        $crate::diff::render(&changes, color)
+      );
+    }
+  }};
+}
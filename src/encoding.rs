@@ -0,0 +1,111 @@
+//! Definition of the detection and the decoding of the encoding of a text.
+
+use std::borrow::Cow;
+
+use super::parser::{Error, ErrorKind, ParseOptions};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Encoding {
+  Utf8,
+  Utf16Be,
+  Utf16Le,
+  Utf32Be,
+  Utf32Le,
+}
+
+impl Encoding {
+  fn name(self) -> &'static str {
+    match self {
+      Encoding::Utf8 => "UTF-8",
+      Encoding::Utf16Be | Encoding::Utf16Le => "UTF-16",
+      Encoding::Utf32Be | Encoding::Utf32Le => "UTF-32",
+    }
+  }
+}
+
+/// Detects the encoding from a byte order mark, or from the pattern of zero bytes
+/// in the first four bytes as described in RFC 4627.
+/// Returns the encoding and the length of the byte order mark of UTF-16 and UTF-32.
+fn detect(bytes: &[u8]) -> (Encoding, usize) {
+  match bytes {
+    [0, 0, 0xfe, 0xff, ..] => (Encoding::Utf32Be, 4),
+    [0xff, 0xfe, 0, 0, ..] => (Encoding::Utf32Le, 4),
+    [0xfe, 0xff, ..] => (Encoding::Utf16Be, 2),
+    [0xff, 0xfe, ..] => (Encoding::Utf16Le, 2),
+    [0, 0, 0, _, ..] => (Encoding::Utf32Be, 0),
+    [_, 0, 0, 0, ..] => (Encoding::Utf32Le, 0),
+    [0, _, 0, _, ..] => (Encoding::Utf16Be, 0),
+    [_, 0, _, 0, ..] => (Encoding::Utf16Le, 0),
+    _ => (Encoding::Utf8, 0),
+  }
+}
+
+/// Decodes bytes into a text.
+///
+/// A UTF-8 byte order mark is left in the text for the parser.
+/// The offset of an error is the offset in the bytes.
+pub(crate) fn decode<'a>(bytes: &'a [u8], options: &ParseOptions) -> Result<Cow<'a, str>, Error> {
+  let (encoding, bom) = if options.detect_encoding {
+    detect(bytes)
+  } else {
+    (Encoding::Utf8, 0)
+  };
+  if encoding == Encoding::Utf8 {
+    return match std::str::from_utf8(bytes) {
+      Ok(text) => Ok(Cow::Borrowed(text)),
+      Err(err) => {
+        let offset = err.valid_up_to();
+        let prefix = std::str::from_utf8(&bytes[..offset]).unwrap();
+        Err(Error::at(
+          ErrorKind::InvalidEncoding("UTF-8"),
+          prefix,
+          offset,
+        ))
+      }
+    };
+  }
+  if bom > 0 && !options.allow_bom {
+    return Err(Error::new(ErrorKind::ByteOrderMark, "", 0));
+  }
+  let width = match encoding {
+    Encoding::Utf16Be | Encoding::Utf16Le => 2,
+    _ => 4,
+  };
+  let mut text = String::with_capacity(bytes.len() / width);
+  let mut offset = bom;
+  while offset < bytes.len() {
+    let invalid = |text: &str| Error::at(ErrorKind::InvalidEncoding(encoding.name()), text, offset);
+    let unit = match bytes.get(offset..offset + width) {
+      Some(unit) => unit,
+      None => return Err(invalid(&text)),
+    };
+    let code = match encoding {
+      Encoding::Utf16Be => u32::from(u16::from_be_bytes([unit[0], unit[1]])),
+      Encoding::Utf16Le => u32::from(u16::from_le_bytes([unit[0], unit[1]])),
+      Encoding::Utf32Be => u32::from_be_bytes([unit[0], unit[1], unit[2], unit[3]]),
+      _ => u32::from_le_bytes([unit[0], unit[1], unit[2], unit[3]]),
+    };
+    let (code, len) = if width == 2 && (0xd800..0xdc00).contains(&code) {
+      let low = match bytes.get(offset + 2..offset + 4) {
+        Some(unit) if encoding == Encoding::Utf16Be => u16::from_be_bytes([unit[0], unit[1]]),
+        Some(unit) => u16::from_le_bytes([unit[0], unit[1]]),
+        None => return Err(invalid(&text)),
+      };
+      if !(0xdc00..0xe000).contains(&low) {
+        return Err(invalid(&text));
+      }
+      (
+        0x10000 + ((code - 0xd800) << 10) + (u32::from(low) - 0xdc00),
+        4,
+      )
+    } else {
+      (code, width)
+    };
+    match std::char::from_u32(code) {
+      Some(c) => text.push(c),
+      None => return Err(invalid(&text)),
+    }
+    offset += len;
+  }
+  Ok(Cow::Owned(text))
+}
@@ -0,0 +1,274 @@
+//! Definition of the conversion between Yojson values and `serde_json` values.
+//!
+//! This module is enabled by the feature `serde_json`.
+//!
+//! Yojson values are converted to standard JSON as follows:
+//! - Tuples become arrays.
+//! - Variants become `"Name"` and `["Name", argument]` with `VariantRepr::Basic`, as
+//!   `convert::to_std` and `yojson --std`, `["Name"]` and `["Name", argument]` with
+//!   `VariantRepr::Array`, or `"Name"` and `{"Name": argument}` with `VariantRepr::Object`.
+//! - `NaN` and infinite floats are errors with `NonFinite::Error`, `null` with `NonFinite::Null`,
+//!   or the strings `"NaN"`, `"Infinity"` and `"-Infinity"` with `NonFinite::String`.
+//!
+//! JSON values are converted back without guessing tuples or variants, so arrays stay arrays.
+//! Integers beyond the range of `i64` are errors with `IntegerOverflow::Error`, or
+//! floats with `IntegerOverflow::Float`, which may lose precision.
+//!
+//! `TryFrom` converts with the default options in both directions.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::pointer;
+use super::value::Value;
+
+/// Representation of variants in JSON.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum VariantRepr {
+  /// `"Name"` and `["Name", argument]`, as `Yojson.Safe.to_basic`.
+  Basic,
+  /// `["Name"]` and `["Name", argument]`, as `ppx_deriving_yojson`.
+  Array,
+  /// `"Name"` and `{"Name": argument}`, as the externally tagged enums of serde.
+  Object,
+}
+
+/// Representation of `NaN` and infinite floats in JSON.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum NonFinite {
+  /// Fails the conversion.
+  Error,
+  /// Writes `null`.
+  Null,
+  /// Writes `"NaN"`, `"Infinity"` or `"-Infinity"`.
+  String,
+}
+
+/// Handling of JSON integers beyond the range of `i64`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum IntegerOverflow {
+  /// Fails the conversion.
+  Error,
+  /// Converts the integer to the nearest float.
+  Float,
+}
+
+/// Options of the conversion.
+///
+/// By default, variants are written as `Yojson.Safe.to_basic` does, and non-finite floats and overflowing integers are errors.
+///
+/// ```
+/// use yojson_rs::json::{JsonOptions, NonFinite, VariantRepr};
+/// let options = JsonOptions::new()
+///   .variants(VariantRepr::Object)
+///   .non_finite(NonFinite::Null);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct JsonOptions {
+  pub(crate) variants: VariantRepr,
+  pub(crate) non_finite: NonFinite,
+  pub(crate) integer_overflow: IntegerOverflow,
+}
+
+impl Default for JsonOptions {
+  fn default() -> Self {
+    JsonOptions {
+      variants: VariantRepr::Basic,
+      non_finite: NonFinite::Error,
+      integer_overflow: IntegerOverflow::Error,
+    }
+  }
+}
+
+impl JsonOptions {
+  /// Creates the default options.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the representation of variants.
+  pub fn variants(mut self, repr: VariantRepr) -> Self {
+    self.variants = repr;
+    self
+  }
+
+  /// Sets the representation of non-finite floats.
+  pub fn non_finite(mut self, non_finite: NonFinite) -> Self {
+    self.non_finite = non_finite;
+    self
+  }
+
+  /// Sets the handling of integers beyond the range of `i64`.
+  pub fn integer_overflow(mut self, overflow: IntegerOverflow) -> Self {
+    self.integer_overflow = overflow;
+    self
+  }
+}
+
+/// Error of the conversion.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct JsonError {
+  path: String,
+  message: String,
+}
+
+impl JsonError {
+  fn new(path: &str, message: impl Into<String>) -> Self {
+    JsonError {
+      path: path.to_string(),
+      message: message.into(),
+    }
+  }
+
+  /// JSON pointer of the value.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Description of the error.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl fmt::Display for JsonError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} at \"{}\"", self.message, self.path)
+  }
+}
+
+impl std::error::Error for JsonError {}
+
+fn child(path: &str, token: &str) -> String {
+  format!("{}/{}", path, pointer::escape(token))
+}
+
+fn to_json_at(
+  value: &Value,
+  path: &str,
+  options: &JsonOptions,
+) -> Result<serde_json::Value, JsonError> {
+  Ok(match value {
+    Value::Null => serde_json::Value::Null,
+    Value::Bool(b) => serde_json::Value::Bool(*b),
+    Value::Integer(i) => serde_json::Value::from(*i),
+    Value::Float(f) => match (serde_json::Number::from_f64(*f), options.non_finite) {
+      (Some(n), _) => serde_json::Value::Number(n),
+      (None, NonFinite::Error) => {
+        return Err(JsonError::new(
+          path,
+          format!("{} is not allowed in JSON", super::float_to_string(*f)),
+        ))
+      }
+      (None, NonFinite::Null) => serde_json::Value::Null,
+      (None, NonFinite::String) => serde_json::Value::String(super::float_to_string(*f)),
+    },
+    Value::String(s) => serde_json::Value::String(s.clone()),
+    Value::Assoc(assoc) => {
+      let mut map = serde_json::Map::new();
+      for (name, value) in assoc {
+        map.insert(
+          name.clone(),
+          to_json_at(value, &child(path, name), options)?,
+        );
+      }
+      serde_json::Value::Object(map)
+    }
+    Value::Array(elements) | Value::Tuple(elements) => serde_json::Value::Array(
+      elements
+        .iter()
+        .enumerate()
+        .map(|(i, value)| to_json_at(value, &child(path, &i.to_string()), options))
+        .collect::<Result<_, _>>()?,
+    ),
+    Value::Variant((name, argument)) => {
+      let argument = match argument {
+        Some(argument) => Some(to_json_at(
+          argument,
+          &child(path, pointer::PAYLOAD),
+          options,
+        )?),
+        None => None,
+      };
+      let string = serde_json::Value::String(name.clone());
+      match (options.variants, argument) {
+        (VariantRepr::Basic, None) | (VariantRepr::Object, None) => string,
+        (VariantRepr::Array, None) => serde_json::Value::Array(vec![string]),
+        (VariantRepr::Basic, Some(argument)) | (VariantRepr::Array, Some(argument)) => {
+          serde_json::Value::Array(vec![string, argument])
+        }
+        (VariantRepr::Object, Some(argument)) => {
+          let mut map = serde_json::Map::new();
+          map.insert(name.clone(), argument);
+          serde_json::Value::Object(map)
+        }
+      }
+    }
+  })
+}
+
+/// Converts a Yojson value to a `serde_json` value.
+pub fn to_json(value: &Value, options: &JsonOptions) -> Result<serde_json::Value, JsonError> {
+  to_json_at(value, "", options)
+}
+
+fn from_json_at(
+  json: &serde_json::Value,
+  path: &str,
+  options: &JsonOptions,
+) -> Result<Value, JsonError> {
+  Ok(match json {
+    serde_json::Value::Null => Value::Null,
+    serde_json::Value::Bool(b) => Value::Bool(*b),
+    serde_json::Value::Number(n) => match n.as_i64() {
+      Some(i) => Value::Integer(i),
+      None if n.is_f64() || options.integer_overflow == IntegerOverflow::Float => {
+        Value::Float(n.as_f64().unwrap())
+      }
+      None => {
+        return Err(JsonError::new(
+          path,
+          format!("integer {} is out of the range of i64", n),
+        ))
+      }
+    },
+    serde_json::Value::String(s) => Value::String(s.clone()),
+    serde_json::Value::Array(elements) => Value::Array(
+      elements
+        .iter()
+        .enumerate()
+        .map(|(i, json)| from_json_at(json, &child(path, &i.to_string()), options))
+        .collect::<Result<_, _>>()?,
+    ),
+    serde_json::Value::Object(map) => Value::Assoc(
+      map
+        .iter()
+        .map(|(name, json)| {
+          Ok((
+            name.clone(),
+            from_json_at(json, &child(path, name), options)?,
+          ))
+        })
+        .collect::<Result<_, _>>()?,
+    ),
+  })
+}
+
+/// Converts a `serde_json` value to a Yojson value.
+pub fn from_json(json: &serde_json::Value, options: &JsonOptions) -> Result<Value, JsonError> {
+  from_json_at(json, "", options)
+}
+
+impl TryFrom<Value> for serde_json::Value {
+  type Error = JsonError;
+  fn try_from(value: Value) -> Result<Self, Self::Error> {
+    to_json(&value, &JsonOptions::default())
+  }
+}
+
+impl TryFrom<serde_json::Value> for Value {
+  type Error = JsonError;
+  fn try_from(json: serde_json::Value) -> Result<Self, Self::Error> {
+    from_json(&json, &JsonOptions::default())
+  }
+}
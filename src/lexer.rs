@@ -0,0 +1,240 @@
+//! Definition of a lossless tokenizer.
+//!
+//! Every byte of the input belongs to exactly one token, including whitespace and comments,
+//! so the text can be reproduced by concatenating the spans of the tokens.
+
+/// Kind of a token.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum TokenKind {
+  Whitespace,
+  Comment,
+  /// Block comment reaching the end of the input.
+  UnterminatedComment,
+  LBrace,
+  RBrace,
+  LBracket,
+  RBracket,
+  LParen,
+  RParen,
+  LAngle,
+  RAngle,
+  Colon,
+  Comma,
+  /// String quoted with `"` or `'`, including the quotes.
+  String,
+  /// Quoted string reaching the end of the input.
+  UnterminatedString,
+  /// Name matching `[A-Za-z][A-Za-z_0-9]*`, which includes `null`, `true`, `NaN`, ...
+  Ident,
+  /// Number literal, including the relaxed forms such as `0x1f`, `+1` and `.5`, or `-Infinity`.
+  Number,
+  /// Any character which does not start a token.
+  Unknown,
+  /// End of the input. It is never returned by `Lexer`.
+  Eof,
+}
+
+impl TokenKind {
+  /// Tests whether this token carries no meaning for the parser.
+  pub(crate) fn is_trivia(self) -> bool {
+    matches!(self, TokenKind::Whitespace | TokenKind::Comment)
+  }
+}
+
+/// Token with its byte range in the input.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) struct Token {
+  pub kind: TokenKind,
+  pub start: usize,
+  pub end: usize,
+}
+
+/// Iterator over the tokens of a text.
+pub(crate) struct Lexer<'a> {
+  text: &'a str,
+  pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+  pub(crate) fn new(text: &'a str) -> Self {
+    Lexer { text, pos: 0 }
+  }
+
+  fn peek_byte(&self, offset: usize) -> Option<u8> {
+    self.text.as_bytes().get(self.pos + offset).copied()
+  }
+
+  fn eat_while(&mut self, f: impl Fn(u8) -> bool) {
+    while let Some(b) = self.peek_byte(0) {
+      if f(b) {
+        self.pos += 1
+      } else {
+        break;
+      }
+    }
+  }
+
+  fn lex_string(&mut self, quote: u8) -> bool {
+    self.pos += 1;
+    while let Some(b) = self.peek_byte(0) {
+      match b {
+        b if b == quote => {
+          self.pos += 1;
+          return true;
+        }
+        b'\\' => {
+          self.pos += 1;
+          if let Some(c) = self.text[self.pos..].chars().next() {
+            self.pos += c.len_utf8()
+          }
+        }
+        _ => self.pos += 1,
+      }
+    }
+    false
+  }
+
+  fn lex_number(&mut self) {
+    if let Some(b'-') | Some(b'+') = self.peek_byte(0) {
+      self.pos += 1;
+      if self.text[self.pos..].starts_with("Infinity") {
+        self.pos += "Infinity".len();
+        return;
+      }
+    }
+    if self.peek_byte(0) == Some(b'0') {
+      if let Some(b'x') | Some(b'X') | Some(b'b') | Some(b'B') = self.peek_byte(1) {
+        self.pos += 2;
+        self.eat_while(|b| b.is_ascii_alphanumeric());
+        return;
+      }
+    }
+    self.eat_while(|b| b.is_ascii_digit());
+    if self.peek_byte(0) == Some(b'.') {
+      self.pos += 1;
+      self.eat_while(|b| b.is_ascii_digit());
+    }
+    if let Some(b'e') | Some(b'E') = self.peek_byte(0) {
+      self.pos += 1;
+      if let Some(b'-') | Some(b'+') = self.peek_byte(0) {
+        self.pos += 1;
+      }
+      self.eat_while(|b| b.is_ascii_digit());
+    }
+  }
+
+  fn lex_comment(&mut self) -> Option<TokenKind> {
+    match self.peek_byte(1) {
+      Some(b'/') => {
+        match self.text[self.pos..].find('\n') {
+          Some(i) => self.pos += i + 1,
+          None => self.pos = self.text.len(),
+        }
+        Some(TokenKind::Comment)
+      }
+      Some(b'*') => match self.text[self.pos + 2..].find("*/") {
+        Some(i) => {
+          self.pos += i + 4;
+          Some(TokenKind::Comment)
+        }
+        None => {
+          self.pos = self.text.len();
+          Some(TokenKind::UnterminatedComment)
+        }
+      },
+      _ => None,
+    }
+  }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+  type Item = Token;
+
+  fn next(&mut self) -> Option<Token> {
+    let start = self.pos;
+    let b = self.peek_byte(0)?;
+    let kind = match b {
+      b' ' | b'\t' | b'\r' | b'\n' => {
+        self.eat_while(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n'));
+        TokenKind::Whitespace
+      }
+      b'/' => match self.lex_comment() {
+        Some(kind) => kind,
+        None => {
+          self.pos += 1;
+          TokenKind::Unknown
+        }
+      },
+      b'{' | b'}' | b'[' | b']' | b'(' | b')' | b'<' | b'>' | b':' | b',' => {
+        self.pos += 1;
+        match b {
+          b'{' => TokenKind::LBrace,
+          b'}' => TokenKind::RBrace,
+          b'[' => TokenKind::LBracket,
+          b']' => TokenKind::RBracket,
+          b'(' => TokenKind::LParen,
+          b')' => TokenKind::RParen,
+          b'<' => TokenKind::LAngle,
+          b'>' => TokenKind::RAngle,
+          b':' => TokenKind::Colon,
+          _ => TokenKind::Comma,
+        }
+      }
+      b'"' | b'\'' => {
+        if self.lex_string(b) {
+          TokenKind::String
+        } else {
+          TokenKind::UnterminatedString
+        }
+      }
+      b'-' | b'+' | b'0'..=b'9' => {
+        self.lex_number();
+        TokenKind::Number
+      }
+      b'.' if self.peek_byte(1).is_some_and(|b| b.is_ascii_digit()) => {
+        self.lex_number();
+        TokenKind::Number
+      }
+      b'a'..=b'z' | b'A'..=b'Z' => {
+        self.eat_while(|b| b.is_ascii_alphanumeric() || b == b'_');
+        TokenKind::Ident
+      }
+      _ => {
+        self.pos += self.text[self.pos..].chars().next().unwrap().len_utf8();
+        TokenKind::Unknown
+      }
+    };
+    Some(Token {
+      kind,
+      start,
+      end: self.pos,
+    })
+  }
+}
+
+/// Decode the escape sequences of the contents of a quoted string.
+pub(crate) fn unescape(str: &str) -> String {
+  let mut s = String::with_capacity(str.len());
+  let mut chars = str.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      s.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('b') => s.push('\u{0008}'),
+      Some('f') => s.push('\u{000c}'),
+      Some('n') => s.push('\n'),
+      Some('r') => s.push('\r'),
+      Some('t') => s.push('\t'),
+      Some('u') => {
+        let hex = chars.by_ref().take(4).collect::<String>();
+        let code = u32::from_str_radix(&hex, 16).unwrap_or(0xfffd);
+        s.push(std::char::from_u32(code).unwrap_or('\u{fffd}'))
+      }
+      Some(c) => s.push(c),
+      None => (),
+    }
+  }
+  s
+}
@@ -88,62 +88,403 @@
 //! println!("{}", yojson_rs::to_string(json));
 //! # }
 //! ```
+//!
+//! # Editing a document
+//!
+//! `cst::Document` keeps comments, whitespace, key order and quoting style, so a configuration file can be edited programmatically.
+//!
+//! ```
+//! use yojson_rs::cst::Document;
+//! use yojson_rs::value::Value;
+//!
+//! # fn main() {
+//! let mut doc = Document::parse("{ server : { port : 80 } // comment\n}").unwrap();
+//! doc.set(&["server", "port"], &Value::Integer(8080)).unwrap();
+//! assert_eq!(doc.to_string(), "{ server : { port : 8080 } // comment\n}");
+//! # }
+//! ```
+//!
+//! # Querying values
+//!
+//! `query::select` takes a JSONPath-like expression with recursive descent, wildcards, slices, filters and variant constructors, and returns references into a value.
+//!
+//! ```
+//! use yojson_rs::value::Value;
+//!
+//! # fn main() {
+//! let value = yojson_rs::parser::parse(
+//!   r#"{items : [{id : 1, status : <Active>}, {id : 2, status : <Closed>}]}"#,
+//! ).unwrap();
+//! let ids = yojson_rs::query::select(&value, "$.items[?(@.status == <Active>)].id").unwrap();
+//! assert_eq!(ids, vec![&Value::Integer(1)]);
+//! # }
+//! ```
+//!
+//! # Generating types from ATD
+//!
+//! `codegen::build` reads an ATD file of atdgen in a build script and writes Rust structs and enums with `convert::ToYojson` and `convert::FromYojson` implementations to `$OUT_DIR`.
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!   yojson_rs::codegen::build("types.atd").unwrap();
+//! }
+//!
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/types.rs"));
+//! ```
+//!
+//! # Deriving conversions
+//!
+//! With the feature `derive`, `YojsonSerialize` and `YojsonDeserialize` derive `convert::ToYojson` and `convert::FromYojson`. The container attribute `#[yojson(encoding = "ppx")]` switches from the encoding of `Yojson.Safe`, such as `<"Circle": 1.5>`, to the encoding of `ppx_deriving_yojson`, such as `["Circle", 1.5]`.
+//!
+//! ```ignore
+//! use yojson_rs::{YojsonDeserialize, YojsonSerialize};
+//!
+//! #[derive(YojsonSerialize, YojsonDeserialize)]
+//! #[yojson(encoding = "ppx")]
+//! enum Shape {
+//!   Point,
+//!   Circle(f64),
+//! }
+//! ```
 
-extern crate pest;
-#[macro_use]
-extern crate pest_derive;
-
+pub mod access;
+pub mod atd;
+pub mod biniou;
+pub mod canonical;
+#[cfg(feature = "ciborium")]
+pub mod cbor;
+pub mod codegen;
+pub mod convert;
+pub mod cst;
+pub mod diff;
+mod encoding;
+#[cfg(feature = "serde_json")]
+pub mod json;
+mod lexer;
+#[cfg(any(feature = "ciborium", feature = "serde_yaml", feature = "toml"))]
+pub mod loss;
+pub mod merge;
+pub mod mutate;
+pub mod ord;
 pub mod parser;
+pub mod patch;
+pub mod pointer;
+pub mod query;
+mod reader;
+pub mod schema;
+#[cfg(feature = "toml")]
+pub mod toml;
 pub mod value;
+pub mod visit;
+#[cfg(feature = "serde_yaml")]
+pub mod yaml;
+
+#[cfg(feature = "derive")]
+pub use yojson_rs_derive::{YojsonDeserialize, YojsonSerialize};
 
 /// Convert to a JSON string.
+///
+/// The string is read back as the same value: floats always have a decimal point, strings
+/// are escaped as in JSON, and names which are not identifiers are quoted.
+///
+/// Nested values are taken apart with an explicit stack while being written,
+/// so neither writing nor dropping a deep value overflows the call stack.
 pub fn to_string(value: value::Value) -> String {
-  match value {
-    value::Value::Null => "null".to_string(),
-    value::Value::Bool(b) => b.to_string(),
-    value::Value::Integer(i) => i.to_string(),
-    value::Value::Float(f) => f.to_string(),
-    value::Value::String(s) => format!("{:?}", s),
-    value::Value::Assoc(assoc) => {
-      let mut s = String::new();
-      for (i, item) in assoc.iter().enumerate() {
-        let (name, value) = item;
-        if i == 0 {
-          s.push_str(&format!("{}:{}", name, to_string(value.clone())))
-        } else {
-          s.push_str(&format!(",{}:{}", name, to_string(value.clone())))
+  enum Task {
+    Value(value::Value),
+    Name(String),
+    Str(&'static str),
+  }
+  let mut s = String::new();
+  let mut tasks = vec![Task::Value(value)];
+  while let Some(task) = tasks.pop() {
+    let value = match task {
+      Task::Value(value) => value,
+      Task::Name(name) => {
+        s.push_str(&name_to_string(&name));
+        continue;
+      }
+      Task::Str(str) => {
+        s.push_str(str);
+        continue;
+      }
+    };
+    let (open, close) = match value {
+      value::Value::Tuple(_) => ("(", ")"),
+      _ => ("[", "]"),
+    };
+    match value {
+      value::Value::Null => s.push_str("null"),
+      value::Value::Bool(b) => s.push_str(&b.to_string()),
+      value::Value::Integer(i) => s.push_str(&i.to_string()),
+      value::Value::Float(f) => s.push_str(&float_to_string(f)),
+      value::Value::String(str) => s.push_str(&quote_string(&str)),
+      value::Value::Assoc(assoc) => {
+        s.push('{');
+        tasks.push(Task::Str("}"));
+        // The stack is popped from the end, so the members are pushed in reverse order.
+        let members = assoc.into_iter().collect::<Vec<_>>();
+        for (i, (name, value)) in members.into_iter().enumerate().rev() {
+          tasks.push(Task::Value(value));
+          tasks.push(Task::Str(":"));
+          tasks.push(Task::Name(name));
+          if i > 0 {
+            tasks.push(Task::Str(","));
+          }
         }
       }
-      format!("{{{}}}", s)
-    }
-    value::Value::Array(array) => {
-      let mut s = String::new();
-      for (i, item) in array.iter().enumerate() {
-        if i == 0 {
-          s.push_str(&to_string(item.clone()))
-        } else {
-          s.push_str(&format!(",{}", to_string(item.clone())))
+      value::Value::Array(elements) | value::Value::Tuple(elements) => {
+        s.push_str(open);
+        tasks.push(Task::Str(close));
+        for (i, value) in elements.into_iter().enumerate().rev() {
+          tasks.push(Task::Value(value));
+          if i > 0 {
+            tasks.push(Task::Str(","));
+          }
         }
       }
-      format!("[{}]", s)
-    }
-    value::Value::Tuple(tuple) => {
-      let mut s = String::new();
-      for (i, item) in tuple.iter().enumerate() {
-        if i == 0 {
-          s.push_str(&to_string(item.clone()))
-        } else {
-          s.push_str(&format!(",{}", to_string(item.clone())))
+      value::Value::Variant((name, value_opt)) => {
+        s.push('<');
+        s.push_str(&name_to_string(&name));
+        tasks.push(Task::Str(">"));
+        if let Some(value) = value_opt {
+          s.push(':');
+          tasks.push(Task::Value(*value));
         }
       }
-      format!("({})", s)
     }
-    value::Value::Variant(variant) => {
-      let (name, value_opt) = variant;
-      match value_opt {
-        None => format!("<{}>", name),
-        Some(value) => format!("<{}:{}>", name, to_string(*value)),
+  }
+  s
+}
+
+/// Options of `to_string_with_options`.
+///
+/// By default, values are written on one line in the Yojson syntax.
+///
+/// ```
+/// let options = yojson_rs::PrintOptions::new().pretty(true).std(true);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct PrintOptions {
+  pub(crate) pretty: bool,
+  pub(crate) std: bool,
+}
+
+impl PrintOptions {
+  /// Creates the default options.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Writes nested values on separate lines indented by two spaces.
+  pub fn pretty(mut self, pretty: bool) -> Self {
+    self.pretty = pretty;
+    self
+  }
+
+  /// Writes standard JSON, as `Yojson.Safe.to_basic`.
+  ///
+  /// Tuples are written as arrays, variants as `"Name"` or `["Name", argument]`,
+  /// and names are always quoted.
+  pub fn std(mut self, std: bool) -> Self {
+    self.std = std;
+    self
+  }
+}
+
+/// Error of writing a value which cannot be written with the options.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PrintError {
+  float: f64,
+}
+
+impl PrintError {
+  /// The float which has no representation in standard JSON.
+  pub fn float(&self) -> f64 {
+    self.float
+  }
+}
+
+impl std::fmt::Display for PrintError {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(
+      f,
+      "{} is not allowed in standard JSON",
+      float_to_string(self.float)
+    )
+  }
+}
+
+impl std::error::Error for PrintError {}
+
+/// Convert to a string with options.
+///
+/// Unlike `to_string`, the members of assocs are written sorted by name, so that the output
+/// is stable. In standard JSON, `NaN` and infinite floats are errors.
+///
+/// ```
+/// let value = yojson_rs::parser::parse("{b : (1, 2), a : <A>}").unwrap();
+/// let options = yojson_rs::PrintOptions::new().std(true);
+/// assert_eq!(
+///   yojson_rs::to_string_with_options(value, &options).unwrap(),
+///   r#"{"a":"A","b":[1,2]}"#
+/// );
+/// ```
+pub fn to_string_with_options(
+  value: value::Value,
+  options: &PrintOptions,
+) -> Result<String, PrintError> {
+  enum Task {
+    Value(value::Value, usize),
+    Name(String),
+    Str(&'static str),
+    Newline(usize),
+  }
+  let colon = if options.pretty { ": " } else { ":" };
+  let mut s = String::new();
+  let mut tasks = vec![Task::Value(value, 0)];
+  while let Some(task) = tasks.pop() {
+    let (value, depth) = match task {
+      Task::Value(value, depth) => (value, depth),
+      Task::Name(name) if options.std => {
+        s.push_str(&quote_string(&name));
+        continue;
+      }
+      Task::Name(name) => {
+        s.push_str(&name_to_string(&name));
+        continue;
+      }
+      Task::Str(str) => {
+        s.push_str(str);
+        continue;
+      }
+      Task::Newline(depth) => {
+        if options.pretty {
+          s.push('\n');
+          for _ in 0..depth {
+            s.push_str("  ");
+          }
+        }
+        continue;
+      }
+    };
+    let (open, close) = match value {
+      value::Value::Tuple(_) if !options.std => ("(", ")"),
+      _ => ("[", "]"),
+    };
+    match value {
+      value::Value::Float(f) if options.std && !f.is_finite() => {
+        return Err(PrintError { float: f })
       }
+      // In standard JSON, variants are written as `Yojson.Safe.to_basic` does.
+      value::Value::Variant((name, None)) if options.std => s.push_str(&quote_string(&name)),
+      value::Value::Variant((name, Some(value))) if options.std => tasks.push(Task::Value(
+        value::Value::Array(vec![value::Value::String(name), *value]),
+        depth,
+      )),
+      value::Value::Assoc(assoc) if assoc.is_empty() => s.push_str("{}"),
+      value::Value::Assoc(assoc) => {
+        s.push('{');
+        tasks.push(Task::Str("}"));
+        tasks.push(Task::Newline(depth));
+        let mut members = assoc.into_iter().collect::<Vec<_>>();
+        members.sort_by(|a, b| a.0.cmp(&b.0));
+        for (i, (name, value)) in members.into_iter().enumerate().rev() {
+          tasks.push(Task::Value(value, depth + 1));
+          tasks.push(Task::Str(colon));
+          tasks.push(Task::Name(name));
+          tasks.push(Task::Newline(depth + 1));
+          if i > 0 {
+            tasks.push(Task::Str(","));
+          }
+        }
+      }
+      value::Value::Array(elements) | value::Value::Tuple(elements) if elements.is_empty() => {
+        s.push_str(open);
+        s.push_str(close);
+      }
+      value::Value::Array(elements) | value::Value::Tuple(elements) => {
+        s.push_str(open);
+        tasks.push(Task::Str(close));
+        tasks.push(Task::Newline(depth));
+        for (i, value) in elements.into_iter().enumerate().rev() {
+          tasks.push(Task::Value(value, depth + 1));
+          tasks.push(Task::Newline(depth + 1));
+          if i > 0 {
+            tasks.push(Task::Str(","));
+          }
+        }
+      }
+      value::Value::Variant((name, value_opt)) => {
+        s.push('<');
+        s.push_str(&name_to_string(&name));
+        tasks.push(Task::Str(">"));
+        if let Some(value) = value_opt {
+          s.push_str(colon);
+          tasks.push(Task::Value(*value, depth));
+        }
+      }
+      value => s.push_str(&to_string(value)),
+    }
+  }
+  Ok(s)
+}
+
+/// Convert a float to a string which is read back as a float.
+pub(crate) fn float_to_string(f: f64) -> String {
+  if f.is_nan() {
+    "NaN".to_string()
+  } else if f == f64::INFINITY {
+    "Infinity".to_string()
+  } else if f == f64::NEG_INFINITY {
+    "-Infinity".to_string()
+  } else {
+    let s = f.to_string();
+    if s.contains('.') {
+      s
+    } else {
+      format!("{}.0", s)
     }
   }
 }
+
+/// Convert a string to a quoted JSON string.
+pub(crate) fn quote_string(str: &str) -> String {
+  let mut s = String::with_capacity(str.len() + 2);
+  s.push('"');
+  for c in str.chars() {
+    match c {
+      '"' => s.push_str("\\\""),
+      '\\' => s.push_str("\\\\"),
+      '\n' => s.push_str("\\n"),
+      '\r' => s.push_str("\\r"),
+      '\t' => s.push_str("\\t"),
+      '\u{0008}' => s.push_str("\\b"),
+      '\u{000c}' => s.push_str("\\f"),
+      c if (c as u32) < 0x20 => s.push_str(&format!("\\u{:04x}", c as u32)),
+      c => s.push(c),
+    }
+  }
+  s.push('"');
+  s
+}
+
+/// Tests whether a name can be written without quotes.
+pub(crate) fn is_unquoted_name(name: &str) -> bool {
+  let mut chars = name.chars();
+  match chars.next() {
+    Some(c) if c.is_ascii_alphabetic() => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+    _ => false,
+  }
+}
+
+/// Convert a field or variant name to a string, quoting it if necessary.
+pub(crate) fn name_to_string(name: &str) -> String {
+  if is_unquoted_name(name) {
+    name.to_string()
+  } else {
+    quote_string(name)
+  }
+}
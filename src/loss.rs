@@ -0,0 +1,83 @@
+//! Definition of the reports of conversions which may lose information.
+//!
+//! The converters to and from YAML, TOML and CBOR never fail. Whatever a format cannot
+//! represent is approximated, and reported as a `Loss` at the JSON pointer of the source value.
+//!
+//! This module is enabled by any of the features `ciborium`, `serde_yaml` and `toml`.
+
+use std::fmt;
+
+/// Information lost in a conversion.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Loss {
+  path: String,
+  message: String,
+}
+
+impl Loss {
+  pub(crate) fn new(path: &str, message: impl Into<String>) -> Self {
+    Loss {
+      path: path.to_string(),
+      message: message.into(),
+    }
+  }
+
+  /// JSON pointer of the source value.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Description of what was lost.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl fmt::Display for Loss {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} at \"{}\"", self.message, self.path)
+  }
+}
+
+/// Result of a conversion, with the information lost in it.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Lossy<T> {
+  value: T,
+  losses: Vec<Loss>,
+}
+
+impl<T> Lossy<T> {
+  pub(crate) fn new(value: T, losses: Vec<Loss>) -> Self {
+    Lossy { value, losses }
+  }
+
+  /// Converted value.
+  pub fn value(&self) -> &T {
+    &self.value
+  }
+
+  /// Information lost in the conversion, in the order of the source value.
+  pub fn losses(&self) -> &[Loss] {
+    &self.losses
+  }
+
+  /// Tests whether nothing was lost in the conversion.
+  pub fn is_lossless(&self) -> bool {
+    self.losses.is_empty()
+  }
+
+  /// Returns the converted value, ignoring the losses.
+  pub fn into_value(self) -> T {
+    self.value
+  }
+
+  /// Returns the converted value and the losses.
+  pub fn into_parts(self) -> (T, Vec<Loss>) {
+    (self.value, self.losses)
+  }
+}
+
+/// Returns the JSON pointer of a child.
+pub(crate) fn child(path: &str, token: &str) -> String {
+  format!("{}/{}", path, super::pointer::escape(token))
+}
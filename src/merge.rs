@@ -0,0 +1,240 @@
+//! Definition of the deep merge of values for layered configuration.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::diff;
+use super::pointer;
+use super::value::Value;
+
+/// How two arrays at the same path are merged.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ArrayStrategy {
+  /// The right array replaces the left array, whatever the conflict strategy.
+  Replace,
+  /// The elements of the right array follow the elements of the left array.
+  Append,
+  /// Elements at the same index are merged, and extra elements of the right array are appended.
+  ByIndex,
+}
+
+/// How a conflict between two values at the same path is resolved.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ConflictStrategy {
+  /// Keeps the left value.
+  PreferLeft,
+  /// Takes the right value.
+  PreferRight,
+  /// Fails the merge.
+  Error,
+}
+
+/// Strategy of `Value::merge`.
+///
+/// By default, arrays are replaced and the right value wins conflicts.
+///
+/// ```
+/// use yojson_rs::merge::{ArrayStrategy, ConflictStrategy, MergeStrategy};
+/// let strategy = MergeStrategy::new()
+///   .arrays(ArrayStrategy::Append)
+///   .conflicts(ConflictStrategy::Error);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MergeStrategy {
+  pub(crate) arrays: ArrayStrategy,
+  pub(crate) conflicts: ConflictStrategy,
+}
+
+impl Default for MergeStrategy {
+  fn default() -> Self {
+    MergeStrategy {
+      arrays: ArrayStrategy::Replace,
+      conflicts: ConflictStrategy::PreferRight,
+    }
+  }
+}
+
+impl MergeStrategy {
+  /// Creates the default strategy.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets how arrays are merged.
+  pub fn arrays(mut self, strategy: ArrayStrategy) -> Self {
+    self.arrays = strategy;
+    self
+  }
+
+  /// Sets how conflicts are resolved.
+  pub fn conflicts(mut self, strategy: ConflictStrategy) -> Self {
+    self.conflicts = strategy;
+    self
+  }
+}
+
+/// Two different values at the same path, which cannot be merged.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Conflict {
+  /// JSON pointer of the values.
+  pub path: String,
+  /// Value of the left side.
+  pub left: Value,
+  /// Value of the right side.
+  pub right: Value,
+}
+
+impl fmt::Display for Conflict {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "\"{}\": {} and {}",
+      self.path,
+      super::to_string(self.left.clone()),
+      super::to_string(self.right.clone())
+    )
+  }
+}
+
+/// Error of a merge with `ConflictStrategy::Error`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct MergeError {
+  conflicts: Vec<Conflict>,
+}
+
+impl MergeError {
+  /// Every conflict of the merge.
+  pub fn conflicts(&self) -> &[Conflict] {
+    &self.conflicts
+  }
+}
+
+impl fmt::Display for MergeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} conflicts", self.conflicts.len())?;
+    for conflict in &self.conflicts {
+      write!(f, "\n{}", conflict)?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for MergeError {}
+
+fn merge_at(
+  left: &mut Value,
+  right: Value,
+  strategy: &MergeStrategy,
+  conflicts: &mut Vec<Conflict>,
+) {
+  // Nested values are merged with an explicit stack, so that merging deep values does not
+  // overflow the call stack.
+  let mut stack = vec![(left, right, String::new())];
+  while let Some((left, right, path)) = stack.pop() {
+    let nested = match (&*left, &right) {
+      (Value::Assoc(_), Value::Assoc(_)) | (Value::Array(_), Value::Array(_)) => true,
+      (Value::Tuple(left), Value::Tuple(right)) => left.len() == right.len(),
+      (Value::Variant((name, Some(_))), Value::Variant((right_name, Some(_)))) => {
+        name == right_name
+      }
+      _ => false,
+    };
+    if !nested {
+      if !diff::diff(left, &right).is_empty() {
+        conflicts.push(Conflict {
+          path,
+          left: left.clone(),
+          right: right.clone(),
+        });
+        if strategy.conflicts == ConflictStrategy::PreferRight {
+          *left = right
+        }
+      }
+      continue;
+    }
+    let child = |token: &str| format!("{}/{}", path, pointer::escape(token));
+    let mut children = Vec::new();
+    match (left, right) {
+      (Value::Assoc(left), Value::Assoc(right)) => {
+        let mut nested = HashMap::new();
+        for (name, value) in right {
+          match left.entry(name) {
+            Entry::Occupied(entry) => {
+              nested.insert(entry.key().clone(), value);
+            }
+            Entry::Vacant(entry) => {
+              entry.insert(value);
+            }
+          }
+        }
+        let mut members = left
+          .iter_mut()
+          .filter_map(|(name, left)| nested.remove(name).map(|right| (name, left, right)))
+          .collect::<Vec<_>>();
+        members.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, left, right) in members {
+          children.push((left, right, child(name)));
+        }
+      }
+      (Value::Array(left), Value::Array(mut right)) => match strategy.arrays {
+        ArrayStrategy::Replace => *left = right,
+        ArrayStrategy::Append => left.extend(right),
+        ArrayStrategy::ByIndex => {
+          let extra = right.split_off(left.len().min(right.len()));
+          left.extend(extra);
+          for (i, (left, right)) in left.iter_mut().zip(right).enumerate() {
+            children.push((left, right, child(&i.to_string())));
+          }
+        }
+      },
+      (Value::Tuple(left), Value::Tuple(right)) => {
+        for (i, (left, right)) in left.iter_mut().zip(right).enumerate() {
+          children.push((left, right, child(&i.to_string())));
+        }
+      }
+      (Value::Variant((_, Some(left))), Value::Variant((_, Some(right)))) => {
+        children.push((left.as_mut(), *right, child(pointer::PAYLOAD)));
+      }
+      _ => unreachable!(),
+    }
+    // The stack is popped from the end, so the children are pushed in reverse order to report
+    // conflicts in order.
+    stack.extend(children.into_iter().rev());
+  }
+}
+
+impl Value {
+  /// Merges another value into this value, and returns the conflicts.
+  ///
+  /// Assocs are merged member by member, tuples of the same length element by element, and
+  /// variants with the same constructor by their arguments. Arrays are merged by the array
+  /// strategy. Any other pair of different values is a conflict, resolved by the conflict
+  /// strategy. With `ConflictStrategy::Error`, this value is left unchanged if there are conflicts.
+  ///
+  /// ```
+  /// use yojson_rs::merge::MergeStrategy;
+  /// let mut base = yojson_rs::parser::parse("{server : {host : \"a\", port : 80}}").unwrap();
+  /// let local = yojson_rs::parser::parse("{server : {port : 8080}}").unwrap();
+  /// let conflicts = base.merge(local, &MergeStrategy::new()).unwrap();
+  /// assert_eq!(conflicts[0].path, "/server/port");
+  /// ```
+  pub fn merge(
+    &mut self,
+    other: Value,
+    strategy: &MergeStrategy,
+  ) -> Result<Vec<Conflict>, MergeError> {
+    let mut conflicts = Vec::new();
+    if strategy.conflicts == ConflictStrategy::Error {
+      let mut merged = self.clone();
+      merge_at(&mut merged, other, strategy, &mut conflicts);
+      if !conflicts.is_empty() {
+        return Err(MergeError { conflicts });
+      }
+      *self = merged;
+    } else {
+      merge_at(self, other, strategy, &mut conflicts);
+    }
+    Ok(conflicts)
+  }
+}
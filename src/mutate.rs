@@ -0,0 +1,177 @@
+//! Definition of the API for building and rewriting values in place.
+//!
+//! ```
+//! use yojson_rs::value::Value;
+//! let mut config = Value::Null;
+//! config.entry("server").or_insert_assoc().entry("port").or_insert(8080);
+//! config.entry("tags").or_insert_array().push("web");
+//! assert_eq!(config.get_i64_or(&["server", "port"], 80), Ok(8080));
+//! ```
+//!
+//! An assoc is a `HashMap`, so it has no order of members to sort. Writers which need an order,
+//! such as `to_string_with_options` and `canonical::to_string`, sort the members themselves, and
+//! `cst::Document::sort_keys` sorts the members of a document in its text.
+
+use std::collections::hash_map;
+use std::collections::HashMap;
+
+use super::access::{cannot_apply, AccessError, Path};
+use super::pointer;
+use super::value::Value;
+use super::visit::{self, VisitorMut, Walk};
+
+/// Member of an assoc, which may be vacant, as `hash_map::Entry`.
+pub struct Entry<'a> {
+  entry: hash_map::Entry<'a, String, Value>,
+}
+
+impl<'a> Entry<'a> {
+  /// Returns the name of the member.
+  pub fn key(&self) -> &str {
+    self.entry.key()
+  }
+
+  /// Inserts a value if the member is vacant, and returns the value of the member.
+  pub fn or_insert(self, default: impl Into<Value>) -> &'a mut Value {
+    self.entry.or_insert_with(|| default.into())
+  }
+
+  /// Inserts the result of a function if the member is vacant, and returns the value of the
+  /// member.
+  pub fn or_insert_with<F: FnOnce() -> Value>(self, default: F) -> &'a mut Value {
+    self.entry.or_insert_with(default)
+  }
+
+  /// Inserts an empty assoc if the member is vacant, and returns the value of the member.
+  pub fn or_insert_assoc(self) -> &'a mut Value {
+    self.or_insert(HashMap::new())
+  }
+
+  /// Inserts an empty array if the member is vacant, and returns the value of the member.
+  pub fn or_insert_array(self) -> &'a mut Value {
+    self.or_insert(Vec::new())
+  }
+
+  /// Modifies the value if the member is occupied.
+  pub fn and_modify<F: FnOnce(&mut Value)>(self, f: F) -> Self {
+    Entry {
+      entry: self.entry.and_modify(f),
+    }
+  }
+}
+
+/// Visitor which calls a function on every value.
+struct Closure<F>(F);
+
+impl<F: FnMut(&str, &mut Value)> VisitorMut for Closure<F> {
+  fn visit_value(&mut self, path: &str, value: &mut Value) -> Walk {
+    (self.0)(path, value);
+    Walk::Continue
+  }
+}
+
+impl Value {
+  /// Returns the entry of a member of this assoc.
+  ///
+  /// # Panics
+  ///
+  /// Panics if this value is neither an assoc nor `null`. `null` is replaced by an empty assoc.
+  pub fn entry(&mut self, name: impl Into<String>) -> Entry<'_> {
+    if self.is_null() {
+      *self = Value::Assoc(HashMap::new());
+    }
+    match self {
+      Value::Assoc(assoc) => Entry {
+        entry: assoc.entry(name.into()),
+      },
+      value => panic!(
+        "cannot take an entry of a value of type {}",
+        value.type_str()
+      ),
+    }
+  }
+
+  /// Takes this value, leaving `null` in its place.
+  pub fn take(&mut self) -> Value {
+    std::mem::replace(self, Value::Null)
+  }
+
+  /// Removes a nested value and returns it.
+  ///
+  /// Elements of an array after the removed one are shifted. Elements of a tuple cannot be
+  /// removed, since the length of a tuple is part of its type. The empty path takes this value.
+  pub fn remove_path<P: Path + ?Sized>(&mut self, path: &P) -> Result<Value, AccessError> {
+    let segments = path.segments();
+    let (last, parents) = match segments.split_last() {
+      Some(split) => split,
+      None => return Ok(self.take()),
+    };
+    let parent = self.get_path_mut(parents)?;
+    let found = parent.type_str();
+    let removed = match (parent, pointer::index(last)) {
+      (Value::Assoc(assoc), _) => assoc.remove(*last),
+      (Value::Array(elements), Some(index)) if index < elements.len() => {
+        Some(elements.remove(index))
+      }
+      (Value::Array(_), Some(_)) => None,
+      (Value::Variant((_, argument)), _) if *last == pointer::PAYLOAD => {
+        argument.take().map(|argument| *argument)
+      }
+      _ => return Err(cannot_apply(found, &segments)),
+    };
+    removed.ok_or_else(|| AccessError::Missing(pointer::join(&segments)))
+  }
+
+  /// Appends an element to this array.
+  ///
+  /// # Panics
+  ///
+  /// Panics if this value is neither an array nor `null`. `null` is replaced by an empty array.
+  pub fn push(&mut self, value: impl Into<Value>) {
+    if self.is_null() {
+      *self = Value::Array(Vec::new());
+    }
+    match self {
+      Value::Array(elements) => elements.push(value.into()),
+      value => panic!("cannot push into a value of type {}", value.type_str()),
+    }
+  }
+
+  /// Keeps only the elements of this array, or the members of this assoc, whose values satisfy
+  /// a predicate. Other values are left unchanged.
+  pub fn retain<F: FnMut(&Value) -> bool>(&mut self, mut f: F) {
+    match self {
+      Value::Array(elements) => elements.retain(f),
+      Value::Assoc(assoc) => assoc.retain(|_, value| f(value)),
+      _ => (),
+    }
+  }
+
+  /// Keeps only the members of this assoc which satisfy a predicate on their names and values.
+  /// Other values are left unchanged.
+  pub fn retain_members<F: FnMut(&str, &Value) -> bool>(&mut self, mut f: F) {
+    if let Value::Assoc(assoc) = self {
+      assoc.retain(|name, value| f(name, value));
+    }
+  }
+
+  /// Calls a function on this value and every nested value, parents first, with the JSON
+  /// pointer of each value. Members of an assoc are visited in the order of their names.
+  ///
+  /// The children of a value are visited after the function returns, so a value replaced by
+  /// the function is walked as replaced.
+  ///
+  /// ```
+  /// use yojson_rs::value::Value;
+  /// let mut value = yojson_rs::parser::parse("{a : 1, b : [2, 3.5]}").unwrap();
+  /// value.walk_mut(|_, value| {
+  ///   if let Value::Integer(i) = *value {
+  ///     *value = Value::Float(i as f64);
+  ///   }
+  /// });
+  /// assert_eq!(value, yojson_rs::parser::parse("{a : 1.0, b : [2.0, 3.5]}").unwrap());
+  /// ```
+  pub fn walk_mut<F: FnMut(&str, &mut Value)>(&mut self, f: F) {
+    visit::walk_mut(self, &mut Closure(f));
+  }
+}
@@ -0,0 +1,209 @@
+//! Definition of a total ordering, equality and hashing of values.
+//!
+//! `Value` only implements `PartialEq`, under which `NaN` is not equal to itself.
+//! `compare` and `equal` define a total ordering instead, and `OrdValue` wraps a value to
+//! implement `Eq`, `Ord` and `Hash` with it, so that values can be keys of maps and sets.
+//!
+//! The ordering matches `Yojson.Safe.compare` and `Yojson.Safe.equal` of OCaml Yojson:
+//! - Floats are compared as `Float.compare`: `NaN` is equal to itself and less than any other
+//!   float, and `-0.0` is equal to `0.0`. Integers and floats are never equal.
+//! - Strings are compared byte by byte.
+//! - Assocs are compared as the lists of their members sorted by name, member by member with
+//!   the name first, so the order of members does not matter.
+//! - Arrays and tuples are compared lexicographically, and a prefix is less than the sequence.
+//! - Variants are compared by their names, then by their arguments, where no argument is less
+//!   than any argument.
+//!
+//! Values of different kinds are ordered as `Null`, `Bool`, `Integer`, `Float`, `String`,
+//! `Assoc`, `Array`, `Tuple` and `Variant`, which is the order of the declaration of
+//! `Yojson.Safe.t`. OCaml orders them by the hashes of the polymorphic variant tags instead,
+//! so only the ordering of values of different kinds differs from OCaml.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use super::value::Value;
+
+fn kind(value: &Value) -> u8 {
+  match value {
+    Value::Null => 0,
+    Value::Bool(_) => 1,
+    Value::Integer(_) => 2,
+    Value::Float(_) => 3,
+    Value::String(_) => 4,
+    Value::Assoc(_) => 5,
+    Value::Array(_) => 6,
+    Value::Tuple(_) => 7,
+    Value::Variant(_) => 8,
+  }
+}
+
+/// Compares floats as OCaml `Float.compare`.
+fn compare_floats(a: f64, b: f64) -> Ordering {
+  match (a.is_nan(), b.is_nan()) {
+    (true, true) => Ordering::Equal,
+    (true, false) => Ordering::Less,
+    (false, true) => Ordering::Greater,
+    (false, false) => a.partial_cmp(&b).unwrap(),
+  }
+}
+
+fn sorted_members(value: &Value) -> Vec<(&String, &Value)> {
+  let mut members = match value {
+    Value::Assoc(assoc) => assoc.iter().collect::<Vec<_>>(),
+    _ => Vec::new(),
+  };
+  members.sort_by(|a, b| a.0.cmp(b.0));
+  members
+}
+
+/// Compares two values by the total ordering.
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use yojson_rs::value::Value;
+/// assert_eq!(yojson_rs::ord::compare(&Value::Float(f64::NAN), &Value::Float(0.0)), Ordering::Less);
+/// ```
+pub fn compare(a: &Value, b: &Value) -> Ordering {
+  enum Task<'a> {
+    Values(&'a Value, &'a Value),
+    Names(&'a str, &'a str),
+    Lengths(usize, usize),
+  }
+  // Nested values are compared with an explicit stack, so that deep values do not overflow
+  // the call stack. The elements of a sequence are pushed in reverse order, after its length.
+  let mut tasks = vec![Task::Values(a, b)];
+  while let Some(task) = tasks.pop() {
+    let (a, b) = match task {
+      Task::Values(a, b) => (a, b),
+      Task::Names(a, b) => match a.cmp(b) {
+        Ordering::Equal => continue,
+        ordering => return ordering,
+      },
+      Task::Lengths(a, b) => match a.cmp(&b) {
+        Ordering::Equal => continue,
+        ordering => return ordering,
+      },
+    };
+    let ordering = match (a, b) {
+      (Value::Null, Value::Null) => Ordering::Equal,
+      (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+      (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+      (Value::Float(a), Value::Float(b)) => compare_floats(*a, *b),
+      (Value::String(a), Value::String(b)) => a.cmp(b),
+      (Value::Assoc(_), Value::Assoc(_)) => {
+        let (a, b) = (sorted_members(a), sorted_members(b));
+        tasks.push(Task::Lengths(a.len(), b.len()));
+        for ((a_name, a), (b_name, b)) in a.into_iter().zip(b).rev() {
+          tasks.push(Task::Values(a, b));
+          tasks.push(Task::Names(a_name, b_name));
+        }
+        Ordering::Equal
+      }
+      (Value::Array(a), Value::Array(b)) | (Value::Tuple(a), Value::Tuple(b)) => {
+        tasks.push(Task::Lengths(a.len(), b.len()));
+        for (a, b) in a.iter().zip(b).rev() {
+          tasks.push(Task::Values(a, b));
+        }
+        Ordering::Equal
+      }
+      (Value::Variant((a_name, a)), Value::Variant((b_name, b))) => {
+        match (a_name.cmp(b_name), a, b) {
+          (Ordering::Equal, Some(a), Some(b)) => {
+            tasks.push(Task::Values(a, b));
+            Ordering::Equal
+          }
+          (Ordering::Equal, a, b) => a.is_some().cmp(&b.is_some()),
+          (ordering, _, _) => ordering,
+        }
+      }
+      (a, b) => kind(a).cmp(&kind(b)),
+    };
+    if ordering != Ordering::Equal {
+      return ordering;
+    }
+  }
+  Ordering::Equal
+}
+
+/// Tests whether two values are equal by the total ordering.
+///
+/// Unlike `==`, `NaN` is equal to itself.
+pub fn equal(a: &Value, b: &Value) -> bool {
+  compare(a, b) == Ordering::Equal
+}
+
+/// Value which implements `Eq`, `Ord` and `Hash` by the total ordering.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use yojson_rs::ord::OrdValue;
+/// let a = yojson_rs::parser::parse("{x : NaN, y : 1}").unwrap();
+/// let b = yojson_rs::parser::parse("{y : 1, x : NaN}").unwrap();
+/// let set = vec![OrdValue(a), OrdValue(b)].into_iter().collect::<HashSet<_>>();
+/// assert_eq!(set.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrdValue(pub Value);
+
+impl PartialEq for OrdValue {
+  fn eq(&self, other: &Self) -> bool {
+    equal(&self.0, &other.0)
+  }
+}
+
+impl Eq for OrdValue {}
+
+impl PartialOrd for OrdValue {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for OrdValue {
+  fn cmp(&self, other: &Self) -> Ordering {
+    compare(&self.0, &other.0)
+  }
+}
+
+impl Hash for OrdValue {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    let mut values = vec![&self.0];
+    while let Some(value) = values.pop() {
+      state.write_u8(kind(value));
+      match value {
+        Value::Null => (),
+        Value::Bool(b) => b.hash(state),
+        Value::Integer(i) => i.hash(state),
+        // Equal floats have the same bits, except `NaN`s and `-0.0`.
+        Value::Float(f) if f.is_nan() => f64::NAN.to_bits().hash(state),
+        Value::Float(f) if *f == 0.0 => 0u64.hash(state),
+        Value::Float(f) => f.to_bits().hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Assoc(_) => {
+          let members = sorted_members(value);
+          members.len().hash(state);
+          for (name, value) in members.into_iter().rev() {
+            name.hash(state);
+            values.push(value);
+          }
+        }
+        Value::Array(elements) | Value::Tuple(elements) => {
+          elements.len().hash(state);
+          values.extend(elements.iter().rev());
+        }
+        Value::Variant((name, argument)) => {
+          name.hash(state);
+          argument.is_some().hash(state);
+          values.extend(argument.as_deref());
+        }
+      }
+    }
+  }
+}
+
+impl From<Value> for OrdValue {
+  fn from(value: Value) -> Self {
+    OrdValue(value)
+  }
+}
@@ -1,151 +1,374 @@
-//! Definition of a parse fucntion.
+//! Definition of a parse function.
 
-use crate::pest::Parser;
-use pest::iterators::Pair;
-use std::collections::HashMap;
+use std::fmt;
 
+use super::encoding;
+use super::reader::Reader;
 use super::value;
 
-#[allow(clippy::upper_case_acronyms)]
-#[derive(Parser)]
-#[grammar = "parser.pest"]
-struct YojsonParser;
-
-pub fn parse(text: &str) -> Result<value::Value, pest::error::Error<Rule>> {
-  let json = YojsonParser::parse(Rule::json, text)?.next().unwrap();
-  let value = parse_value(json);
-  Ok(value)
-}
-
-fn parse_value(pair: Pair<Rule>) -> value::Value {
-  match pair.as_rule() {
-    Rule::null => value::Value::Null,
-    Rule::bool => value::Value::Bool(pair.as_str().parse().unwrap()),
-    Rule::integer => value::Value::Integer(pair.as_str().parse().unwrap()),
-    Rule::float => {
-      let str = pair.as_str();
-      let f = if str == "NaN" {
-        f64::NAN
-      } else if str == "Infinity" {
-        f64::INFINITY
-      } else if str == "-Infinity" {
-        f64::NEG_INFINITY
-      } else {
-        str.parse().unwrap()
-      };
-      value::Value::Float(f)
+/// Parse a text into a value.
+///
+/// The text must be an assoc, such as `{ x : 123 }`.
+pub fn parse(text: &str) -> Result<value::Value, Error> {
+  parse_with_options(text, &ParseOptions::default())
+}
+
+/// Parse a text into a value borrowing its strings from the text.
+///
+/// Strings and names without escape sequences are not copied.
+pub fn parse_borrowed(text: &str) -> Result<value::BorrowedValue<'_>, Error> {
+  let options = ParseOptions::default();
+  let mut reader = Reader::new(text, &options, false);
+  reader.document().map_err(|_| reader.errors.remove(0))
+}
+
+/// Parse a text with options.
+pub fn parse_with_options(text: &str, options: &ParseOptions) -> Result<value::Value, Error> {
+  let mut reader = Reader::new(text, options, false);
+  reader.document().map_err(|_| reader.errors.remove(0))
+}
+
+/// Parse a text of a sequence of documents, such as `{a : 1} {a : 2}`.
+///
+/// Documents may be separated by whitespace, newlines or comments.
+pub fn parse_stream(text: &str) -> Result<Vec<value::Value>, Error> {
+  parse_stream_with_options(text, &ParseOptions::default())
+}
+
+/// Parse a text of a sequence of documents with options.
+pub fn parse_stream_with_options(
+  text: &str,
+  options: &ParseOptions,
+) -> Result<Vec<value::Value>, Error> {
+  let mut reader = Reader::new(text, options, false);
+  reader.stream().map_err(|_| reader.errors.remove(0))
+}
+
+/// Parse bytes encoded in UTF-8 into a value.
+///
+/// An invalid UTF-8 sequence is reported with its byte offset.
+pub fn parse_slice(bytes: &[u8]) -> Result<value::Value, Error> {
+  parse_slice_with_options(bytes, &ParseOptions::default())
+}
+
+/// Parse bytes with options.
+///
+/// If the detection of the encoding is enabled, UTF-16 and UTF-32 bytes are transcoded
+/// before parsing, and the positions of syntax errors are those in the transcoded text.
+pub fn parse_slice_with_options(
+  bytes: &[u8],
+  options: &ParseOptions,
+) -> Result<value::Value, Error> {
+  let text = encoding::decode(bytes, options)?;
+  parse_with_options(&text, options)
+}
+
+/// Parse bytes of a sequence of documents, as `parse_stream`.
+pub fn parse_stream_slice(bytes: &[u8]) -> Result<Vec<value::Value>, Error> {
+  parse_stream_slice_with_options(bytes, &ParseOptions::default())
+}
+
+/// Parse bytes of a sequence of documents with options, as `parse_slice_with_options`.
+pub fn parse_stream_slice_with_options(
+  bytes: &[u8],
+  options: &ParseOptions,
+) -> Result<Vec<value::Value>, Error> {
+  let text = encoding::decode(bytes, options)?;
+  parse_stream_with_options(&text, options)
+}
+
+/// Parse a text, recovering from syntax errors.
+///
+/// Returns a best-effort value, in which broken values are replaced with `Null`,
+/// and every error found in the text.
+/// The list of errors is empty if and only if `parse` succeeds.
+pub fn parse_recovering(text: &str) -> (value::Value, Vec<Error>) {
+  let options = ParseOptions::default();
+  let mut reader = Reader::new(text, &options, true);
+  let value = reader.document().ok().unwrap_or(value::Value::Null);
+  (value, reader.errors)
+}
+
+/// Options of parsing.
+///
+/// Every relaxation of the syntax is disabled by default.
+/// The nesting depth is limited to `DEFAULT_MAX_DEPTH` by default, and the other limits are disabled.
+///
+/// ```
+/// let options = yojson_rs::parser::ParseOptions::new()
+///   .trailing_commas(true)
+///   .hex_numbers(true);
+/// assert!(yojson_rs::parser::parse_with_options("{a: [0x1f,],}", &options).is_ok());
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseOptions {
+  pub(crate) trailing_commas: bool,
+  pub(crate) single_quotes: bool,
+  pub(crate) hex_numbers: bool,
+  pub(crate) binary_numbers: bool,
+  pub(crate) leading_plus: bool,
+  pub(crate) leading_decimal_point: bool,
+  pub(crate) allow_bom: bool,
+  pub(crate) detect_encoding: bool,
+  pub(crate) max_depth: Option<usize>,
+  pub(crate) max_string_length: Option<usize>,
+  pub(crate) max_assoc_members: Option<usize>,
+  pub(crate) max_nodes: Option<usize>,
+}
+
+impl Default for ParseOptions {
+  fn default() -> Self {
+    ParseOptions {
+      trailing_commas: false,
+      single_quotes: false,
+      hex_numbers: false,
+      binary_numbers: false,
+      leading_plus: false,
+      leading_decimal_point: false,
+      allow_bom: false,
+      detect_encoding: false,
+      max_depth: Some(DEFAULT_MAX_DEPTH),
+      max_string_length: None,
+      max_assoc_members: None,
+      max_nodes: None,
     }
-    Rule::string => {
-      let str = parse_string(pair.into_inner().next().unwrap());
-      value::Value::String(str)
+  }
+}
+
+/// Default limit of the nesting depth of assocs, arrays, tuples and variants.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+impl ParseOptions {
+  /// Creates options of the strict syntax.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Accepts a comma before `}`, `]` and `)`: `[1, 2,]`.
+  pub fn trailing_commas(mut self, allow: bool) -> Self {
+    self.trailing_commas = allow;
+    self
+  }
+
+  /// Accepts strings and names quoted with `'`: `{'a': 'b'}`.
+  pub fn single_quotes(mut self, allow: bool) -> Self {
+    self.single_quotes = allow;
+    self
+  }
+
+  /// Accepts hexadecimal integers: `0x1f`.
+  pub fn hex_numbers(mut self, allow: bool) -> Self {
+    self.hex_numbers = allow;
+    self
+  }
+
+  /// Accepts binary integers: `0b101`.
+  pub fn binary_numbers(mut self, allow: bool) -> Self {
+    self.binary_numbers = allow;
+    self
+  }
+
+  /// Accepts a `+` sign before numbers: `+1`.
+  pub fn leading_plus(mut self, allow: bool) -> Self {
+    self.leading_plus = allow;
+    self
+  }
+
+  /// Accepts floats without an integer part: `.5`.
+  pub fn leading_decimal_point(mut self, allow: bool) -> Self {
+    self.leading_decimal_point = allow;
+    self
+  }
+
+  /// Skips a byte order mark at the beginning of the text instead of rejecting it.
+  pub fn allow_bom(mut self, allow: bool) -> Self {
+    self.allow_bom = allow;
+    self
+  }
+
+  /// Detects UTF-16 and UTF-32 in `parse_slice_with_options`, as described in RFC 4627.
+  pub fn detect_encoding(mut self, detect: bool) -> Self {
+    self.detect_encoding = detect;
+    self
+  }
+
+  /// Limits the nesting depth of assocs, arrays, tuples and variants. `None` disables the limit.
+  pub fn max_depth(mut self, limit: Option<usize>) -> Self {
+    self.max_depth = limit;
+    self
+  }
+
+  /// Limits the length in bytes of strings and names after unescaping.
+  pub fn max_string_length(mut self, limit: Option<usize>) -> Self {
+    self.max_string_length = limit;
+    self
+  }
+
+  /// Limits the number of members of each assoc.
+  pub fn max_assoc_members(mut self, limit: Option<usize>) -> Self {
+    self.max_assoc_members = limit;
+    self
+  }
+
+  /// Limits the total number of values in the document.
+  pub fn max_nodes(mut self, limit: Option<usize>) -> Self {
+    self.max_nodes = limit;
+    self
+  }
+}
+
+/// Limit set by `ParseOptions`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Limit {
+  /// Nesting depth.
+  Depth,
+  /// Length of a string or a name.
+  StringLength,
+  /// Number of members of an assoc.
+  AssocMembers,
+  /// Total number of values.
+  Nodes,
+}
+
+impl Limit {
+  /// Returns the value of this limit in the options.
+  pub fn get(self, options: &ParseOptions) -> Option<usize> {
+    match self {
+      Limit::Depth => options.max_depth,
+      Limit::StringLength => options.max_string_length,
+      Limit::AssocMembers => options.max_assoc_members,
+      Limit::Nodes => options.max_nodes,
     }
-    Rule::assoc => {
-      let mut assoc = HashMap::new();
-      pair.into_inner().for_each(|pair| {
-        let mut inner_rules = pair.into_inner();
-        let name = parse_name(inner_rules.next().unwrap());
-        let value = parse_value(inner_rules.next().unwrap());
-        assoc.insert(name, value);
-      });
-      value::Value::Assoc(assoc)
+  }
+}
+
+impl fmt::Display for Limit {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Limit::Depth => write!(f, "nesting depth"),
+      Limit::StringLength => write!(f, "string length"),
+      Limit::AssocMembers => write!(f, "number of assoc members"),
+      Limit::Nodes => write!(f, "number of values"),
     }
-    Rule::array => value::Value::Array(pair.into_inner().map(parse_value).collect()),
-    Rule::tuple => value::Value::Tuple(pair.into_inner().map(parse_value).collect()),
-    Rule::variant => {
-      let mut inner_rules = pair.into_inner();
-      let name = parse_name(inner_rules.next().unwrap());
-      let value = match inner_rules.next() {
-        None => None,
-        Some(rule) => Some(Box::new(parse_value(rule))),
-      };
-      let variant = (name, value);
-      value::Value::Variant(variant)
+  }
+}
+
+/// Position in a text.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Position {
+  /// Byte offset from the beginning of the text.
+  pub offset: usize,
+  /// Line number, starting at 1.
+  pub line: usize,
+  /// Column number in characters, starting at 1.
+  pub column: usize,
+}
+
+impl Position {
+  pub(crate) fn new(text: &str, offset: usize) -> Self {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+      offset,
+      line: before.matches('\n').count() + 1,
+      column: before[line_start..].chars().count() + 1,
     }
-    Rule::json
-    | Rule::EOI
-    | Rule::pair
-    | Rule::value
-    | Rule::inner
-    | Rule::name
-    | Rule::ascii_inner
-    | Rule::quoted_string
-    | Rule::c
-    | Rule::escape_char
-    | Rule::unicode_char
-    | Rule::unquoted_string
-    | Rule::ascii_char
-    | Rule::WHITESPACE
-    | Rule::COMMENT => unreachable!(),
-  }
-}
-
-fn parse_name(pair: Pair<Rule>) -> String {
-  let mut inner_rules = pair.into_inner().peekable();
-  let str = match inner_rules.peek().unwrap().as_rule() {
-    Rule::ascii_inner => inner_rules.next().unwrap().as_str().to_string(),
-    Rule::inner => {
-      let mut s = String::new();
-      for pair in inner_rules.next().unwrap().into_inner() {
-        match pair.as_rule() {
-          Rule::c => s.push_str(pair.as_str()),
-          Rule::escape_char => {
-            let c = match pair.as_str().chars().nth(1).unwrap() {
-              '"' => '\"',
-              '\\' => '\\',
-              '/' => '/',
-              'b' => '\u{0008}', // Backspace
-              'f' => '\u{000c}', // Form Feed
-              'n' => '\n',
-              'r' => '\r',
-              't' => '\t',
-              _ => unreachable!(),
-            };
-            s.push(c)
-          }
-          Rule::unicode_char => {
-            let hex = pair.as_str().chars().skip(2).collect::<String>();
-            let hex_i64 = i64::from_str_radix(&hex, 16).unwrap();
-            let str = String::from_utf8(vec![hex_i64 as u8]).unwrap();
-            s.push_str(&str)
-          }
-          _ => break,
-        }
+  }
+}
+
+/// Kind of a syntax error.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ErrorKind {
+  /// A token other than the expected one was found.
+  Expected {
+    /// Description of the expected tokens.
+    expected: &'static str,
+    /// Text of the found token.
+    found: String,
+  },
+  /// A member of an assoc has no colon after its name.
+  MissingColon,
+  /// A comma is followed by a closing bracket.
+  TrailingComma,
+  /// A string is quoted with `'`.
+  SingleQuotedString,
+  /// A string has no closing quote.
+  UnterminatedString,
+  /// A block comment has no closing `*/`.
+  UnterminatedComment,
+  /// A string contains an unknown escape sequence.
+  InvalidEscape(String),
+  /// A number is malformed or out of range.
+  InvalidNumber(String),
+  /// The text begins with a byte order mark.
+  ByteOrderMark,
+  /// The bytes are not valid in the encoding.
+  InvalidEncoding(&'static str),
+  /// A limit set by `ParseOptions` is exceeded. Parsing stops even in recovery mode.
+  LimitExceeded(Limit),
+}
+
+impl fmt::Display for ErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ErrorKind::Expected { expected, found } => {
+        write!(f, "expected {}, found {}", expected, found)
       }
-      s
+      ErrorKind::MissingColon => write!(f, "missing ':' after the name"),
+      ErrorKind::TrailingComma => write!(f, "trailing comma"),
+      ErrorKind::SingleQuotedString => write!(f, "single-quoted string"),
+      ErrorKind::UnterminatedString => write!(f, "unterminated string"),
+      ErrorKind::UnterminatedComment => write!(f, "unterminated comment"),
+      ErrorKind::InvalidEscape(escape) => write!(f, "invalid escape sequence {}", escape),
+      ErrorKind::InvalidNumber(number) => write!(f, "invalid number {}", number),
+      ErrorKind::ByteOrderMark => write!(f, "byte order mark"),
+      ErrorKind::InvalidEncoding(encoding) => write!(f, "invalid {}", encoding),
+      ErrorKind::LimitExceeded(limit) => write!(f, "limit of the {} exceeded", limit),
     }
-    _ => unreachable!(),
-  };
-  str
-}
-
-fn parse_string(pair: Pair<Rule>) -> String {
-  let mut s = String::new();
-  let inner_rules = pair.into_inner();
-  for pair in inner_rules {
-    match pair.as_rule() {
-      Rule::c => s.push_str(pair.as_str()),
-      Rule::escape_char => {
-        let c = match pair.as_str().chars().nth(1).unwrap() {
-          '"' => '\"',
-          '\\' => '\\',
-          '/' => '/',
-          'b' => '\u{0008}', // Backspace
-          'f' => '\u{000c}', // Form Feed
-          'n' => '\n',
-          'r' => '\r',
-          't' => '\t',
-          _ => unreachable!(),
-        };
-        s.push(c)
-      }
-      Rule::unicode_char => {
-        let hex = pair.as_str().chars().skip(2).collect::<String>();
-        let hex_i64 = i64::from_str_radix(&hex, 16).unwrap();
-        let str = String::from_utf8(vec![hex_i64 as u8]).unwrap();
-        s.push_str(&str)
-      }
-      _ => break,
+  }
+}
+
+/// Syntax error with its position.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Error {
+  kind: ErrorKind,
+  position: Position,
+}
+
+impl Error {
+  pub(crate) fn new(kind: ErrorKind, text: &str, offset: usize) -> Self {
+    Error {
+      kind,
+      position: Position::new(text, offset),
     }
   }
-  s
+
+  /// Creates an error at a byte offset, where `prefix` is the text decoded before the offset.
+  pub(crate) fn at(kind: ErrorKind, prefix: &str, offset: usize) -> Self {
+    let mut position = Position::new(prefix, prefix.len());
+    position.offset = offset;
+    Error { kind, position }
+  }
+
+  /// Returns the kind of this error.
+  pub fn kind(&self) -> &ErrorKind {
+    &self.kind
+  }
+
+  /// Returns the position where this error is found.
+  pub fn position(&self) -> Position {
+    self.position
+  }
 }
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} at line {}, column {}",
+      self.kind, self.position.line, self.position.column
+    )
+  }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,348 @@
+//! Definition of JSON Patch (RFC 6902) and JSON Merge Patch (RFC 7396) on Yojson values.
+//!
+//! Paths are JSON pointers as in the `pointer` module, so:
+//! - Elements of a tuple can be replaced and tested, but a tuple has a fixed length, so `add`
+//!   and `remove` into a tuple, and `move` out of it, fail.
+//! - The argument of a variant is at `-payload`: `add` sets it, `remove` drops it and `replace`
+//!   changes it. Replacing the variant itself changes the constructor.
+
+use std::fmt;
+
+use super::diff::{self, Change};
+use super::pointer::PointerError;
+use super::value::Value;
+
+/// Operation of a JSON Patch.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Operation {
+  /// Inserts a value, or replaces a member of an assoc.
+  Add {
+    /// Target pointer.
+    path: String,
+    /// Inserted value.
+    value: Value,
+  },
+  /// Removes a value.
+  Remove {
+    /// Target pointer.
+    path: String,
+  },
+  /// Replaces an existing value.
+  Replace {
+    /// Target pointer.
+    path: String,
+    /// New value.
+    value: Value,
+  },
+  /// Removes a value and adds it at another pointer.
+  Move {
+    /// Source pointer.
+    from: String,
+    /// Target pointer.
+    path: String,
+  },
+  /// Adds a copy of a value at another pointer.
+  Copy {
+    /// Source pointer.
+    from: String,
+    /// Target pointer.
+    path: String,
+  },
+  /// Tests whether the value at a pointer equals a value.
+  Test {
+    /// Target pointer.
+    path: String,
+    /// Expected value.
+    value: Value,
+  },
+}
+
+/// List of operations, applied in order.
+pub type Patch = Vec<Operation>;
+
+/// Error of patching.
+#[derive(PartialEq, Clone, Debug)]
+pub enum PatchError {
+  /// An operation failed at a pointer.
+  Pointer {
+    /// Index of the operation.
+    index: usize,
+    /// Error at the pointer.
+    error: PointerError,
+  },
+  /// A `test` operation found a different value.
+  TestFailed {
+    /// Index of the operation.
+    index: usize,
+    /// Target pointer.
+    path: String,
+  },
+  /// A `move` operation moves a value into one of its children.
+  MoveIntoItself {
+    /// Index of the operation.
+    index: usize,
+    /// Source pointer.
+    from: String,
+  },
+  /// A value does not represent a patch.
+  Invalid(String),
+}
+
+impl fmt::Display for PatchError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PatchError::Pointer { index, error } => write!(f, "operation {}: {}", index, error),
+      PatchError::TestFailed { index, path } => {
+        write!(f, "operation {}: test failed at \"{}\"", index, path)
+      }
+      PatchError::MoveIntoItself { index, from } => {
+        write!(
+          f,
+          "operation {}: cannot move \"{}\" into itself",
+          index, from
+        )
+      }
+      PatchError::Invalid(message) => write!(f, "invalid patch: {}", message),
+    }
+  }
+}
+
+impl std::error::Error for PatchError {}
+
+fn apply_operation(
+  value: &mut Value,
+  operation: &Operation,
+  index: usize,
+) -> Result<(), PatchError> {
+  let pointer_error = |error| PatchError::Pointer { index, error };
+  let not_found = |path: &str| pointer_error(PointerError::NotFound(path.to_string()));
+  match operation {
+    Operation::Add { path, value: v } => {
+      value
+        .pointer_insert(path, v.clone())
+        .map_err(pointer_error)?;
+    }
+    Operation::Remove { path } => {
+      value.pointer_remove(path).map_err(pointer_error)?;
+    }
+    Operation::Replace { path, value: v } => match value.pointer_mut(path) {
+      Some(target) => *target = v.clone(),
+      None => return Err(not_found(path)),
+    },
+    Operation::Move { from, path } => {
+      if value.pointer(from).is_none() {
+        return Err(not_found(from));
+      }
+      if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+        return Err(PatchError::MoveIntoItself {
+          index,
+          from: from.clone(),
+        });
+      }
+      if from != path {
+        let moved = value.pointer_remove(from).map_err(pointer_error)?;
+        value.pointer_insert(path, moved).map_err(pointer_error)?;
+      }
+    }
+    Operation::Copy { from, path } => {
+      let copied = value
+        .pointer(from)
+        .cloned()
+        .ok_or_else(|| not_found(from))?;
+      value.pointer_insert(path, copied).map_err(pointer_error)?;
+    }
+    Operation::Test { path, value: v } => match value.pointer(path) {
+      Some(target) if diff::diff(target, v).is_empty() => (),
+      _ => {
+        return Err(PatchError::TestFailed {
+          index,
+          path: path.clone(),
+        })
+      }
+    },
+  }
+  Ok(())
+}
+
+/// Applies a patch. The value is left unchanged if an operation fails.
+pub fn apply_patch(value: &mut Value, patch: &[Operation]) -> Result<(), PatchError> {
+  let mut patched = value.clone();
+  for (index, operation) in patch.iter().enumerate() {
+    apply_operation(&mut patched, operation, index)?;
+  }
+  *value = patched;
+  Ok(())
+}
+
+/// Applies a merge patch.
+///
+/// An assoc is merged member by member and `null` removes a member, as in RFC 7396.
+/// As extensions, a tuple of the same length as the target is merged element by element,
+/// and a variant with the same constructor as the target, where both have an argument,
+/// merges its argument. Any other value replaces the target.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+  // Nested patches are applied with an explicit stack, so that deep values do not overflow
+  // the call stack.
+  let mut stack = vec![(target, patch)];
+  while let Some((target, patch)) = stack.pop() {
+    let merged = match (&*target, patch) {
+      (_, Value::Assoc(_)) => true,
+      (Value::Tuple(target), Value::Tuple(patch)) => target.len() == patch.len(),
+      (Value::Variant((name, Some(_))), Value::Variant((patch_name, Some(_)))) => {
+        name == patch_name
+      }
+      _ => false,
+    };
+    if !merged {
+      *target = patch.clone();
+      continue;
+    }
+    if !matches!(target, Value::Assoc(_)) && matches!(patch, Value::Assoc(_)) {
+      *target = Value::Assoc(Default::default());
+    }
+    match (target, patch) {
+      (Value::Assoc(target), Value::Assoc(patch)) => {
+        for (name, value) in patch {
+          if value.is_null() {
+            target.remove(name);
+          } else {
+            target.entry(name.clone()).or_insert(Value::Null);
+          }
+        }
+        for (name, target) in target.iter_mut() {
+          if let Some(patch) = patch.get(name) {
+            stack.push((target, patch));
+          }
+        }
+      }
+      (Value::Tuple(target), Value::Tuple(patch)) => stack.extend(target.iter_mut().zip(patch)),
+      (Value::Variant((_, Some(target))), Value::Variant((_, Some(patch)))) => {
+        stack.push((target, patch))
+      }
+      _ => unreachable!(),
+    }
+  }
+}
+
+/// Generates a patch which turns `left` into `right`.
+///
+/// ```
+/// let left = yojson_rs::parser::parse("{a : [1, 2, 3], b : <A>}").unwrap();
+/// let right = yojson_rs::parser::parse("{a : [1], b : <A: 1>}").unwrap();
+/// let patch = yojson_rs::patch::generate(&left, &right);
+/// let mut value = left.clone();
+/// yojson_rs::patch::apply_patch(&mut value, &patch).unwrap();
+/// assert_eq!(value, right);
+/// ```
+pub fn generate(left: &Value, right: &Value) -> Patch {
+  let mut patch = Patch::new();
+  // Trailing elements of an array are removed from the last one, so that indices stay valid.
+  let mut removed = Vec::new();
+  for change in diff::diff(left, right) {
+    if let Change::Removed { path, .. } = change {
+      if let Some(Operation::Remove { path: last }) = removed.last() {
+        if parent(last) != parent(&path) {
+          patch.extend(removed.drain(..).rev());
+        }
+      }
+      removed.push(Operation::Remove { path });
+      continue;
+    }
+    patch.extend(removed.drain(..).rev());
+    patch.push(match change {
+      Change::Added { path, value } => Operation::Add { path, value },
+      Change::Changed { path, new, .. } => Operation::Replace { path, value: new },
+      Change::Removed { .. } => unreachable!(),
+    });
+  }
+  patch.extend(removed.drain(..).rev());
+  patch
+}
+
+fn parent(path: &str) -> &str {
+  &path[..path.rfind('/').unwrap_or(0)]
+}
+
+/// Reads a patch from its JSON representation, an array of assocs such as
+/// `[{op : "add", path : "/a", value : 1}]`.
+pub fn from_value(value: &Value) -> Result<Patch, PatchError> {
+  let operations = match value {
+    Value::Array(operations) => operations,
+    _ => return Err(PatchError::Invalid("a patch must be an array".to_string())),
+  };
+  let mut patch = Patch::new();
+  for (index, operation) in operations.iter().enumerate() {
+    let invalid = |message: &str| PatchError::Invalid(format!("operation {}: {}", index, message));
+    let assoc = operation
+      .as_assoc()
+      .ok_or_else(|| invalid("an operation must be an assoc"))?;
+    let string = |name: &str| {
+      assoc
+        .get(name)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| invalid(&format!("\"{}\" must be a string", name)))
+    };
+    let value = || {
+      assoc
+        .get("value")
+        .cloned()
+        .ok_or_else(|| invalid("\"value\" is missing"))
+    };
+    let path = string("path")?;
+    patch.push(match string("op")?.as_str() {
+      "add" => Operation::Add {
+        path,
+        value: value()?,
+      },
+      "remove" => Operation::Remove { path },
+      "replace" => Operation::Replace {
+        path,
+        value: value()?,
+      },
+      "move" => Operation::Move {
+        from: string("from")?,
+        path,
+      },
+      "copy" => Operation::Copy {
+        from: string("from")?,
+        path,
+      },
+      "test" => Operation::Test {
+        path,
+        value: value()?,
+      },
+      op => return Err(invalid(&format!("unknown operation \"{}\"", op))),
+    });
+  }
+  Ok(patch)
+}
+
+/// Writes a patch in its JSON representation.
+pub fn to_value(patch: &[Operation]) -> Value {
+  let operations = patch
+    .iter()
+    .map(|operation| {
+      let string = |s: &str| Value::String(s.to_string());
+      let mut assoc = std::collections::HashMap::new();
+      let (op, path, from, value) = match operation {
+        Operation::Add { path, value } => ("add", path, None, Some(value)),
+        Operation::Remove { path } => ("remove", path, None, None),
+        Operation::Replace { path, value } => ("replace", path, None, Some(value)),
+        Operation::Move { from, path } => ("move", path, Some(from), None),
+        Operation::Copy { from, path } => ("copy", path, Some(from), None),
+        Operation::Test { path, value } => ("test", path, None, Some(value)),
+      };
+      assoc.insert("op".to_string(), string(op));
+      assoc.insert("path".to_string(), string(path));
+      if let Some(from) = from {
+        assoc.insert("from".to_string(), string(from));
+      }
+      if let Some(value) = value {
+        assoc.insert("value".to_string(), value.clone());
+      }
+      Value::Assoc(assoc)
+    })
+    .collect();
+  Value::Array(operations)
+}
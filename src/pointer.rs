@@ -0,0 +1,219 @@
+//! Definition of JSON Pointer (RFC 6901) on Yojson values.
+//!
+//! A pointer is a sequence of tokens, each prefixed with `/`, and `~` and `/` in a token are
+//! written `~0` and `~1`. The semantics are extended for Yojson:
+//! - A numeric token indexes into an array or a tuple.
+//! - The token `-payload` descends into the argument of a variant: `/status/-payload`.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use super::value::Value;
+
+/// Token which descends into the argument of a variant.
+pub const PAYLOAD: &str = "-payload";
+
+/// Error of an operation at a pointer.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PointerError {
+  /// The pointer is neither empty nor starts with `/`.
+  Syntax(String),
+  /// Nothing is found at the pointer.
+  NotFound(String),
+  /// A token cannot be applied to the value it points into.
+  TypeMismatch {
+    /// Token of the pointer.
+    token: String,
+    /// Type of the value, as returned by `Value::type_str`.
+    found: &'static str,
+  },
+  /// An element is inserted into or removed from a tuple, whose length is fixed.
+  FixedLength(String),
+}
+
+impl fmt::Display for PointerError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PointerError::Syntax(pointer) => write!(f, "invalid pointer \"{}\"", pointer),
+      PointerError::NotFound(pointer) => write!(f, "\"{}\" is not found", pointer),
+      PointerError::TypeMismatch { token, found } => {
+        write!(f, "cannot apply \"{}\" to a value of type {}", token, found)
+      }
+      PointerError::FixedLength(pointer) => {
+        write!(
+          f,
+          "cannot change the length of the tuple at \"{}\"",
+          pointer
+        )
+      }
+    }
+  }
+}
+
+impl std::error::Error for PointerError {}
+
+/// Escapes `~` and `/` in a token.
+pub fn escape(token: &str) -> Cow<'_, str> {
+  if token.contains(['~', '/']) {
+    Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+  } else {
+    Cow::Borrowed(token)
+  }
+}
+
+/// Splits a pointer into unescaped tokens.
+pub fn tokens(pointer: &str) -> Result<Vec<String>, PointerError> {
+  if pointer.is_empty() {
+    return Ok(Vec::new());
+  }
+  match pointer.strip_prefix('/') {
+    Some(rest) => Ok(
+      rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect(),
+    ),
+    None => Err(PointerError::Syntax(pointer.to_string())),
+  }
+}
+
+/// Builds a pointer from tokens.
+pub fn join<S: AsRef<str>>(tokens: &[S]) -> String {
+  let mut pointer = String::new();
+  for token in tokens {
+    pointer.push('/');
+    pointer.push_str(&escape(token.as_ref()));
+  }
+  pointer
+}
+
+/// Reads an array index, which has no leading zeros.
+pub(crate) fn index(token: &str) -> Option<usize> {
+  if token.bytes().all(|b| b.is_ascii_digit()) && (token == "0" || !token.starts_with('0')) {
+    token.parse().ok()
+  } else {
+    None
+  }
+}
+
+fn child<'a>(value: &'a Value, token: &str) -> Option<&'a Value> {
+  match value {
+    Value::Assoc(assoc) => assoc.get(token),
+    Value::Array(array) | Value::Tuple(array) => array.get(index(token)?),
+    Value::Variant((_, Some(payload))) if token == PAYLOAD => Some(payload),
+    _ => None,
+  }
+}
+
+fn child_mut<'a>(value: &'a mut Value, token: &str) -> Option<&'a mut Value> {
+  match value {
+    Value::Assoc(assoc) => assoc.get_mut(token),
+    Value::Array(array) | Value::Tuple(array) => array.get_mut(index(token)?),
+    Value::Variant((_, Some(payload))) if token == PAYLOAD => Some(payload),
+    _ => None,
+  }
+}
+
+impl Value {
+  /// Looks up a value by a JSON pointer.
+  ///
+  /// ```
+  /// let value = yojson_rs::parser::parse(r#"{y : {y2 : [null, 123.45, (12, "y3")]}}"#).unwrap();
+  /// assert_eq!(value.pointer("/y/y2/2/1").and_then(|v| v.as_str()), Some("y3"));
+  /// ```
+  pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+    let mut value = self;
+    for token in tokens(pointer).ok()? {
+      value = child(value, &token)?;
+    }
+    Some(value)
+  }
+
+  /// Looks up a value by a JSON pointer and returns a mutable reference to it.
+  pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+    let mut value = self;
+    for token in tokens(pointer).ok()? {
+      value = child_mut(value, &token)?;
+    }
+    Some(value)
+  }
+
+  /// Returns the parent of the value at a pointer, and the last token.
+  fn pointer_parent_mut(&mut self, pointer: &str) -> Result<(&mut Value, String), PointerError> {
+    let mut tokens = tokens(pointer)?;
+    let last = match tokens.pop() {
+      Some(last) => last,
+      None => return Err(PointerError::NotFound(pointer.to_string())),
+    };
+    let mut value = self;
+    for (i, token) in tokens.iter().enumerate() {
+      value = match child_mut(value, token) {
+        Some(value) => value,
+        None => return Err(PointerError::NotFound(join(&tokens[..=i]))),
+      };
+    }
+    Ok((value, last))
+  }
+
+  /// Inserts a value at a JSON pointer, as the `add` operation of JSON Patch.
+  ///
+  /// A member of an assoc and the argument of a variant are replaced and the old value is
+  /// returned. An element is inserted into an array, and `-` appends it. The empty pointer
+  /// replaces the whole value. Tuples cannot get new elements.
+  pub fn pointer_insert(
+    &mut self,
+    pointer: &str,
+    value: Value,
+  ) -> Result<Option<Value>, PointerError> {
+    if pointer.is_empty() {
+      return Ok(Some(std::mem::replace(self, value)));
+    }
+    let (parent, token) = self.pointer_parent_mut(pointer)?;
+    match parent {
+      Value::Assoc(assoc) => Ok(assoc.insert(token, value)),
+      Value::Array(array) => {
+        let i = if token == "-" {
+          array.len()
+        } else {
+          match index(&token) {
+            Some(i) if i <= array.len() => i,
+            _ => return Err(PointerError::NotFound(pointer.to_string())),
+          }
+        };
+        array.insert(i, value);
+        Ok(None)
+      }
+      Value::Tuple(_) => Err(PointerError::FixedLength(pointer.to_string())),
+      Value::Variant((_, payload)) if token == PAYLOAD => {
+        Ok(payload.replace(Box::new(value)).map(|old| *old))
+      }
+      parent => Err(PointerError::TypeMismatch {
+        token,
+        found: parent.type_str(),
+      }),
+    }
+  }
+
+  /// Removes the value at a JSON pointer and returns it.
+  ///
+  /// Removing `-payload` leaves a variant without argument. Tuples cannot lose elements.
+  pub fn pointer_remove(&mut self, pointer: &str) -> Result<Value, PointerError> {
+    let (parent, token) = self.pointer_parent_mut(pointer)?;
+    let not_found = || PointerError::NotFound(pointer.to_string());
+    match parent {
+      Value::Assoc(assoc) => assoc.remove(&token).ok_or_else(not_found),
+      Value::Array(array) => match index(&token) {
+        Some(i) if i < array.len() => Ok(array.remove(i)),
+        _ => Err(not_found()),
+      },
+      Value::Tuple(_) => Err(PointerError::FixedLength(pointer.to_string())),
+      Value::Variant((_, payload)) if token == PAYLOAD => {
+        payload.take().map(|old| *old).ok_or_else(not_found)
+      }
+      parent => Err(PointerError::TypeMismatch {
+        token,
+        found: parent.type_str(),
+      }),
+    }
+  }
+}
@@ -0,0 +1,675 @@
+//! Definition of a JSONPath-like query language over Yojson values.
+//!
+//! A query is a sequence of steps applied to the list of selected values, starting from the root:
+//! - `$` is the root and may be omitted, and a leading name may omit the dot: `items[0]`.
+//! - `.name` and `['name']` select a member of an assoc, `.*` and `[*]` every child.
+//! - `..` selects the value and all of its descendants before the next step: `..id`.
+//! - `[1]`, `[-1]`, `[0, 2]` and `[1:5:2]` index and slice arrays and tuples.
+//! - `[?(expr)]` keeps the children for which the filter holds, such as
+//!   `[?(@.status == <Active> && @.score >= 10)]`. Operands are paths from the child (`@`) or
+//!   from the root (`$`), and literals written in Yojson syntax. A path alone tests existence.
+//! - `<Name>` keeps the values which are the variant `Name`, and `<Name:>` selects its argument.
+//!   `<*>` matches any constructor.
+//!
+//! A query starting with `/` is a JSON pointer instead, and selects the value of
+//! `Value::pointer`, such as `/servers/0/port` or `/status/-payload`.
+//!
+//! The children of a value are the members of an assoc in the order of their names,
+//! the elements of an array or a tuple, and the argument of a variant.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use super::value::Value;
+
+/// Error of a query expression.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct QueryError {
+  message: String,
+  offset: usize,
+}
+
+impl QueryError {
+  fn new(message: impl Into<String>, offset: usize) -> Self {
+    QueryError {
+      message: message.into(),
+      offset,
+    }
+  }
+
+  /// Description of the error.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Byte offset of the error in the expression.
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+}
+
+impl fmt::Display for QueryError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} at offset {}", self.message, self.offset)
+  }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Clone, Debug)]
+enum Selector {
+  Name(String),
+  Index(i64),
+  Slice(Option<i64>, Option<i64>, i64),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Op {
+  Eq,
+  Ne,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+}
+
+#[derive(Clone, Debug)]
+enum Operand {
+  Current(Vec<Step>),
+  Root(Vec<Step>),
+  Literal(Value),
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+  Exists(Operand),
+  Compare(Operand, Op, Operand),
+  Not(Box<Expr>),
+  And(Box<Expr>, Box<Expr>),
+  Or(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+  Child(String),
+  Wildcard,
+  Descendants,
+  Select(Vec<Selector>),
+  Filter(Expr),
+  Variant(Option<String>, bool),
+  Pointer(String),
+}
+
+/// Compiled query expression.
+#[derive(Clone, Debug)]
+pub struct Query {
+  steps: Vec<Step>,
+}
+
+impl Query {
+  /// Compiles a query expression.
+  pub fn parse(expr: &str) -> Result<Query, QueryError> {
+    if expr.starts_with('/') {
+      return Ok(Query {
+        steps: vec![Step::Pointer(expr.to_string())],
+      });
+    }
+    let mut parser = Parser {
+      text: expr,
+      pos: 0,
+      depth: 0,
+    };
+    parser.skip_whitespace();
+    let mut steps = Vec::new();
+    if !parser.eat("$") && parser.peek().is_some_and(is_name_start) {
+      steps.push(Step::Child(parser.name()));
+    }
+    steps.extend(parser.steps()?);
+    parser.skip_whitespace();
+    if parser.pos < expr.len() {
+      return Err(QueryError::new("unexpected character", parser.pos));
+    }
+    Ok(Query { steps })
+  }
+
+  /// Selects the values matching the query.
+  pub fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+    apply(&self.steps, root, root)
+  }
+}
+
+impl FromStr for Query {
+  type Err = QueryError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Query::parse(s)
+  }
+}
+
+/// Compiles a query expression and selects the values matching it.
+///
+/// ```
+/// let value = yojson_rs::parser::parse(
+///   r#"{items : [{id : 1, status : <Active>}, {id : 2, status : <Closed>}]}"#,
+/// ).unwrap();
+/// let ids = yojson_rs::query::select(&value, "$.items[?(@.status == <Active>)].id").unwrap();
+/// assert_eq!(ids, vec![&yojson_rs::value::Value::Integer(1)]);
+/// ```
+pub fn select<'a>(value: &'a Value, expr: &str) -> Result<Vec<&'a Value>, QueryError> {
+  Ok(Query::parse(expr)?.select(value))
+}
+
+fn is_name_start(c: char) -> bool {
+  c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Maximum nesting of filters, groups and negations in an expression.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+  text: &'a str,
+  pos: usize,
+  depth: usize,
+}
+
+impl<'a> Parser<'a> {
+  fn rest(&self) -> &'a str {
+    &self.text[self.pos..]
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.rest().chars().next()
+  }
+
+  fn eat(&mut self, s: &str) -> bool {
+    if self.rest().starts_with(s) {
+      self.pos += s.len();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, s: &str) -> Result<(), QueryError> {
+    self.skip_whitespace();
+    if self.eat(s) {
+      Ok(())
+    } else {
+      Err(QueryError::new(format!("expected \"{}\"", s), self.pos))
+    }
+  }
+
+  fn skip_whitespace(&mut self) {
+    let rest = self.rest();
+    self.pos += rest.len() - rest.trim_start().len();
+  }
+
+  fn name(&mut self) -> String {
+    let rest = self.rest();
+    let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+    self.pos += len;
+    rest[..len].to_string()
+  }
+
+  /// Parses a nested expression, which fails beyond `MAX_DEPTH` levels of nesting.
+  fn nested<T>(
+    &mut self,
+    f: impl FnOnce(&mut Self) -> Result<T, QueryError>,
+  ) -> Result<T, QueryError> {
+    if self.depth == MAX_DEPTH {
+      return Err(QueryError::new("expression nested too deeply", self.pos));
+    }
+    self.depth += 1;
+    let result = f(self);
+    self.depth -= 1;
+    result
+  }
+
+  /// Parses steps until a character which cannot start a step.
+  fn steps(&mut self) -> Result<Vec<Step>, QueryError> {
+    let mut steps = Vec::new();
+    loop {
+      if self.eat("..") {
+        steps.push(Step::Descendants);
+        match self.peek() {
+          Some('[') | Some('<') => continue,
+          _ => steps.push(self.member()?),
+        }
+      } else if self.eat(".") {
+        steps.push(self.member()?)
+      } else if self.peek() == Some('[') {
+        steps.push(self.bracket()?)
+      } else if self.peek() == Some('<') && self.is_variant_step() {
+        steps.push(self.variant()?)
+      } else {
+        return Ok(steps);
+      }
+    }
+  }
+
+  /// Parses the name or the wildcard after a dot.
+  fn member(&mut self) -> Result<Step, QueryError> {
+    if self.eat("*") {
+      Ok(Step::Wildcard)
+    } else if self.peek().is_some_and(is_name_char) {
+      Ok(Step::Child(self.name()))
+    } else {
+      Err(QueryError::new("expected a name", self.pos))
+    }
+  }
+
+  /// Tells a variant step from the comparison operator `<` in a filter.
+  fn is_variant_step(&self) -> bool {
+    let rest = &self.rest()[1..];
+    let after = match rest.strip_prefix('*') {
+      Some(after) => after,
+      None if rest.starts_with(is_name_start) => rest.trim_start_matches(is_name_char),
+      None => return false,
+    };
+    after.starts_with('>') || after.starts_with(":>")
+  }
+
+  fn variant(&mut self) -> Result<Step, QueryError> {
+    self.pos += 1;
+    let name = if self.eat("*") {
+      None
+    } else {
+      Some(self.name())
+    };
+    let payload = self.eat(":");
+    self.expect(">")?;
+    Ok(Step::Variant(name, payload))
+  }
+
+  fn bracket(&mut self) -> Result<Step, QueryError> {
+    self.pos += 1;
+    self.skip_whitespace();
+    let step = if self.eat("*") {
+      Step::Wildcard
+    } else if self.eat("?") {
+      self.expect("(")?;
+      let expr = self.nested(Self::or)?;
+      self.expect(")")?;
+      Step::Filter(expr)
+    } else {
+      let mut selectors = vec![self.selector()?];
+      loop {
+        self.skip_whitespace();
+        if !self.eat(",") {
+          break;
+        }
+        self.skip_whitespace();
+        selectors.push(self.selector()?);
+      }
+      Step::Select(selectors)
+    };
+    self.expect("]")?;
+    Ok(step)
+  }
+
+  fn selector(&mut self) -> Result<Selector, QueryError> {
+    if let Some(quote) = self.peek().filter(|&c| c == '\'' || c == '"') {
+      return Ok(Selector::Name(self.quoted(quote)?));
+    }
+    let start = self.integer()?;
+    self.skip_whitespace();
+    if !self.eat(":") {
+      return match start {
+        Some(i) => Ok(Selector::Index(i)),
+        None => Err(QueryError::new("expected an index or a name", self.pos)),
+      };
+    }
+    let end = self.integer()?;
+    self.skip_whitespace();
+    let step = if self.eat(":") {
+      let offset = self.pos;
+      match self.integer()? {
+        Some(0) => return Err(QueryError::new("slice step cannot be zero", offset)),
+        Some(step) => step,
+        None => 1,
+      }
+    } else {
+      1
+    };
+    Ok(Selector::Slice(start, end, step))
+  }
+
+  fn integer(&mut self) -> Result<Option<i64>, QueryError> {
+    self.skip_whitespace();
+    let start = self.pos;
+    self.eat("-");
+    let rest = self.rest();
+    let len = rest
+      .find(|c: char| !c.is_ascii_digit())
+      .unwrap_or(rest.len());
+    self.pos += len;
+    if len == 0 {
+      self.pos = start;
+      return Ok(None);
+    }
+    match self.text[start..self.pos].parse() {
+      Ok(i) => Ok(Some(i)),
+      Err(_) => Err(QueryError::new("integer out of range", start)),
+    }
+  }
+
+  fn quoted(&mut self, quote: char) -> Result<String, QueryError> {
+    let start = self.pos;
+    self.pos += 1;
+    let mut s = String::new();
+    let mut chars = self.rest().chars();
+    while let Some(c) = chars.next() {
+      self.pos += c.len_utf8();
+      match c {
+        '\\' => match chars.next() {
+          Some(c) => {
+            self.pos += c.len_utf8();
+            s.push(c)
+          }
+          None => break,
+        },
+        c if c == quote => return Ok(s),
+        c => s.push(c),
+      }
+    }
+    Err(QueryError::new("unterminated string", start))
+  }
+
+  fn or(&mut self) -> Result<Expr, QueryError> {
+    let mut expr = self.and()?;
+    loop {
+      self.skip_whitespace();
+      if !self.eat("||") {
+        return Ok(expr);
+      }
+      expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+    }
+  }
+
+  fn and(&mut self) -> Result<Expr, QueryError> {
+    let mut expr = self.unary()?;
+    loop {
+      self.skip_whitespace();
+      if !self.eat("&&") {
+        return Ok(expr);
+      }
+      expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+    }
+  }
+
+  fn unary(&mut self) -> Result<Expr, QueryError> {
+    self.skip_whitespace();
+    if self.rest().starts_with('!') && !self.rest().starts_with("!=") {
+      self.pos += 1;
+      return Ok(Expr::Not(Box::new(self.nested(Self::unary)?)));
+    }
+    if self.peek() == Some('(') && !self.is_tuple() {
+      self.pos += 1;
+      let expr = self.nested(Self::or)?;
+      self.expect(")")?;
+      return Ok(expr);
+    }
+    let left = self.operand()?;
+    self.skip_whitespace();
+    let op = [
+      ("==", Op::Eq),
+      ("!=", Op::Ne),
+      ("<=", Op::Le),
+      (">=", Op::Ge),
+      ("<", Op::Lt),
+      (">", Op::Gt),
+    ]
+    .iter()
+    .find(|(s, _)| self.rest().starts_with(s));
+    match op {
+      Some(&(s, op)) => {
+        self.pos += s.len();
+        Ok(Expr::Compare(left, op, self.operand()?))
+      }
+      None => Ok(Expr::Exists(left)),
+    }
+  }
+
+  /// Tells a tuple literal from a group, by a comma which is not nested in another bracket.
+  fn is_tuple(&self) -> bool {
+    let mut depth = 0usize;
+    let mut chars = self.rest()[1..].chars();
+    while let Some(c) = chars.next() {
+      match c {
+        '"' | '\'' => {
+          let mut escaped = false;
+          for d in chars.by_ref() {
+            match d {
+              '\\' if !escaped => escaped = true,
+              d if d == c && !escaped => break,
+              _ => escaped = false,
+            }
+          }
+        }
+        '(' | '[' | '{' => depth += 1,
+        ')' | ']' | '}' if depth == 0 => return false,
+        ')' | ']' | '}' => depth -= 1,
+        ',' if depth == 0 => return true,
+        _ => (),
+      }
+    }
+    false
+  }
+
+  fn operand(&mut self) -> Result<Operand, QueryError> {
+    self.skip_whitespace();
+    if self.eat("@") {
+      Ok(Operand::Current(self.steps()?))
+    } else if self.eat("$") {
+      Ok(Operand::Root(self.steps()?))
+    } else {
+      self.literal().map(Operand::Literal)
+    }
+  }
+
+  /// Parses a literal in Yojson syntax, which ends at an operator or a closing parenthesis.
+  fn literal(&mut self) -> Result<Value, QueryError> {
+    let start = self.pos;
+    let mut depth = 0usize;
+    let mut chars = self.rest().char_indices();
+    let mut end = self.rest().len();
+    while let Some((i, c)) = chars.next() {
+      match c {
+        '"' | '\'' => {
+          let mut escaped = false;
+          for (_, d) in chars.by_ref() {
+            match d {
+              '\\' if !escaped => escaped = true,
+              d if d == c && !escaped => break,
+              _ => escaped = false,
+            }
+          }
+        }
+        '<' if depth > 0 || i == 0 => depth += 1,
+        '(' | '[' | '{' => depth += 1,
+        ')' | ']' | '}' | '>' if depth > 0 => depth -= 1,
+        c if depth == 0 && (c.is_whitespace() || "()[]<>=!&|,".contains(c)) => {
+          end = i;
+          break;
+        }
+        _ => (),
+      }
+    }
+    if end == 0 {
+      return Err(QueryError::new("expected an operand", start));
+    }
+    let options = super::parser::ParseOptions::default();
+    let mut reader = super::reader::Reader::new(&self.rest()[..end], &options, false);
+    match reader.single_value() {
+      Ok(value) => {
+        self.pos += end;
+        Ok(value)
+      }
+      Err(_) => Err(QueryError::new("invalid literal", start)),
+    }
+  }
+}
+
+/// Children of a value.
+fn children(value: &Value) -> Vec<&Value> {
+  match value {
+    Value::Assoc(assoc) => {
+      let mut members = assoc.iter().collect::<Vec<_>>();
+      members.sort_by(|a, b| a.0.cmp(b.0));
+      members.into_iter().map(|(_, v)| v).collect()
+    }
+    Value::Array(array) | Value::Tuple(array) => array.iter().collect(),
+    Value::Variant((_, Some(payload))) => vec![payload],
+    _ => Vec::new(),
+  }
+}
+
+/// The value and its descendants in pre-order, without recursion.
+fn descendants(value: &Value) -> Vec<&Value> {
+  let mut values = Vec::new();
+  let mut stack = vec![value];
+  while let Some(value) = stack.pop() {
+    values.push(value);
+    stack.extend(children(value).into_iter().rev());
+  }
+  values
+}
+
+/// Indices of a slice with Python semantics.
+fn slice(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+  let len = len as i64;
+  let norm = |i: i64| if i < 0 { i + len } else { i };
+  let mut indices = Vec::new();
+  if step > 0 {
+    let mut i = start.map_or(0, norm).clamp(0, len);
+    let end = end.map_or(len, norm).clamp(0, len);
+    while i < end {
+      indices.push(i as usize);
+      i += step;
+    }
+  } else {
+    let mut i = start.map_or(len - 1, norm).clamp(-1, len - 1);
+    let end = end.map_or(-1, norm).clamp(-1, len - 1);
+    while i > end {
+      indices.push(i as usize);
+      i += step;
+    }
+  }
+  indices
+}
+
+fn apply<'a>(steps: &[Step], value: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+  let mut values = vec![value];
+  for step in steps {
+    let mut next = Vec::new();
+    for value in values {
+      match step {
+        Step::Child(name) => {
+          if let Value::Assoc(assoc) = value {
+            next.extend(assoc.get(name))
+          }
+        }
+        Step::Wildcard => next.extend(children(value)),
+        Step::Descendants => next.extend(descendants(value)),
+        Step::Select(selectors) => {
+          for selector in selectors {
+            select_one(selector, value, &mut next)
+          }
+        }
+        Step::Filter(expr) => next.extend(
+          children(value)
+            .into_iter()
+            .filter(|child| eval(expr, child, root)),
+        ),
+        Step::Variant(name, payload) => {
+          if let Value::Variant((constructor, argument)) = value {
+            if name.as_ref().is_none_or(|name| name == constructor) {
+              match (payload, argument) {
+                (false, _) => next.push(value),
+                (true, Some(argument)) => next.push(argument),
+                (true, None) => (),
+              }
+            }
+          }
+        }
+        Step::Pointer(pointer) => next.extend(value.pointer(pointer)),
+      }
+    }
+    values = next;
+  }
+  values
+}
+
+fn select_one<'a>(selector: &Selector, value: &'a Value, next: &mut Vec<&'a Value>) {
+  match (selector, value) {
+    (Selector::Name(name), Value::Assoc(assoc)) => next.extend(assoc.get(name)),
+    (Selector::Index(i), Value::Array(array)) | (Selector::Index(i), Value::Tuple(array)) => {
+      let i = if *i < 0 { *i + array.len() as i64 } else { *i };
+      if i >= 0 {
+        next.extend(array.get(i as usize))
+      }
+    }
+    (Selector::Slice(start, end, step), Value::Array(array))
+    | (Selector::Slice(start, end, step), Value::Tuple(array)) => next.extend(
+      slice(array.len(), *start, *end, *step)
+        .into_iter()
+        .map(|i| &array[i]),
+    ),
+    _ => (),
+  }
+}
+
+fn eval(expr: &Expr, current: &Value, root: &Value) -> bool {
+  match expr {
+    Expr::Exists(operand) => match operand {
+      Operand::Literal(value) => value != &Value::Null && value != &Value::Bool(false),
+      Operand::Current(steps) => !apply(steps, current, root).is_empty(),
+      Operand::Root(steps) => !apply(steps, root, root).is_empty(),
+    },
+    Expr::Compare(left, op, right) => {
+      match (resolve(left, current, root), resolve(right, current, root)) {
+        (Some(left), Some(right)) => compare(left, *op, right),
+        _ => false,
+      }
+    }
+    Expr::Not(expr) => !eval(expr, current, root),
+    Expr::And(left, right) => eval(left, current, root) && eval(right, current, root),
+    Expr::Or(left, right) => eval(left, current, root) || eval(right, current, root),
+  }
+}
+
+/// Resolves an operand of a comparison, which must be a single value.
+fn resolve<'a>(operand: &'a Operand, current: &'a Value, root: &'a Value) -> Option<&'a Value> {
+  let values = match operand {
+    Operand::Literal(value) => return Some(value),
+    Operand::Current(steps) => apply(steps, current, root),
+    Operand::Root(steps) => apply(steps, root, root),
+  };
+  match values.as_slice() {
+    [value] => Some(value),
+    _ => None,
+  }
+}
+
+fn compare(left: &Value, op: Op, right: &Value) -> bool {
+  let ordering = match (left, right) {
+    (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+    (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+    (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+    (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+    (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+    _ => None,
+  };
+  match op {
+    Op::Eq => ordering.map_or(left == right, |o| o == Ordering::Equal),
+    Op::Ne => !ordering.map_or(left == right, |o| o == Ordering::Equal),
+    Op::Lt => ordering == Some(Ordering::Less),
+    Op::Le => matches!(ordering, Some(Ordering::Less) | Some(Ordering::Equal)),
+    Op::Gt => ordering == Some(Ordering::Greater),
+    Op::Ge => matches!(ordering, Some(Ordering::Greater) | Some(Ordering::Equal)),
+  }
+}
@@ -0,0 +1,703 @@
+//! Definition of a hand-written parser which can recover from syntax errors.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use super::lexer::{Lexer, Token, TokenKind};
+use super::parser::{Error, ErrorKind, Limit, ParseOptions};
+use super::value::{BorrowedValue, Value};
+
+/// Value which can be built by `Reader`.
+pub(crate) trait Build<'a>: Sized {
+  type Members: Default;
+  fn null() -> Self;
+  fn bool(b: bool) -> Self;
+  fn integer(i: i64) -> Self;
+  fn float(f: f64) -> Self;
+  fn string(s: Cow<'a, str>) -> Self;
+  fn insert(assoc: &mut Self::Members, name: Cow<'a, str>, value: Self);
+  fn assoc(assoc: Self::Members) -> Self;
+  fn array(array: Vec<Self>) -> Self;
+  fn tuple(tuple: Vec<Self>) -> Self;
+  fn variant(name: Cow<'a, str>, value: Option<Self>) -> Self;
+}
+
+impl<'a> Build<'a> for Value {
+  type Members = HashMap<String, Value>;
+
+  fn null() -> Self {
+    Value::Null
+  }
+
+  fn bool(b: bool) -> Self {
+    Value::Bool(b)
+  }
+
+  fn integer(i: i64) -> Self {
+    Value::Integer(i)
+  }
+
+  fn float(f: f64) -> Self {
+    Value::Float(f)
+  }
+
+  fn string(s: Cow<'a, str>) -> Self {
+    Value::String(s.into_owned())
+  }
+
+  fn insert(assoc: &mut Self::Members, name: Cow<'a, str>, value: Self) {
+    assoc.insert(name.into_owned(), value);
+  }
+
+  fn assoc(assoc: Self::Members) -> Self {
+    Value::Assoc(assoc)
+  }
+
+  fn array(array: Vec<Self>) -> Self {
+    Value::Array(array)
+  }
+
+  fn tuple(tuple: Vec<Self>) -> Self {
+    Value::Tuple(tuple)
+  }
+
+  fn variant(name: Cow<'a, str>, value: Option<Self>) -> Self {
+    Value::Variant((name.into_owned(), value.map(Box::new)))
+  }
+}
+
+impl<'a> Build<'a> for BorrowedValue<'a> {
+  type Members = HashMap<Cow<'a, str>, BorrowedValue<'a>>;
+
+  fn null() -> Self {
+    BorrowedValue::Null
+  }
+
+  fn bool(b: bool) -> Self {
+    BorrowedValue::Bool(b)
+  }
+
+  fn integer(i: i64) -> Self {
+    BorrowedValue::Integer(i)
+  }
+
+  fn float(f: f64) -> Self {
+    BorrowedValue::Float(f)
+  }
+
+  fn string(s: Cow<'a, str>) -> Self {
+    BorrowedValue::String(s)
+  }
+
+  fn insert(assoc: &mut Self::Members, name: Cow<'a, str>, value: Self) {
+    assoc.insert(name, value);
+  }
+
+  fn assoc(assoc: Self::Members) -> Self {
+    BorrowedValue::Assoc(assoc)
+  }
+
+  fn array(array: Vec<Self>) -> Self {
+    BorrowedValue::Array(array)
+  }
+
+  fn tuple(tuple: Vec<Self>) -> Self {
+    BorrowedValue::Tuple(tuple)
+  }
+
+  fn variant(name: Cow<'a, str>, value: Option<Self>) -> Self {
+    BorrowedValue::Variant((name, value.map(Box::new)))
+  }
+}
+
+/// Marker that parsing has stopped at an error.
+pub(crate) struct Stop;
+
+type Result<T> = std::result::Result<T, Stop>;
+
+/// Parser over the tokens of a text.
+///
+/// In strict mode the first error stops parsing. In recovery mode every error is recorded,
+/// a placeholder `Null` is used for a broken value and parsing resumes at the next
+/// `,`, `}`, `]`, `)` or `>`.
+pub(crate) struct Reader<'a> {
+  text: &'a str,
+  options: ParseOptions,
+  lexer: Lexer<'a>,
+  peeked: Option<Token>,
+  recover: bool,
+  /// Whether errors are suppressed until the next token, to avoid a cascade of errors.
+  panicking: bool,
+  /// Closing brackets of the values being parsed.
+  closers: Vec<TokenKind>,
+  depth: usize,
+  nodes: usize,
+  pub(crate) errors: Vec<Error>,
+}
+
+impl<'a> Reader<'a> {
+  pub(crate) fn new(text: &'a str, options: &ParseOptions, recover: bool) -> Self {
+    Reader {
+      text,
+      options: options.clone(),
+      lexer: Lexer::new(text),
+      peeked: None,
+      recover,
+      panicking: false,
+      closers: Vec::new(),
+      depth: 0,
+      nodes: 0,
+      errors: Vec::new(),
+    }
+  }
+
+  fn error(&mut self, kind: ErrorKind, offset: usize) -> Result<()> {
+    if !self.panicking {
+      self.errors.push(Error::new(kind, self.text, offset));
+      self.panicking = true;
+    }
+    if self.recover {
+      Ok(())
+    } else {
+      Err(Stop)
+    }
+  }
+
+  /// Reports an exceeded limit, which stops parsing even in recovery mode.
+  fn limit(&mut self, limit: Limit, actual: Option<usize>, offset: usize) -> Result<()> {
+    match actual {
+      Some(actual) if actual > limit.get(&self.options).unwrap_or(usize::MAX) => {
+        self.errors.push(Error::new(
+          ErrorKind::LimitExceeded(limit),
+          self.text,
+          offset,
+        ));
+        Err(Stop)
+      }
+      _ => Ok(()),
+    }
+  }
+
+  fn expected(&mut self, expected: &'static str, token: Token) -> Result<()> {
+    let found = match token.kind {
+      TokenKind::Eof => "end of input".to_string(),
+      _ => self.text[token.start..token.end].chars().take(16).collect(),
+    };
+    self.error(ErrorKind::Expected { expected, found }, token.start)
+  }
+
+  fn peek(&mut self) -> Result<Token> {
+    if let Some(token) = self.peeked {
+      return Ok(token);
+    }
+    loop {
+      match self.lexer.next() {
+        Some(token) if token.kind.is_trivia() => (),
+        Some(token) if token.kind == TokenKind::UnterminatedComment => {
+          self.error(ErrorKind::UnterminatedComment, token.start)?
+        }
+        Some(token) => {
+          self.peeked = Some(token);
+          return Ok(token);
+        }
+        None => {
+          let len = self.text.len();
+          let token = Token {
+            kind: TokenKind::Eof,
+            start: len,
+            end: len,
+          };
+          self.peeked = Some(token);
+          return Ok(token);
+        }
+      }
+    }
+  }
+
+  fn next(&mut self) -> Result<Token> {
+    let token = self.peek()?;
+    if token.kind != TokenKind::Eof {
+      self.peeked = None;
+    }
+    self.panicking = false;
+    Ok(token)
+  }
+
+  /// Skips tokens until a separator or a closing bracket which is not nested.
+  fn sync(&mut self) -> Result<()> {
+    let mut depth = 0;
+    loop {
+      let token = self.peek()?;
+      match token.kind {
+        TokenKind::Eof => return Ok(()),
+        TokenKind::LBrace | TokenKind::LBracket | TokenKind::LParen | TokenKind::LAngle => {
+          depth += 1
+        }
+        TokenKind::RBrace | TokenKind::RBracket | TokenKind::RParen | TokenKind::RAngle
+          if depth > 0 =>
+        {
+          depth -= 1
+        }
+        TokenKind::Comma
+        | TokenKind::RBrace
+        | TokenKind::RBracket
+        | TokenKind::RParen
+        | TokenKind::RAngle
+          if depth == 0 =>
+        {
+          return Ok(())
+        }
+        _ => (),
+      }
+      self.peeked = None;
+    }
+  }
+
+  /// Parses a whole document, which is an assoc.
+  pub(crate) fn document<V: Build<'a>>(&mut self) -> Result<V> {
+    if self.text.starts_with('\u{feff}') {
+      if !self.options.allow_bom {
+        self.error(ErrorKind::ByteOrderMark, 0)?;
+      }
+      self.next()?;
+    }
+    let token = self.peek()?;
+    if token.kind != TokenKind::LBrace {
+      self.expected("'{'", token)?;
+    }
+    let value = self.value()?;
+    let token = self.peek()?;
+    if token.kind != TokenKind::Eof {
+      self.expected("end of input", token)?;
+    }
+    Ok(value)
+  }
+
+  /// Parses a single value of any kind, which is the whole text.
+  pub(crate) fn single_value<V: Build<'a>>(&mut self) -> Result<V> {
+    let value = self.value()?;
+    let token = self.peek()?;
+    if token.kind != TokenKind::Eof {
+      self.expected("end of input", token)?;
+    }
+    Ok(value)
+  }
+
+  /// Parses a sequence of documents, each of which is an assoc.
+  pub(crate) fn stream<V: Build<'a>>(&mut self) -> Result<Vec<V>> {
+    if self.text.starts_with('\u{feff}') {
+      if !self.options.allow_bom {
+        self.error(ErrorKind::ByteOrderMark, 0)?;
+      }
+      self.next()?;
+    }
+    let mut values = Vec::new();
+    loop {
+      let token = self.peek()?;
+      match token.kind {
+        TokenKind::Eof => return Ok(values),
+        TokenKind::LBrace => values.push(self.value()?),
+        _ => {
+          self.expected("'{' or end of input", token)?;
+          self.next()?;
+        }
+      }
+    }
+  }
+
+  fn value<V: Build<'a>>(&mut self) -> Result<V> {
+    let token = self.peek()?;
+    match token.kind {
+      TokenKind::Comma
+      | TokenKind::Colon
+      | TokenKind::RBrace
+      | TokenKind::RBracket
+      | TokenKind::RParen
+      | TokenKind::RAngle
+      | TokenKind::Eof => {
+        self.expected("value", token)?;
+        return Ok(V::null());
+      }
+      _ => (),
+    }
+    self.next()?;
+    self.nodes += 1;
+    self.limit(Limit::Nodes, Some(self.nodes), token.start)?;
+    let nested = matches!(
+      token.kind,
+      TokenKind::LBrace | TokenKind::LBracket | TokenKind::LParen | TokenKind::LAngle
+    );
+    if nested {
+      self.depth += 1;
+      self.limit(Limit::Depth, Some(self.depth), token.start)?;
+    }
+    let value = self.compound_or_scalar(token);
+    if nested {
+      self.depth -= 1;
+    }
+    value
+  }
+
+  fn compound_or_scalar<V: Build<'a>>(&mut self, token: Token) -> Result<V> {
+    match token.kind {
+      TokenKind::LBrace => {
+        let mut assoc = V::Members::default();
+        let mut members = 0;
+        self.seq(TokenKind::RBrace, "',' or '}'", &mut |reader| {
+          members += 1;
+          let offset = reader.peek()?.start;
+          reader.limit(Limit::AssocMembers, Some(members), offset)?;
+          let (name, value) = reader.member()?;
+          V::insert(&mut assoc, name, value);
+          Ok(())
+        })?;
+        Ok(V::assoc(assoc))
+      }
+      TokenKind::LBracket => {
+        let mut array = Vec::new();
+        self.seq(TokenKind::RBracket, "',' or ']'", &mut |reader| {
+          array.push(reader.value()?);
+          Ok(())
+        })?;
+        Ok(V::array(array))
+      }
+      TokenKind::LParen => {
+        let mut tuple = Vec::new();
+        if self.peek()?.kind == TokenKind::RParen {
+          let token = self.peek()?;
+          self.expected("value", token)?;
+        }
+        self.seq(TokenKind::RParen, "',' or ')'", &mut |reader| {
+          tuple.push(reader.value()?);
+          Ok(())
+        })?;
+        Ok(V::tuple(tuple))
+      }
+      TokenKind::LAngle => self.variant(),
+      TokenKind::String | TokenKind::UnterminatedString => Ok(V::string(self.string(token)?)),
+      TokenKind::Number => self.number(token),
+      TokenKind::Ident => match &self.text[token.start..token.end] {
+        "null" => Ok(V::null()),
+        "true" => Ok(V::bool(true)),
+        "false" => Ok(V::bool(false)),
+        "NaN" => Ok(V::float(f64::NAN)),
+        "Infinity" => Ok(V::float(f64::INFINITY)),
+        _ => {
+          self.expected("value", token)?;
+          Ok(V::null())
+        }
+      },
+      _ => {
+        self.expected("value", token)?;
+        Ok(V::null())
+      }
+    }
+  }
+
+  /// Parses the elements of an assoc, an array or a tuple after the opening bracket.
+  fn seq(
+    &mut self,
+    close: TokenKind,
+    expected: &'static str,
+    element: &mut dyn FnMut(&mut Self) -> Result<()>,
+  ) -> Result<()> {
+    if self.peek()?.kind == close {
+      self.next()?;
+      return Ok(());
+    }
+    self.closers.push(close);
+    let result = self.elements(close, expected, element);
+    self.closers.pop();
+    result
+  }
+
+  fn elements(
+    &mut self,
+    close: TokenKind,
+    expected: &'static str,
+    element: &mut dyn FnMut(&mut Self) -> Result<()>,
+  ) -> Result<()> {
+    loop {
+      element(self)?;
+      let token = self.peek()?;
+      if token.kind == TokenKind::Comma {
+        self.next()?;
+        if self.peek()?.kind == close {
+          if !self.options.trailing_commas {
+            self.error(ErrorKind::TrailingComma, token.start)?;
+          }
+          self.next()?;
+          return Ok(());
+        }
+        continue;
+      }
+      if token.kind == close {
+        self.next()?;
+        return Ok(());
+      }
+      self.expected(expected, token)?;
+      let starts_element = match close {
+        TokenKind::RBrace => matches!(token.kind, TokenKind::String | TokenKind::Ident),
+        _ => !is_closer(token.kind) && token.kind != TokenKind::Comma,
+      };
+      if starts_element {
+        continue;
+      }
+      self.sync()?;
+      let token = self.peek()?;
+      if token.kind == TokenKind::Comma {
+        self.next()?;
+        continue;
+      }
+      if token.kind != close && (token.kind == TokenKind::Eof || self.closers.contains(&token.kind))
+      {
+        // The closing bracket belongs to an enclosing value.
+        return Ok(());
+      }
+      // A closing bracket which matches nothing is taken as a mistyped one.
+      self.next()?;
+      return Ok(());
+    }
+  }
+
+  fn member<V: Build<'a>>(&mut self) -> Result<(Cow<'a, str>, V)> {
+    let name = self.name()?;
+    let token = self.peek()?;
+    if token.kind == TokenKind::Colon {
+      self.next()?;
+    } else {
+      self.error(ErrorKind::MissingColon, token.start)?;
+    }
+    let value = self.value()?;
+    Ok((name, value))
+  }
+
+  /// Parses a quoted or an unquoted name. An unquoted name may be empty.
+  fn name(&mut self) -> Result<Cow<'a, str>> {
+    let token = self.peek()?;
+    match token.kind {
+      TokenKind::String | TokenKind::UnterminatedString => {
+        self.next()?;
+        self.string(token)
+      }
+      TokenKind::Ident => {
+        self.next()?;
+        let text = self.text;
+        let name = &text[token.start..token.end];
+        self.limit(Limit::StringLength, Some(name.len()), token.start)?;
+        Ok(Cow::Borrowed(name))
+      }
+      TokenKind::Colon | TokenKind::RAngle => Ok(Cow::Borrowed("")),
+      _ => {
+        self.expected("name", token)?;
+        Ok(Cow::Borrowed(""))
+      }
+    }
+  }
+
+  fn variant<V: Build<'a>>(&mut self) -> Result<V> {
+    let name = self.name()?;
+    let token = self.peek()?;
+    let value = match token.kind {
+      TokenKind::RAngle => None,
+      TokenKind::Colon => {
+        self.next()?;
+        Some(self.value()?)
+      }
+      _ => {
+        self.expected("':' or '>'", token)?;
+        self.sync()?;
+        None
+      }
+    };
+    let token = self.peek()?;
+    if token.kind == TokenKind::RAngle {
+      self.next()?;
+    } else {
+      self.expected("'>'", token)?;
+    }
+    Ok(V::variant(name, value))
+  }
+
+  fn string(&mut self, token: Token) -> Result<Cow<'a, str>> {
+    let single_quoted = self.text.as_bytes()[token.start] == b'\'';
+    if single_quoted && !self.options.single_quotes {
+      self.error(ErrorKind::SingleQuotedString, token.start)?;
+    }
+    let text = self.text;
+    let inner = if token.kind == TokenKind::String {
+      &text[token.start + 1..token.end - 1]
+    } else {
+      self.error(ErrorKind::UnterminatedString, token.start)?;
+      &text[token.start + 1..token.end]
+    };
+    if !inner.contains('\\') {
+      self.limit(Limit::StringLength, Some(inner.len()), token.start)?;
+      return Ok(Cow::Borrowed(inner));
+    }
+    let mut s = String::with_capacity(inner.len());
+    let mut chars = inner.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+      if c != '\\' {
+        s.push(c);
+        continue;
+      }
+      let offset = token.start + 1 + i;
+      match chars.next().map(|(_, c)| c) {
+        Some('"') => s.push('"'),
+        Some('\'') if single_quoted => s.push('\''),
+        Some('\\') => s.push('\\'),
+        Some('/') => s.push('/'),
+        Some('b') => s.push('\u{0008}'), // Backspace
+        Some('f') => s.push('\u{000c}'), // Form Feed
+        Some('n') => s.push('\n'),
+        Some('r') => s.push('\r'),
+        Some('t') => s.push('\t'),
+        Some('u') => match hex4(&inner[i + 2..]) {
+          Some(high @ 0xd800..=0xdbff) => {
+            let low = if inner[i + 6..].starts_with("\\u") {
+              hex4(&inner[i + 8..]).filter(|low| (0xdc00..=0xdfff).contains(low))
+            } else {
+              None
+            };
+            match low {
+              Some(low) => {
+                let code = 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00);
+                s.push(std::char::from_u32(code).unwrap());
+                chars.nth(9);
+              }
+              None => {
+                s.push('\u{fffd}');
+                chars.nth(3);
+              }
+            }
+          }
+          Some(code) => {
+            s.push(std::char::from_u32(code).unwrap_or('\u{fffd}'));
+            chars.nth(3);
+          }
+          None => {
+            let escape = inner[i..].chars().take(6).collect();
+            self.error(ErrorKind::InvalidEscape(escape), offset)?;
+          }
+        },
+        c => {
+          let escape = c.map_or("\\".to_string(), |c| format!("\\{}", c));
+          self.error(ErrorKind::InvalidEscape(escape), offset)?;
+          s.extend(c);
+        }
+      }
+    }
+    self.limit(Limit::StringLength, Some(s.len()), token.start)?;
+    Ok(Cow::Owned(s))
+  }
+
+  fn number<V: Build<'a>>(&mut self, token: Token) -> Result<V> {
+    let str = &self.text[token.start..token.end];
+    let (negative, body) = match str.as_bytes()[0] {
+      b'-' => (true, &str[1..]),
+      b'+' if self.options.leading_plus => (false, &str[1..]),
+      _ => (false, str),
+    };
+    let value = if body == "Infinity" {
+      Some(V::float(if negative {
+        f64::NEG_INFINITY
+      } else {
+        f64::INFINITY
+      }))
+    } else if let Some(radix) = self.radix(body) {
+      u64::from_str_radix(&body[2..], radix)
+        .ok()
+        .and_then(|i| {
+          if negative {
+            0i64.checked_sub_unsigned(i)
+          } else {
+            i64::try_from(i).ok()
+          }
+        })
+        .map(V::integer)
+    } else {
+      let digits = match (negative, body.strip_prefix('.')) {
+        (_, Some(fraction)) if self.options.leading_decimal_point => {
+          let sign = if negative { "-" } else { "" };
+          Cow::Owned(format!("{}0.{}", sign, fraction))
+        }
+        (true, _) => Cow::Borrowed(str),
+        (false, _) => Cow::Borrowed(body),
+      };
+      match number_kind(&digits) {
+        Some(false) => digits.parse().ok().map(V::integer),
+        Some(true) => digits.parse().ok().map(V::float),
+        None => None,
+      }
+    };
+    match value {
+      Some(value) => Ok(value),
+      None => {
+        self.error(ErrorKind::InvalidNumber(str.to_string()), token.start)?;
+        Ok(V::null())
+      }
+    }
+  }
+
+  /// Returns the radix of a hexadecimal or binary literal allowed by the options.
+  fn radix(&self, body: &str) -> Option<u32> {
+    match body.get(..2) {
+      Some("0x") | Some("0X") if self.options.hex_numbers => Some(16),
+      Some("0b") | Some("0B") if self.options.binary_numbers => Some(2),
+      _ => None,
+    }
+  }
+}
+
+fn is_closer(kind: TokenKind) -> bool {
+  matches!(
+    kind,
+    TokenKind::Colon
+      | TokenKind::RBrace
+      | TokenKind::RBracket
+      | TokenKind::RParen
+      | TokenKind::RAngle
+      | TokenKind::Eof
+  )
+}
+
+/// Reads four hexadecimal digits.
+fn hex4(str: &str) -> Option<u32> {
+  let hex = str.get(..4)?;
+  if hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+    u32::from_str_radix(hex, 16).ok()
+  } else {
+    None
+  }
+}
+
+/// Checks a number literal against the grammar, and returns whether it is a float.
+fn number_kind(str: &str) -> Option<bool> {
+  let str = str.strip_prefix('-').unwrap_or(str);
+  let digits = str.bytes().take_while(u8::is_ascii_digit).count();
+  if digits == 0 || (digits > 1 && str.starts_with('0')) {
+    return None;
+  }
+  let rest = &str[digits..];
+  if rest.is_empty() {
+    return Some(false);
+  }
+  let rest = rest.strip_prefix('.')?;
+  let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+  if digits == 0 {
+    return None;
+  }
+  let rest = &rest[digits..];
+  if rest.is_empty() {
+    return Some(true);
+  }
+  let rest = rest.strip_prefix(|c| c == 'e' || c == 'E')?;
+  let rest = rest.strip_prefix(|c| c == '-' || c == '+').unwrap_or(rest);
+  if !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()) {
+    Some(true)
+  } else {
+    None
+  }
+}
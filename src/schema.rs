@@ -0,0 +1,645 @@
+//! Definition of schema validation of Yojson values.
+//!
+//! A schema is written as a Yojson value, in a subset of JSON Schema with Yojson extensions:
+//! - `type`: a name or an array of names among `"null"`, `"boolean"`, `"integer"`, `"number"`,
+//!   `"string"`, `"object"` (or `"assoc"`), `"array"`, `"tuple"` and `"variant"`.
+//!   `"number"` accepts integers too.
+//! - `enum` and `const`: the allowed values.
+//! - `minimum`, `maximum`, `minLength` and `maxLength`.
+//! - `properties`, `required` and `additionalProperties` for assocs.
+//! - `items`, `minItems` and `maxItems` for arrays.
+//! - `prefixItems` for tuples: the schemas of the elements, which fix the length of the tuple.
+//! - `constructors` for variants: an assoc from the allowed constructors to the schemas of their
+//!   arguments, where `null` means the constructor takes no argument.
+//! - `anyOf`, `allOf` and `not`.
+//! - `definitions` at the root, referred to by `{"$ref" : "#/definitions/Name"}`.
+//!
+//! `true` accepts any value and `false` rejects any value.
+//!
+//! ```
+//! use yojson_rs::schema::Schema;
+//! let schema = Schema::from_value(&yojson_rs::parser::parse(r#"{
+//!   type : "object",
+//!   properties : {
+//!     port : {type : "integer", minimum : 1},
+//!     pos : {type : "tuple", prefixItems : [{type : "number"}, {type : "number"}]},
+//!     mode : {type : "variant", constructors : {Dev : null, Prod : {type : "string"}}}
+//!   },
+//!   required : ["port"]
+//! }"#).unwrap()).unwrap();
+//! let value = yojson_rs::parser::parse("{port : 0, pos : (1, 2, 3), mode : <Dev: 1>}").unwrap();
+//! assert_eq!(schema.validate(&value).len(), 3);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::diff;
+use super::pointer;
+use super::value::Value;
+
+/// Error of a schema definition.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct SchemaError {
+  path: String,
+  message: String,
+}
+
+impl SchemaError {
+  pub(crate) fn new(path: &str, message: impl Into<String>) -> Self {
+    SchemaError {
+      path: path.to_string(),
+      message: message.into(),
+    }
+  }
+
+  /// JSON pointer of the invalid part of the schema.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Description of the error.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl fmt::Display for SchemaError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "invalid schema at \"{}\": {}", self.path, self.message)
+  }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Violation of a schema by a value.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Violation {
+  /// JSON pointer of the value.
+  pub path: String,
+  /// Description of the violation.
+  pub message: String,
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.path.is_empty() {
+      write!(f, "(root): {}", self.message)
+    } else {
+      write!(f, "{}: {}", self.path, self.message)
+    }
+  }
+}
+
+/// Type of a value in a schema.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Type {
+  Null,
+  Boolean,
+  Integer,
+  Number,
+  String,
+  Object,
+  Array,
+  Tuple,
+  Variant,
+}
+
+impl Type {
+  fn from_name(name: &str) -> Option<Type> {
+    match name {
+      "null" => Some(Type::Null),
+      "boolean" => Some(Type::Boolean),
+      "integer" => Some(Type::Integer),
+      "number" => Some(Type::Number),
+      "string" => Some(Type::String),
+      "object" | "assoc" => Some(Type::Object),
+      "array" => Some(Type::Array),
+      "tuple" => Some(Type::Tuple),
+      "variant" => Some(Type::Variant),
+      _ => None,
+    }
+  }
+
+  fn name(self) -> &'static str {
+    match self {
+      Type::Null => "null",
+      Type::Boolean => "boolean",
+      Type::Integer => "integer",
+      Type::Number => "number",
+      Type::String => "string",
+      Type::Object => "object",
+      Type::Array => "array",
+      Type::Tuple => "tuple",
+      Type::Variant => "variant",
+    }
+  }
+
+  fn accepts(self, value: &Value) -> bool {
+    matches!(
+      (self, value),
+      (Type::Null, Value::Null)
+        | (Type::Boolean, Value::Bool(_))
+        | (Type::Integer, Value::Integer(_))
+        | (Type::Number, Value::Integer(_))
+        | (Type::Number, Value::Float(_))
+        | (Type::String, Value::String(_))
+        | (Type::Object, Value::Assoc(_))
+        | (Type::Array, Value::Array(_))
+        | (Type::Tuple, Value::Tuple(_))
+        | (Type::Variant, Value::Variant(_))
+    )
+  }
+}
+
+/// Members of an assoc which are not listed in `properties`.
+#[derive(Clone, Debug)]
+pub(crate) enum Additional {
+  Allowed,
+  Forbidden,
+  Schema(Box<Node>),
+}
+
+/// Compiled schema of a value.
+#[derive(Clone, Debug)]
+pub(crate) struct Node {
+  pub(crate) always: Option<bool>,
+  pub(crate) types: Option<Vec<Type>>,
+  pub(crate) enumeration: Option<Vec<Value>>,
+  pub(crate) minimum: Option<f64>,
+  pub(crate) maximum: Option<f64>,
+  pub(crate) min_length: Option<usize>,
+  pub(crate) max_length: Option<usize>,
+  pub(crate) properties: Vec<(String, Node)>,
+  pub(crate) required: Vec<String>,
+  pub(crate) additional: Additional,
+  pub(crate) items: Option<Box<Node>>,
+  pub(crate) min_items: Option<usize>,
+  pub(crate) max_items: Option<usize>,
+  pub(crate) prefix_items: Option<Vec<Node>>,
+  pub(crate) constructors: Option<Vec<(String, Option<Node>)>>,
+  pub(crate) any_of: Vec<Node>,
+  pub(crate) all_of: Vec<Node>,
+  pub(crate) not: Option<Box<Node>>,
+  pub(crate) reference: Option<String>,
+}
+
+impl Default for Node {
+  fn default() -> Self {
+    Node {
+      always: None,
+      types: None,
+      enumeration: None,
+      minimum: None,
+      maximum: None,
+      min_length: None,
+      max_length: None,
+      properties: Vec::new(),
+      required: Vec::new(),
+      additional: Additional::Allowed,
+      items: None,
+      min_items: None,
+      max_items: None,
+      prefix_items: None,
+      constructors: None,
+      any_of: Vec::new(),
+      all_of: Vec::new(),
+      not: None,
+      reference: None,
+    }
+  }
+}
+
+/// Compiled schema.
+#[derive(Clone, Debug)]
+pub struct Schema {
+  pub(crate) root: Node,
+  pub(crate) definitions: HashMap<String, Node>,
+}
+
+const DEFINITIONS: &str = "#/definitions/";
+
+fn compile(value: &Value, path: &str) -> Result<Node, SchemaError> {
+  let assoc = match value {
+    Value::Bool(b) => {
+      return Ok(Node {
+        always: Some(*b),
+        ..Node::default()
+      })
+    }
+    Value::Assoc(assoc) => assoc,
+    _ => {
+      return Err(SchemaError::new(
+        path,
+        "a schema must be an assoc or a boolean",
+      ))
+    }
+  };
+  let child = |token: &str| format!("{}/{}", path, pointer::escape(token));
+  let size = |name: &str| match assoc.get(name) {
+    None => Ok(None),
+    Some(Value::Integer(i)) if *i >= 0 => Ok(Some(*i as usize)),
+    Some(_) => Err(SchemaError::new(
+      &child(name),
+      "expected a non-negative integer",
+    )),
+  };
+  let number = |name: &str| match assoc.get(name) {
+    None => Ok(None),
+    Some(Value::Integer(i)) => Ok(Some(*i as f64)),
+    Some(Value::Float(f)) => Ok(Some(*f)),
+    Some(_) => Err(SchemaError::new(&child(name), "expected a number")),
+  };
+  let schemas = |name: &str| match assoc.get(name) {
+    None => Ok(Vec::new()),
+    Some(Value::Array(array)) => array
+      .iter()
+      .enumerate()
+      .map(|(i, v)| compile(v, &format!("{}/{}", child(name), i)))
+      .collect(),
+    Some(_) => Err(SchemaError::new(
+      &child(name),
+      "expected an array of schemas",
+    )),
+  };
+  let mut node = Node::default();
+  for (name, v) in assoc {
+    match name.as_str() {
+      "type" => {
+        let names = match v {
+          Value::String(s) => vec![s.as_str()],
+          Value::Array(array) => {
+            let mut names = Vec::new();
+            for (i, v) in array.iter().enumerate() {
+              match v.as_str() {
+                Some(s) => names.push(s),
+                None => {
+                  let path = format!("{}/{}", child(name), i);
+                  return Err(SchemaError::new(&path, "expected a string"));
+                }
+              }
+            }
+            names
+          }
+          _ => Vec::new(),
+        };
+        let types = names
+          .iter()
+          .map(|s| Type::from_name(s))
+          .collect::<Option<Vec<_>>>();
+        match types {
+          Some(types) if !types.is_empty() => node.types = Some(types),
+          _ => return Err(SchemaError::new(&child(name), "unknown type")),
+        }
+      }
+      "enum" => match v {
+        Value::Array(array) => node.enumeration = Some(array.clone()),
+        _ => return Err(SchemaError::new(&child(name), "expected an array")),
+      },
+      "const" => node.enumeration = Some(vec![v.clone()]),
+      "properties" => match v {
+        Value::Assoc(properties) => {
+          let path = child(name);
+          let mut properties = properties
+            .iter()
+            .map(|(k, v)| {
+              Ok((
+                k.clone(),
+                compile(v, &format!("{}/{}", path, pointer::escape(k)))?,
+              ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+          properties.sort_by(|a, b| a.0.cmp(&b.0));
+          node.properties = properties;
+        }
+        _ => return Err(SchemaError::new(&child(name), "expected an assoc")),
+      },
+      "required" => match v {
+        Value::Array(array) if array.iter().all(|v| v.is_str()) => {
+          node.required = array
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(String::from)
+            .collect()
+        }
+        _ => {
+          return Err(SchemaError::new(
+            &child(name),
+            "expected an array of strings",
+          ))
+        }
+      },
+      "additionalProperties" => {
+        node.additional = match v {
+          Value::Bool(true) => Additional::Allowed,
+          Value::Bool(false) => Additional::Forbidden,
+          v => Additional::Schema(Box::new(compile(v, &child(name))?)),
+        }
+      }
+      "items" => node.items = Some(Box::new(compile(v, &child(name))?)),
+      "prefixItems" => node.prefix_items = Some(schemas(name)?),
+      "constructors" => match v {
+        Value::Assoc(constructors) => {
+          let path = child(name);
+          let mut constructors = constructors
+            .iter()
+            .map(|(k, v)| {
+              let schema = match v {
+                Value::Null => None,
+                v => Some(compile(v, &format!("{}/{}", path, pointer::escape(k)))?),
+              };
+              Ok((k.clone(), schema))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+          constructors.sort_by(|a, b| a.0.cmp(&b.0));
+          node.constructors = Some(constructors);
+        }
+        _ => return Err(SchemaError::new(&child(name), "expected an assoc")),
+      },
+      "anyOf" => node.any_of = schemas(name)?,
+      "allOf" => node.all_of = schemas(name)?,
+      "not" => node.not = Some(Box::new(compile(v, &child(name))?)),
+      "$ref" => match v.as_str().and_then(|s| s.strip_prefix(DEFINITIONS)) {
+        Some(reference) => node.reference = Some(reference.to_string()),
+        None => {
+          return Err(SchemaError::new(
+            &child(name),
+            "expected a reference \"#/definitions/Name\"",
+          ))
+        }
+      },
+      _ => (),
+    }
+  }
+  node.minimum = number("minimum")?;
+  node.maximum = number("maximum")?;
+  node.min_length = size("minLength")?;
+  node.max_length = size("maxLength")?;
+  node.min_items = size("minItems")?;
+  node.max_items = size("maxItems")?;
+  Ok(node)
+}
+
+/// Collects the references of a node and its descendants.
+fn references<'a>(node: &'a Node, refs: &mut Vec<&'a str>) {
+  refs.extend(node.reference.as_deref());
+  let children = node
+    .properties
+    .iter()
+    .map(|(_, n)| n)
+    .chain(node.items.as_deref())
+    .chain(node.prefix_items.iter().flatten())
+    .chain(
+      node
+        .constructors
+        .iter()
+        .flatten()
+        .filter_map(|(_, n)| n.as_ref()),
+    )
+    .chain(&node.any_of)
+    .chain(&node.all_of)
+    .chain(node.not.as_deref());
+  for child in children {
+    references(child, refs)
+  }
+  if let Additional::Schema(node) = &node.additional {
+    references(node, refs)
+  }
+}
+
+fn push(out: &mut Vec<Violation>, path: &str, message: String) {
+  out.push(Violation {
+    path: path.to_string(),
+    message,
+  })
+}
+
+impl Schema {
+  /// Compiles a schema written as a Yojson value.
+  pub fn from_value(value: &Value) -> Result<Schema, SchemaError> {
+    let root = compile(value, "")?;
+    let mut definitions = HashMap::new();
+    if let Some(defs) = value.as_assoc().and_then(|a| a.get("definitions")) {
+      match defs {
+        Value::Assoc(defs) => {
+          for (name, def) in defs {
+            let path = format!("/definitions/{}", pointer::escape(name));
+            definitions.insert(name.clone(), compile(def, &path)?);
+          }
+        }
+        _ => return Err(SchemaError::new("/definitions", "expected an assoc")),
+      }
+    }
+    let schema = Schema { root, definitions };
+    schema.check_references()?;
+    Ok(schema)
+  }
+
+  pub(crate) fn check_references(&self) -> Result<(), SchemaError> {
+    let mut refs = Vec::new();
+    references(&self.root, &mut refs);
+    for def in self.definitions.values() {
+      references(def, &mut refs)
+    }
+    match refs.iter().find(|r| !self.definitions.contains_key(**r)) {
+      Some(r) => Err(SchemaError::new(
+        "",
+        format!("undefined reference \"{}{}\"", DEFINITIONS, r),
+      )),
+      None => Ok(()),
+    }
+  }
+
+  /// Validates a value and returns every violation.
+  pub fn validate(&self, value: &Value) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    self.check(&self.root, value, "", 0, &mut violations);
+    violations
+  }
+
+  /// Tests whether a value satisfies the schema.
+  pub fn is_valid(&self, value: &Value) -> bool {
+    self.validate(value).is_empty()
+  }
+
+  fn check(&self, node: &Node, value: &Value, path: &str, hops: usize, out: &mut Vec<Violation>) {
+    if let Some(reference) = &node.reference {
+      // A chain of references without descending into the value is a cycle.
+      if hops > self.definitions.len() {
+        push(
+          out,
+          path,
+          format!("circular reference \"{}{}\"", DEFINITIONS, reference),
+        );
+        return;
+      }
+      self.check(&self.definitions[reference], value, path, hops + 1, out);
+    }
+    if node.always == Some(false) {
+      push(out, path, "no value is allowed".to_string());
+      return;
+    }
+    if let Some(types) = &node.types {
+      if !types.iter().any(|t| t.accepts(value)) {
+        let names = types.iter().map(|t| t.name()).collect::<Vec<_>>();
+        push(
+          out,
+          path,
+          format!(
+            "expected {}, found {}",
+            names.join(" or "),
+            value.type_str()
+          ),
+        );
+        return;
+      }
+    }
+    if let Some(values) = &node.enumeration {
+      if !values.iter().any(|v| diff::diff(v, value).is_empty()) {
+        push(
+          out,
+          path,
+          "value is not one of the allowed values".to_string(),
+        );
+      }
+    }
+    let number = match value {
+      Value::Integer(i) => Some(*i as f64),
+      Value::Float(f) => Some(*f),
+      _ => None,
+    };
+    if let Some(number) = number {
+      if let Some(minimum) = node.minimum.filter(|m| number < *m) {
+        push(
+          out,
+          path,
+          format!("value is less than the minimum {}", minimum),
+        );
+      }
+      if let Some(maximum) = node.maximum.filter(|m| number > *m) {
+        push(
+          out,
+          path,
+          format!("value is greater than the maximum {}", maximum),
+        );
+      }
+    }
+    if let Value::String(s) = value {
+      let len = s.chars().count();
+      if let Some(min) = node.min_length.filter(|m| len < *m) {
+        push(
+          out,
+          path,
+          format!("string is shorter than {} characters", min),
+        );
+      }
+      if let Some(max) = node.max_length.filter(|m| len > *m) {
+        push(
+          out,
+          path,
+          format!("string is longer than {} characters", max),
+        );
+      }
+    }
+    let child = |token: &str| format!("{}/{}", path, pointer::escape(token));
+    match value {
+      Value::Assoc(assoc) => {
+        for name in &node.required {
+          if !assoc.contains_key(name) {
+            push(out, path, format!("missing member \"{}\"", name));
+          }
+        }
+        let mut members = assoc.iter().collect::<Vec<_>>();
+        members.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, v) in members {
+          match node.properties.iter().find(|(n, _)| n == name) {
+            Some((_, schema)) => self.check(schema, v, &child(name), 0, out),
+            None => match &node.additional {
+              Additional::Allowed => (),
+              Additional::Forbidden => {
+                push(out, &child(name), format!("unexpected member \"{}\"", name))
+              }
+              Additional::Schema(schema) => self.check(schema, v, &child(name), 0, out),
+            },
+          }
+        }
+      }
+      Value::Array(array) => {
+        if let Some(min) = node.min_items.filter(|m| array.len() < *m) {
+          push(out, path, format!("array has fewer than {} elements", min));
+        }
+        if let Some(max) = node.max_items.filter(|m| array.len() > *m) {
+          push(out, path, format!("array has more than {} elements", max));
+        }
+        if let Some(items) = &node.items {
+          for (i, v) in array.iter().enumerate() {
+            self.check(items, v, &child(&i.to_string()), 0, out)
+          }
+        }
+      }
+      Value::Tuple(tuple) => {
+        if let Some(schemas) = &node.prefix_items {
+          if tuple.len() != schemas.len() {
+            push(
+              out,
+              path,
+              format!(
+                "expected a tuple of {} elements, found {}",
+                schemas.len(),
+                tuple.len()
+              ),
+            );
+          }
+          for (i, (schema, v)) in schemas.iter().zip(tuple).enumerate() {
+            self.check(schema, v, &child(&i.to_string()), 0, out)
+          }
+        }
+      }
+      Value::Variant((name, payload)) => {
+        if let Some(constructors) = &node.constructors {
+          match constructors.iter().find(|(n, _)| n == name) {
+            None => push(
+              out,
+              path,
+              format!("constructor \"{}\" is not allowed", name),
+            ),
+            Some((_, Some(schema))) => match payload {
+              Some(payload) => self.check(schema, payload, &child(pointer::PAYLOAD), 0, out),
+              None => push(
+                out,
+                path,
+                format!("constructor \"{}\" requires an argument", name),
+              ),
+            },
+            Some((_, None)) if payload.is_some() => push(
+              out,
+              path,
+              format!("constructor \"{}\" takes no argument", name),
+            ),
+            Some((_, None)) => (),
+          }
+        }
+      }
+      _ => (),
+    }
+    for schema in &node.all_of {
+      self.check(schema, value, path, hops, out)
+    }
+    if !node.any_of.is_empty()
+      && !node.any_of.iter().any(|schema| {
+        let mut violations = Vec::new();
+        self.check(schema, value, path, hops, &mut violations);
+        violations.is_empty()
+      })
+    {
+      push(out, path, "value matches none of the schemas".to_string());
+    }
+    if let Some(schema) = &node.not {
+      let mut violations = Vec::new();
+      self.check(schema, value, path, hops, &mut violations);
+      if violations.is_empty() {
+        push(out, path, "value matches a forbidden schema".to_string());
+      }
+    }
+  }
+}
@@ -0,0 +1,125 @@
+//! Definition of the conversion between Yojson values and `toml` values.
+//!
+//! This module is enabled by the feature `toml`.
+//!
+//! TOML has neither null nor tuples nor variants, so Yojson values are converted as follows:
+//! - `null` is dropped from assocs and arrays.
+//! - Assocs become tables.
+//! - Tuples become arrays.
+//! - Variants become values as the externally tagged enums of serde: `<Point>` becomes the
+//!   string `"Point"`, and `<Circle: 1.5>` becomes the table `{ Circle = 1.5 }`.
+//! - Non-finite floats become `nan`, `inf` and `-inf`.
+//!
+//! TOML values are converted back without guessing tuples or variants, and datetimes become
+//! strings, such as `"1979-05-27T07:32:00Z"`.
+//!
+//! Everything which is not read back as the same value is reported as a `loss::Loss`.
+
+use super::loss::{child, Loss, Lossy};
+use super::value::Value;
+
+fn to_toml_at(value: &Value, path: &str, losses: &mut Vec<Loss>) -> Option<::toml::Value> {
+  Some(match value {
+    Value::Null => {
+      losses.push(Loss::new(path, "null is dropped"));
+      return None;
+    }
+    Value::Bool(b) => ::toml::Value::Boolean(*b),
+    Value::Integer(i) => ::toml::Value::Integer(*i),
+    Value::Float(f) => ::toml::Value::Float(*f),
+    Value::String(s) => ::toml::Value::String(s.clone()),
+    Value::Assoc(assoc) => {
+      let mut table = ::toml::Table::new();
+      for (name, value) in assoc {
+        if let Some(value) = to_toml_at(value, &child(path, name), losses) {
+          table.insert(name.clone(), value);
+        }
+      }
+      ::toml::Value::Table(table)
+    }
+    Value::Array(elements) | Value::Tuple(elements) => {
+      if let Value::Tuple(_) = value {
+        losses.push(Loss::new(path, "tuple is read back as an array"));
+      }
+      ::toml::Value::Array(
+        elements
+          .iter()
+          .enumerate()
+          .filter_map(|(i, value)| to_toml_at(value, &child(path, &i.to_string()), losses))
+          .collect(),
+      )
+    }
+    Value::Variant((name, argument)) => {
+      let argument = argument
+        .as_ref()
+        .and_then(|argument| to_toml_at(argument, &child(path, super::pointer::PAYLOAD), losses));
+      match argument {
+        Some(argument) => {
+          losses.push(Loss::new(
+            path,
+            format!("variant {} is read back as a table", name),
+          ));
+          let mut table = ::toml::Table::new();
+          table.insert(name.clone(), argument);
+          ::toml::Value::Table(table)
+        }
+        None => {
+          losses.push(Loss::new(
+            path,
+            format!("variant {} is read back as a string", name),
+          ));
+          ::toml::Value::String(name.clone())
+        }
+      }
+    }
+  })
+}
+
+/// Converts a Yojson value to a `toml` value.
+///
+/// `null` at the top level becomes an empty table.
+///
+/// ```
+/// let value = yojson_rs::parser::parse("{name : \"app\", port : 8080}").unwrap();
+/// let toml = yojson_rs::toml::to_toml(&value).into_value();
+/// assert_eq!(toml::to_string(&toml).unwrap(), "name = \"app\"\nport = 8080\n");
+/// ```
+pub fn to_toml(value: &Value) -> Lossy<::toml::Value> {
+  let mut losses = Vec::new();
+  let toml = to_toml_at(value, "", &mut losses)
+    .unwrap_or_else(|| ::toml::Value::Table(::toml::Table::new()));
+  Lossy::new(toml, losses)
+}
+
+fn from_toml_at(toml: &::toml::Value, path: &str, losses: &mut Vec<Loss>) -> Value {
+  match toml {
+    ::toml::Value::Boolean(b) => Value::Bool(*b),
+    ::toml::Value::Integer(i) => Value::Integer(*i),
+    ::toml::Value::Float(f) => Value::Float(*f),
+    ::toml::Value::String(s) => Value::String(s.clone()),
+    ::toml::Value::Datetime(datetime) => {
+      losses.push(Loss::new(path, "datetime is read as a string"));
+      Value::String(datetime.to_string())
+    }
+    ::toml::Value::Array(elements) => Value::Array(
+      elements
+        .iter()
+        .enumerate()
+        .map(|(i, toml)| from_toml_at(toml, &child(path, &i.to_string()), losses))
+        .collect(),
+    ),
+    ::toml::Value::Table(table) => Value::Assoc(
+      table
+        .iter()
+        .map(|(name, toml)| (name.clone(), from_toml_at(toml, &child(path, name), losses)))
+        .collect(),
+    ),
+  }
+}
+
+/// Converts a `toml` value to a Yojson value.
+pub fn from_toml(toml: &::toml::Value) -> Lossy<Value> {
+  let mut losses = Vec::new();
+  let value = from_toml_at(toml, "", &mut losses);
+  Lossy::new(value, losses)
+}
@@ -1,6 +1,7 @@
 //! Definition of a Yojson value
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::mem::discriminant;
 
@@ -198,3 +199,142 @@ impl Value {
     }
   }
 }
+
+/// Representation of a Yojson value which borrows strings from the parsed text.
+///
+/// Strings and names without escape sequences are borrowed; the others are owned.
+#[derive(PartialEq, Clone, Debug)]
+pub enum BorrowedValue<'a> {
+  /// JSON null
+  Null,
+  /// JSON boolean
+  Bool(bool),
+  /// JSON number without decimal point or exponent.
+  Integer(i64),
+  /// JSON number, Infinity, -Infinity or NaN.
+  Float(f64),
+  /// JSON string.
+  String(Cow<'a, str>),
+  /// JSON object.
+  Assoc(HashMap<Cow<'a, str>, BorrowedValue<'a>>),
+  /// JSON array
+  Array(Vec<BorrowedValue<'a>>),
+  /// Tuple (non-standard extension of JSON). Syntax: `("abc", 123)`.
+  Tuple(Vec<BorrowedValue<'a>>),
+  /// Variant (non-standard extension of JSON). Syntax: `<"Foo">` or `<"Bar":123>`.
+  Variant((Cow<'a, str>, Option<Box<BorrowedValue<'a>>>)),
+}
+
+impl<'a> BorrowedValue<'a> {
+  /// Converts to a value which owns all its strings.
+  pub fn into_owned(self) -> Value {
+    match self {
+      BorrowedValue::Null => Value::Null,
+      BorrowedValue::Bool(b) => Value::Bool(b),
+      BorrowedValue::Integer(i) => Value::Integer(i),
+      BorrowedValue::Float(f) => Value::Float(f),
+      BorrowedValue::String(s) => Value::String(s.into_owned()),
+      BorrowedValue::Assoc(assoc) => Value::Assoc(
+        assoc
+          .into_iter()
+          .map(|(name, value)| (name.into_owned(), value.into_owned()))
+          .collect(),
+      ),
+      BorrowedValue::Array(array) => {
+        Value::Array(array.into_iter().map(BorrowedValue::into_owned).collect())
+      }
+      BorrowedValue::Tuple(tuple) => {
+        Value::Tuple(tuple.into_iter().map(BorrowedValue::into_owned).collect())
+      }
+      BorrowedValue::Variant((name, value)) => Value::Variant((
+        name.into_owned(),
+        value.map(|value| Box::new(value.into_owned())),
+      )),
+    }
+  }
+
+  /// Extracts the string of this value if it is a string.
+  pub fn as_str(&self) -> Option<&str> {
+    match *self {
+      BorrowedValue::String(ref s) => Some(s),
+      _ => None,
+    }
+  }
+
+  /// Looks up a member if this value is an assoc.
+  pub fn get(&self, name: &str) -> Option<&BorrowedValue<'a>> {
+    match *self {
+      BorrowedValue::Assoc(ref assoc) => assoc.get(name),
+      _ => None,
+    }
+  }
+
+  /// Returns a human-readable representation of the type of this value.
+  pub fn type_str(&self) -> &'static str {
+    match *self {
+      BorrowedValue::Null => "null",
+      BorrowedValue::String(..) => "string",
+      BorrowedValue::Integer(..) => "integer",
+      BorrowedValue::Float(..) => "float",
+      BorrowedValue::Bool(..) => "boolean",
+      BorrowedValue::Array(..) => "array",
+      BorrowedValue::Assoc(..) => "assoc",
+      BorrowedValue::Tuple(..) => "tuple",
+      BorrowedValue::Variant(..) => "variant",
+    }
+  }
+}
+
+impl<'a> From<BorrowedValue<'a>> for Value {
+  fn from(value: BorrowedValue<'a>) -> Self {
+    value.into_owned()
+  }
+}
+
+impl From<bool> for Value {
+  fn from(b: bool) -> Self {
+    Value::Bool(b)
+  }
+}
+
+impl From<i32> for Value {
+  fn from(i: i32) -> Self {
+    Value::Integer(i as i64)
+  }
+}
+
+impl From<i64> for Value {
+  fn from(i: i64) -> Self {
+    Value::Integer(i)
+  }
+}
+
+impl From<f64> for Value {
+  fn from(f: f64) -> Self {
+    Value::Float(f)
+  }
+}
+
+impl From<&str> for Value {
+  fn from(s: &str) -> Self {
+    Value::String(s.to_string())
+  }
+}
+
+impl From<String> for Value {
+  fn from(s: String) -> Self {
+    Value::String(s)
+  }
+}
+
+impl From<Array> for Value {
+  fn from(array: Array) -> Self {
+    Value::Array(array)
+  }
+}
+
+impl From<Assoc> for Value {
+  fn from(assoc: Assoc) -> Self {
+    Value::Assoc(assoc)
+  }
+}
@@ -0,0 +1,280 @@
+//! Definition of visitors, which traverse every nested value of a value.
+//!
+//! `walk` and `walk_mut` visit a value and its nested values, parents first, with the JSON
+//! pointer of each value. Members of an assoc are visited in the order of their names.
+//! For each value, the visitor is called with `visit_value`, then with the method for the kind
+//! of the value, such as `visit_float`. All methods do nothing by default, and each returns a
+//! `Walk` which continues the traversal, skips the nested values, or stops the traversal.
+//!
+//! ```
+//! use yojson_rs::visit::{walk, Visitor, Walk};
+//! use yojson_rs::value::Value;
+//!
+//! struct Constructors(Vec<String>);
+//!
+//! impl Visitor for Constructors {
+//!   fn visit_variant(&mut self, _: &str, name: &str, _: Option<&Value>) -> Walk {
+//!     self.0.push(name.to_string());
+//!     Walk::Continue
+//!   }
+//! }
+//!
+//! let value = yojson_rs::parser::parse("{a : <A: [<B>]>, b : (1, <C>)}").unwrap();
+//! let mut constructors = Constructors(Vec::new());
+//! walk(&value, &mut constructors);
+//! assert_eq!(constructors.0, vec!["A", "B", "C"]);
+//! ```
+
+use super::pointer;
+use super::value::{Assoc, Value, Variant};
+
+/// How a traversal goes on after a value is visited.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Walk {
+  /// Visits the nested values.
+  Continue,
+  /// Skips the nested values, and goes on with the next value.
+  Skip,
+  /// Stops the traversal.
+  Stop,
+}
+
+/// Visitor of values.
+pub trait Visitor {
+  /// Visits any value, before the method for its kind.
+  fn visit_value(&mut self, _path: &str, _value: &Value) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits `null`.
+  fn visit_null(&mut self, _path: &str) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a boolean.
+  fn visit_bool(&mut self, _path: &str, _b: bool) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits an integer.
+  fn visit_integer(&mut self, _path: &str, _i: i64) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a float.
+  fn visit_float(&mut self, _path: &str, _f: f64) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a string.
+  fn visit_string(&mut self, _path: &str, _s: &str) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits an assoc, before its members.
+  fn visit_assoc(&mut self, _path: &str, _assoc: &Assoc) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits an array, before its elements.
+  fn visit_array(&mut self, _path: &str, _elements: &[Value]) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a tuple, before its elements.
+  fn visit_tuple(&mut self, _path: &str, _elements: &[Value]) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a variant, before its argument.
+  fn visit_variant(&mut self, _path: &str, _name: &str, _argument: Option<&Value>) -> Walk {
+    Walk::Continue
+  }
+}
+
+/// Visitor of values which can modify them.
+///
+/// A value replaced in `visit_value` is visited by the method for the kind of the new value,
+/// and its nested values are visited as replaced.
+pub trait VisitorMut {
+  /// Visits any value, before the method for its kind.
+  fn visit_value(&mut self, _path: &str, _value: &mut Value) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits `null`.
+  fn visit_null(&mut self, _path: &str) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a boolean.
+  fn visit_bool(&mut self, _path: &str, _b: &mut bool) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits an integer.
+  fn visit_integer(&mut self, _path: &str, _i: &mut i64) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a float.
+  fn visit_float(&mut self, _path: &str, _f: &mut f64) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a string.
+  fn visit_string(&mut self, _path: &str, _s: &mut String) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits an assoc, before its members.
+  fn visit_assoc(&mut self, _path: &str, _assoc: &mut Assoc) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits an array, before its elements.
+  fn visit_array(&mut self, _path: &str, _elements: &mut Vec<Value>) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a tuple, before its elements.
+  fn visit_tuple(&mut self, _path: &str, _elements: &mut Vec<Value>) -> Walk {
+    Walk::Continue
+  }
+
+  /// Visits a variant, before its argument.
+  fn visit_variant(&mut self, _path: &str, _variant: &mut Variant) -> Walk {
+    Walk::Continue
+  }
+}
+
+fn child(path: &str, token: &str) -> String {
+  format!("{}/{}", path, pointer::escape(token))
+}
+
+/// Pushes the nested values of a value onto a stack, so that they are popped in order.
+fn push_children<'a>(stack: &mut Vec<(String, &'a Value)>, path: &str, value: &'a Value) {
+  match value {
+    Value::Assoc(assoc) => {
+      let mut members = assoc.iter().collect::<Vec<_>>();
+      members.sort_by(|a, b| b.0.cmp(a.0));
+      for (name, value) in members {
+        stack.push((child(path, name), value));
+      }
+    }
+    Value::Array(elements) | Value::Tuple(elements) => {
+      for (i, value) in elements.iter().enumerate().rev() {
+        stack.push((child(path, &i.to_string()), value));
+      }
+    }
+    Value::Variant((_, Some(argument))) => stack.push((child(path, pointer::PAYLOAD), argument)),
+    _ => (),
+  }
+}
+
+/// Visits a value and its nested values.
+///
+/// Returns `Walk::Stop` if the visitor stopped the traversal, and `Walk::Continue` otherwise.
+pub fn walk<V: Visitor + ?Sized>(value: &Value, visitor: &mut V) -> Walk {
+  // Nested values are visited with an explicit stack, so that deep values do not overflow the
+  // call stack.
+  let mut stack = vec![(String::new(), value)];
+  while let Some((path, value)) = stack.pop() {
+    let mut walk = visitor.visit_value(&path, value);
+    if walk == Walk::Continue {
+      walk = match value {
+        Value::Null => visitor.visit_null(&path),
+        Value::Bool(b) => visitor.visit_bool(&path, *b),
+        Value::Integer(i) => visitor.visit_integer(&path, *i),
+        Value::Float(f) => visitor.visit_float(&path, *f),
+        Value::String(s) => visitor.visit_string(&path, s),
+        Value::Assoc(assoc) => visitor.visit_assoc(&path, assoc),
+        Value::Array(elements) => visitor.visit_array(&path, elements),
+        Value::Tuple(elements) => visitor.visit_tuple(&path, elements),
+        Value::Variant((name, argument)) => visitor.visit_variant(&path, name, argument.as_deref()),
+      };
+    }
+    match walk {
+      Walk::Continue => push_children(&mut stack, &path, value),
+      Walk::Skip => (),
+      Walk::Stop => return Walk::Stop,
+    }
+  }
+  Walk::Continue
+}
+
+/// Visits a value and its nested values, which the visitor can modify.
+///
+/// Returns `Walk::Stop` if the visitor stopped the traversal, and `Walk::Continue` otherwise.
+pub fn walk_mut<V: VisitorMut + ?Sized>(value: &mut Value, visitor: &mut V) -> Walk {
+  let mut stack = vec![(String::new(), value)];
+  while let Some((path, value)) = stack.pop() {
+    let mut walk = visitor.visit_value(&path, value);
+    if walk == Walk::Continue {
+      walk = match value {
+        Value::Null => visitor.visit_null(&path),
+        Value::Bool(b) => visitor.visit_bool(&path, b),
+        Value::Integer(i) => visitor.visit_integer(&path, i),
+        Value::Float(f) => visitor.visit_float(&path, f),
+        Value::String(s) => visitor.visit_string(&path, s),
+        Value::Assoc(assoc) => visitor.visit_assoc(&path, assoc),
+        Value::Array(elements) => visitor.visit_array(&path, elements),
+        Value::Tuple(elements) => visitor.visit_tuple(&path, elements),
+        Value::Variant(variant) => visitor.visit_variant(&path, variant),
+      };
+    }
+    match walk {
+      Walk::Continue => (),
+      Walk::Skip => continue,
+      Walk::Stop => return Walk::Stop,
+    }
+    match value {
+      Value::Assoc(assoc) => {
+        let mut members = assoc.iter_mut().collect::<Vec<_>>();
+        members.sort_by(|a, b| b.0.cmp(a.0));
+        for (name, value) in members {
+          stack.push((child(&path, name), value));
+        }
+      }
+      Value::Array(elements) | Value::Tuple(elements) => {
+        for (i, value) in elements.iter_mut().enumerate().rev() {
+          stack.push((child(&path, &i.to_string()), value));
+        }
+      }
+      Value::Variant((_, Some(argument))) => stack.push((child(&path, pointer::PAYLOAD), argument)),
+      _ => (),
+    }
+  }
+  Walk::Continue
+}
+
+/// Iterator over the nested values of a value, returned by `Value::descendants`.
+pub struct Descendants<'a> {
+  stack: Vec<(String, &'a Value)>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+  type Item = (String, &'a Value);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (path, value) = self.stack.pop()?;
+    push_children(&mut self.stack, &path, value);
+    Some((path, value))
+  }
+}
+
+impl Value {
+  /// Returns an iterator over the nested values of this value, excluding this value, with
+  /// their JSON pointers. Values are returned in the order of `walk`.
+  ///
+  /// ```
+  /// let value = yojson_rs::parser::parse("{a : [1, 2]}").unwrap();
+  /// let paths = value.descendants().map(|(path, _)| path).collect::<Vec<_>>();
+  /// assert_eq!(paths, vec!["/a", "/a/0", "/a/1"]);
+  /// ```
+  pub fn descendants(&self) -> Descendants<'_> {
+    let mut stack = Vec::new();
+    push_children(&mut stack, "", self);
+    Descendants { stack }
+  }
+}
@@ -0,0 +1,236 @@
+//! Definition of the conversion between Yojson values and `serde_yaml` values.
+//!
+//! This module is enabled by the feature `serde_yaml`.
+//!
+//! Yojson values are converted to YAML as follows:
+//! - Assocs become mappings with string keys.
+//! - Tuples become sequences tagged `!tuple`, such as `!tuple [1, 2]`.
+//! - Variants become values tagged with the constructor, such as `!Circle 1.5`. A variant
+//!   without argument is tagged `null`, such as `!Point null`. A constructor which is empty or
+//!   starts with `!` is not a valid tag, so only its argument is written.
+//! - The tuple argument of a variant becomes an untagged sequence, such as `!Rect [1.0, 2.0]`
+//!   for `<Rect: (1.0, 2.0)>`, since `serde_yaml` cannot write a tagged value directly inside
+//!   another one. An argument which is a variant, an array or a tuple of one element is
+//!   wrapped in a sequence of one element instead, such as `!Some [!None null]`.
+//! - Non-finite floats become `.nan`, `.inf` and `-.inf`.
+//!
+//! YAML values are converted back with the same mapping, so `!Point null` becomes `<Point>`
+//! and `<Point: null>` is lost. Keys which are not strings are written as strings, or dropped
+//! if they are sequences or mappings, and integers beyond the range of `i64` become floats.
+//!
+//! Everything which is not read back as the same value is reported as a `loss::Loss`.
+
+use std::collections::HashMap;
+
+use super::loss::{child, Loss, Lossy};
+use super::value::Value;
+use serde_yaml::value::{Tag, TaggedValue};
+
+/// Tag of tuples.
+pub const TUPLE: &str = "tuple";
+
+fn to_yaml_at(value: &Value, path: &str, losses: &mut Vec<Loss>) -> serde_yaml::Value {
+  match value {
+    Value::Null => serde_yaml::Value::Null,
+    Value::Bool(b) => serde_yaml::Value::Bool(*b),
+    Value::Integer(i) => serde_yaml::Value::Number((*i).into()),
+    Value::Float(f) => serde_yaml::Value::Number((*f).into()),
+    Value::String(s) => serde_yaml::Value::String(s.clone()),
+    Value::Assoc(assoc) => {
+      let mut mapping = serde_yaml::Mapping::new();
+      for (name, value) in assoc {
+        let value = to_yaml_at(value, &child(path, name), losses);
+        mapping.insert(serde_yaml::Value::String(name.clone()), value);
+      }
+      serde_yaml::Value::Mapping(mapping)
+    }
+    Value::Array(elements) => serde_yaml::Value::Sequence(
+      elements
+        .iter()
+        .enumerate()
+        .map(|(i, value)| to_yaml_at(value, &child(path, &i.to_string()), losses))
+        .collect(),
+    ),
+    Value::Tuple(elements) => {
+      let elements = elements
+        .iter()
+        .enumerate()
+        .map(|(i, value)| to_yaml_at(value, &child(path, &i.to_string()), losses))
+        .collect();
+      serde_yaml::Value::Tagged(Box::new(TaggedValue {
+        tag: Tag::new(TUPLE),
+        value: serde_yaml::Value::Sequence(elements),
+      }))
+    }
+    Value::Variant((name, argument)) => {
+      let payload = child(path, super::pointer::PAYLOAD);
+      if name.is_empty() || name.starts_with('!') {
+        losses.push(Loss::new(
+          path,
+          format!("variant {:?} is not a valid tag", name),
+        ));
+        return match argument {
+          Some(argument) => to_yaml_at(argument, &payload, losses),
+          None => serde_yaml::Value::Null,
+        };
+      }
+      if name == TUPLE {
+        losses.push(Loss::new(path, "variant tuple is read back as a tuple"));
+      }
+      let value = match argument.as_deref() {
+        None => serde_yaml::Value::Null,
+        Some(Value::Null) => {
+          losses.push(Loss::new(
+            path,
+            format!(
+              "variant {} with the argument null is read back without argument",
+              name
+            ),
+          ));
+          serde_yaml::Value::Null
+        }
+        Some(Value::Tuple(elements)) if elements.len() != 1 => serde_yaml::Value::Sequence(
+          elements
+            .iter()
+            .enumerate()
+            .map(|(i, value)| to_yaml_at(value, &child(&payload, &i.to_string()), losses))
+            .collect(),
+        ),
+        Some(argument @ Value::Tuple(_))
+        | Some(argument @ Value::Array(_))
+        | Some(argument @ Value::Variant(_)) => {
+          serde_yaml::Value::Sequence(vec![to_yaml_at(argument, &payload, losses)])
+        }
+        Some(argument) => to_yaml_at(argument, &payload, losses),
+      };
+      serde_yaml::Value::Tagged(Box::new(TaggedValue {
+        tag: Tag::new(name.clone()),
+        value,
+      }))
+    }
+  }
+}
+
+/// Converts a Yojson value to a `serde_yaml` value.
+///
+/// ```
+/// let value = yojson_rs::parser::parse("{shape : <Circle: 1.5>, size : (1, 2)}").unwrap();
+/// let yaml = yojson_rs::yaml::to_yaml(&value).into_value();
+/// let expected: serde_yaml::Value = serde_yaml::from_str("{shape: !Circle 1.5, size: !tuple [1, 2]}").unwrap();
+/// assert_eq!(yaml, expected);
+/// ```
+pub fn to_yaml(value: &Value) -> Lossy<serde_yaml::Value> {
+  let mut losses = Vec::new();
+  let yaml = to_yaml_at(value, "", &mut losses);
+  Lossy::new(yaml, losses)
+}
+
+fn key_to_string(key: &serde_yaml::Value) -> Option<String> {
+  match key {
+    serde_yaml::Value::Null => Some("null".to_string()),
+    serde_yaml::Value::Bool(b) => Some(b.to_string()),
+    serde_yaml::Value::Number(n) => Some(n.to_string()),
+    serde_yaml::Value::String(s) => Some(s.clone()),
+    serde_yaml::Value::Tagged(tagged) => key_to_string(&tagged.value),
+    serde_yaml::Value::Sequence(_) | serde_yaml::Value::Mapping(_) => None,
+  }
+}
+
+fn from_yaml_at(yaml: &serde_yaml::Value, path: &str, losses: &mut Vec<Loss>) -> Value {
+  match yaml {
+    serde_yaml::Value::Null => Value::Null,
+    serde_yaml::Value::Bool(b) => Value::Bool(*b),
+    serde_yaml::Value::Number(n) => match n.as_i64() {
+      Some(i) => Value::Integer(i),
+      None => {
+        if !n.is_f64() {
+          losses.push(Loss::new(
+            path,
+            format!(
+              "integer {} is out of the range of i64 and read as a float",
+              n
+            ),
+          ));
+        }
+        Value::Float(n.as_f64().unwrap())
+      }
+    },
+    serde_yaml::Value::String(s) => Value::String(s.clone()),
+    serde_yaml::Value::Sequence(elements) => Value::Array(
+      elements
+        .iter()
+        .enumerate()
+        .map(|(i, yaml)| from_yaml_at(yaml, &child(path, &i.to_string()), losses))
+        .collect(),
+    ),
+    serde_yaml::Value::Mapping(mapping) => {
+      let mut assoc = HashMap::new();
+      for (key, yaml) in mapping {
+        let name = match (key_to_string(key), key) {
+          (Some(name), serde_yaml::Value::String(_)) => name,
+          (Some(name), _) => {
+            losses.push(Loss::new(
+              &child(path, &name),
+              format!("key {} is read as a string", name),
+            ));
+            name
+          }
+          (None, _) => {
+            losses.push(Loss::new(
+              path,
+              "member with a sequence or a mapping as key is dropped",
+            ));
+            continue;
+          }
+        };
+        let path = child(path, &name);
+        let value = from_yaml_at(yaml, &path, losses);
+        if assoc.insert(name.clone(), value).is_some() {
+          losses.push(Loss::new(
+            &path,
+            format!("duplicate member {} replaces the previous one", name),
+          ));
+        }
+      }
+      Value::Assoc(assoc)
+    }
+    serde_yaml::Value::Tagged(tagged) => {
+      let name = tagged.tag.to_string()[1..].to_string();
+      match &tagged.value {
+        serde_yaml::Value::Sequence(elements) if name == TUPLE => Value::Tuple(
+          elements
+            .iter()
+            .enumerate()
+            .map(|(i, yaml)| from_yaml_at(yaml, &child(path, &i.to_string()), losses))
+            .collect(),
+        ),
+        serde_yaml::Value::Null => Value::Variant((name, None)),
+        serde_yaml::Value::Sequence(elements) => {
+          let payload = child(path, super::pointer::PAYLOAD);
+          let argument = match elements.as_slice() {
+            [yaml] => from_yaml_at(yaml, &payload, losses),
+            elements => Value::Tuple(
+              elements
+                .iter()
+                .enumerate()
+                .map(|(i, yaml)| from_yaml_at(yaml, &child(&payload, &i.to_string()), losses))
+                .collect(),
+            ),
+          };
+          Value::Variant((name, Some(Box::new(argument))))
+        }
+        yaml => {
+          let argument = from_yaml_at(yaml, &child(path, super::pointer::PAYLOAD), losses);
+          Value::Variant((name, Some(Box::new(argument))))
+        }
+      }
+    }
+  }
+}
+
+/// Converts a `serde_yaml` value to a Yojson value.
+pub fn from_yaml(yaml: &serde_yaml::Value) -> Lossy<Value> {
+  let mut losses = Vec::new();
+  let value = from_yaml_at(yaml, "", &mut losses);
+  Lossy::new(value, losses)
+}
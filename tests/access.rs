@@ -0,0 +1,87 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::access::AccessError;
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_get_path() {
+    let config =
+      parse("{servers : [{host : \"a\", port : 80}], state : <Active: (1, \"x\")>}").unwrap();
+    assert_eq!(
+      config.get_path(&["servers", "0", "port"]),
+      Ok(&Value::Integer(80))
+    );
+    assert_eq!(
+      config.get_path(&["state", "-payload", "1"]),
+      Ok(&Value::String("x".to_string()))
+    );
+    assert_eq!(
+      config.get_path(&["servers", "1", "port"]),
+      Err(AccessError::Missing("/servers/1".to_string()))
+    );
+    let error = config
+      .get_path(&["servers", "0", "host", "name"])
+      .unwrap_err();
+    assert!(!error.is_missing());
+    assert_eq!(
+      error.to_string(),
+      "cannot apply \"name\" to a value of type string at \"/servers/0/host\""
+    );
+  }
+
+  #[test]
+  fn check_get_or() {
+    let config =
+      parse("{timeout : 30, ratio : 1, debug : null, name : \"app\", retries : 2.5}").unwrap();
+    assert_eq!(config.get_i64_or("timeout", 10), Ok(30));
+    assert_eq!(config.get_i64_or("delay", 10), Ok(10));
+    assert_eq!(config.get_f64_or("ratio", 0.5), Ok(1.0));
+    assert_eq!(config.get_bool_or("debug", true), Ok(true));
+    assert_eq!(config.get_str_or("name", "default"), Ok("app"));
+    assert_eq!(
+      config.get_str_or(&["server", "name"], "default"),
+      Ok("default")
+    );
+    let error = config.get_i64_or("retries", 3).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "expected integer, found float at \"/retries\""
+    );
+    assert_eq!(
+      config
+        .get_str_or("timeout", "default")
+        .unwrap_err()
+        .to_string(),
+      "expected string, found integer at \"/timeout\""
+    );
+  }
+
+  #[test]
+  fn check_get_as() {
+    let config =
+      parse("{server : {ports : [80, \"443\"], hosts : [\"a\", \"b\"], size : (1, 2.0)}}").unwrap();
+    assert_eq!(
+      config.get_as::<Vec<String>, _>(&["server", "hosts"]),
+      Ok(vec!["a".to_string(), "b".to_string()])
+    );
+    assert_eq!(
+      config.get_as::<(f64, f64), _>(&["server", "size"]),
+      Ok((1.0, 2.0))
+    );
+    let path = vec!["server".to_string(), "ports".to_string()];
+    match config.get_as::<Vec<i64>, _>(&path) {
+      Err(AccessError::WrongType(error)) => {
+        assert_eq!(error.path(), "/server/ports/1");
+        assert_eq!(error.message(), "expected integer, found string");
+      }
+      result => panic!("unexpected result {:?}", result),
+    }
+    assert!(config
+      .get_as::<Option<i64>, _>(&["server", "timeout"])
+      .unwrap_err()
+      .is_missing());
+  }
+}
@@ -0,0 +1,160 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::convert::{FromYojson, ToYojson};
+  use yojson_rs::parser::parse;
+
+  include!("atd/config.rs");
+
+  mod keywords {
+    include!("atd/keywords.rs");
+  }
+
+  const CONFIG: &str = r#"{
+    port : 80,
+    mode : <"Weighted": ("a", 0.5)>,
+    range : (1, 2),
+    environment : {HOME : "/root"},
+    type : null,
+    fallback : <"Some": {
+      port : 8080,
+      host : "localhost",
+      tags : ["x"],
+      retries : 1,
+      mode : ["Prod", "b"],
+      range : [3, 4],
+      environment : {},
+      type : "backup",
+      fallback : "None"
+    }>
+  }"#;
+
+  #[test]
+  fn check_parse() {
+    let module = yojson_rs::atd::parse(include_str!("atd/config.atd")).unwrap();
+    assert_eq!(module.types.len(), 5);
+    assert_eq!(module.get("pair").unwrap().params, vec!["a".to_string()]);
+    let error = yojson_rs::atd::parse("type t = {\n  x : u;\n}").unwrap_err();
+    assert_eq!(error.message(), "type \"u\" is not defined");
+    let error = yojson_rs::atd::parse("type t = { ?x : int }").unwrap_err();
+    assert!(error.message().contains("option"), "{}", error);
+  }
+
+  #[test]
+  fn check_schema() {
+    let module = yojson_rs::atd::parse(include_str!("atd/config.atd")).unwrap();
+    let schema = module.to_schema("config").unwrap();
+    let config = Config::from_yojson(&parse(CONFIG).unwrap()).unwrap();
+    assert!(schema.is_valid(&config.to_yojson()));
+    let violations = schema.validate(&parse(r#"{port : "80", mode : <Dev>}"#).unwrap());
+    let paths = violations
+      .iter()
+      .map(|v| v.path.as_str())
+      .collect::<Vec<_>>();
+    assert!(paths.contains(&"/port"), "{:?}", violations);
+    assert!(paths.contains(&""), "{:?}", violations);
+  }
+
+  #[test]
+  fn check_parametric_schema() {
+    let module = yojson_rs::atd::parse(
+      "type 'a box = { v : 'a }\n\
+       type 'a result = [ Ok of 'a | Error of string ]\n\
+       type t = { b : int box; r : float result }",
+    )
+    .unwrap();
+    let schema = module.to_schema("t").unwrap();
+    assert!(schema.is_valid(&parse("{b : {v : 1}, r : <Ok: 1.5>}").unwrap()));
+    let violations = schema.validate(&parse("{b : {v : \"1\"}, r : <Ok: \"x\">}").unwrap());
+    let paths = violations
+      .iter()
+      .map(|v| v.path.as_str())
+      .collect::<Vec<_>>();
+    assert!(paths.contains(&"/b/v"), "{:?}", violations);
+    assert!(paths.contains(&"/r/-payload"), "{:?}", violations);
+  }
+
+  #[test]
+  fn check_generate() {
+    let module = yojson_rs::atd::parse(include_str!("atd/config.atd")).unwrap();
+    let options = yojson_rs::codegen::CodegenOptions::new();
+    assert_eq!(
+      yojson_rs::codegen::generate(&module, &options).unwrap(),
+      include_str!("atd/config.rs")
+    );
+    let std = yojson_rs::codegen::generate(&module, &options.std_json(true)).unwrap();
+    assert!(std.contains("write_variant(\"Dev\", None, true)"));
+
+    let config = Config::from_yojson(&parse(CONFIG).unwrap()).unwrap();
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.mode, Mode::Weighted("a".to_string(), 0.5));
+    let fallback = config.fallback.as_ref().unwrap();
+    assert_eq!(fallback.host.as_deref(), Some("localhost"));
+    assert_eq!(fallback.range, (3, 4));
+    assert_eq!(fallback.type_.as_deref(), Some("backup"));
+    assert_eq!(Config::from_yojson(&config.to_yojson()), Ok(config));
+
+    let tree = Tree::Node(Box::new(Tree::Leaf(1)), Box::new(Tree::Leaf(2)));
+    assert_eq!(
+      yojson_rs::to_string(tree.to_yojson()),
+      "<Node:(<Leaf:1>,<Leaf:2>)>"
+    );
+
+    let error = Config::from_yojson(&parse(r#"{port : 80, mode : <Prod>}"#).unwrap()).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "unexpected constructor \"Prod\" at \"/mode\""
+    );
+  }
+
+  #[test]
+  fn check_defaults() {
+    let generate = |t: &str, default: &str| {
+      let text = format!("type t = {{ ~x <ocaml default=\"{}\"> : {} }}", default, t);
+      let module = yojson_rs::atd::parse(&text).unwrap();
+      yojson_rs::codegen::generate(&module, &yojson_rs::codegen::CodegenOptions::new())
+    };
+    assert!(generate("int", "(-1)").unwrap().contains("None => -1,"));
+    assert!(generate("float", "1").unwrap().contains("None => 1.0,"));
+    assert!(generate("float", "1e3").unwrap().contains("None => 1e3,"));
+    assert!(generate("unit", "()").unwrap().contains("None => (),"));
+    for (t, default) in [
+      ("int", "1.5"),
+      ("int", "1e3"),
+      ("float", "nan"),
+      ("float", "inf"),
+      ("float", "1.e3"),
+      ("int", "()"),
+      ("string", "[]"),
+    ] {
+      let error = generate(t, default).unwrap_err();
+      assert!(
+        error.message().starts_with("unsupported default value"),
+        "{}",
+        error
+      );
+    }
+  }
+
+  #[test]
+  fn check_generate_keywords() {
+    let module = yojson_rs::atd::parse(include_str!("atd/keywords.atd")).unwrap();
+    let options = yojson_rs::codegen::CodegenOptions::new();
+    assert_eq!(
+      yojson_rs::codegen::generate(&module, &options).unwrap(),
+      include_str!("atd/keywords.rs")
+    );
+    let value = parse(
+      "{self : 1, crate : 2, super : 3, try : 4, yield : 5, box : 6, macro : 7, abstract : 8, \
+       final : 9, override : 10, priv : 11, typeof : 12, unsized : 13, virtual : 14, do : 15, \
+       become : 16, gen : 17, async : 18, state : <Self>}",
+    )
+    .unwrap();
+    let keywords = keywords::Keywords::from_yojson(&value).unwrap();
+    assert_eq!(keywords.self_, 1);
+    assert_eq!(keywords.r#try, 4);
+    assert_eq!(keywords.state, keywords::State::Self_);
+    assert_eq!(keywords.to_yojson(), value);
+  }
+}
@@ -0,0 +1,135 @@
+// This file is generated by yojson_rs::codegen. Do not edit it by hand.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mode {
+  /// Local development.
+  Dev,
+  Prod(String),
+  Weighted(String, f64),
+}
+
+impl ::yojson_rs::convert::ToYojson for Mode {
+  fn to_yojson(&self) -> ::yojson_rs::value::Value {
+    match self {
+      Mode::Dev => ::yojson_rs::convert::write_variant("Dev", None, false),
+      Mode::Prod(x0) => ::yojson_rs::convert::write_variant("Prod", Some(::yojson_rs::convert::ToYojson::to_yojson(x0)), false),
+      Mode::Weighted(x0, x1) => ::yojson_rs::convert::write_variant("Weighted", Some(::yojson_rs::value::Value::Tuple(vec![::yojson_rs::convert::ToYojson::to_yojson(x0), ::yojson_rs::convert::ToYojson::to_yojson(x1)])), false),
+    }
+  }
+}
+
+impl ::yojson_rs::convert::FromYojson for Mode {
+  fn from_yojson(v: &::yojson_rs::value::Value) -> Result<Self, ::yojson_rs::convert::ConvertError> {
+    match ::yojson_rs::convert::read_variant(v)? {
+      ("Dev", None) => Ok(Mode::Dev),
+      ("Prod", Some(v)) => <String as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(Mode::Prod).map_err(|e| e.at("-payload")),
+      ("Weighted", Some(v)) => ::yojson_rs::convert::expect_tuple(v, 2).and_then(|t| Ok(Mode::Weighted({ let v = &t[0]; <String as ::yojson_rs::convert::FromYojson>::from_yojson(v) }.map_err(|e| e.at(0))?, { let v = &t[1]; <f64 as ::yojson_rs::convert::FromYojson>::from_yojson(v) }.map_err(|e| e.at(1))?))).map_err(|e| e.at("-payload")),
+      (name, _) => Err(::yojson_rs::convert::ConvertError::new(format!("unexpected constructor \"{}\"", name))),
+    }
+  }
+}
+
+pub type Pair<A> = (A, A);
+
+pub type Port = i64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+  pub port: Port,
+  pub host: Option<String>,
+  pub tags: Vec<String>,
+  pub retries: i64,
+  pub mode: Mode,
+  pub range: Pair<i64>,
+  pub env: Vec<(String, String)>,
+  pub type_: Option<String>,
+  pub fallback: Option<Box<Config>>,
+}
+
+impl ::yojson_rs::convert::ToYojson for Config {
+  fn to_yojson(&self) -> ::yojson_rs::value::Value {
+    #[allow(unused_mut)]
+    let mut assoc = ::std::collections::HashMap::new();
+    assoc.insert("port".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.port));
+    if let Some(x) = &self.host {
+      assoc.insert("host".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(x));
+    }
+    assoc.insert("tags".to_string(), ::yojson_rs::value::Value::Array(self.tags.iter().map(::yojson_rs::convert::ToYojson::to_yojson).collect()));
+    assoc.insert("retries".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.retries));
+    assoc.insert("mode".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.mode));
+    assoc.insert("range".to_string(), { let (x1_0, x1_1,) = &self.range; ::yojson_rs::value::Value::Tuple(vec![::yojson_rs::convert::ToYojson::to_yojson(x1_0), ::yojson_rs::convert::ToYojson::to_yojson(x1_1)]) });
+    assoc.insert("environment".to_string(), ::yojson_rs::value::Value::Assoc(self.env.iter().map(|(k, x)| (k.clone(), ::yojson_rs::convert::ToYojson::to_yojson(x))).collect()));
+    assoc.insert("type".to_string(), match &self.type_ { None => ::yojson_rs::value::Value::Null, Some(x) => ::yojson_rs::convert::ToYojson::to_yojson(x) });
+    assoc.insert("fallback".to_string(), match &self.fallback { None => ::yojson_rs::convert::write_variant("None", None, false), Some(x) => ::yojson_rs::convert::write_variant("Some", Some(::yojson_rs::convert::ToYojson::to_yojson(x)), false) });
+    ::yojson_rs::value::Value::Assoc(assoc)
+  }
+}
+
+impl ::yojson_rs::convert::FromYojson for Config {
+  fn from_yojson(v: &::yojson_rs::value::Value) -> Result<Self, ::yojson_rs::convert::ConvertError> {
+    let assoc = ::yojson_rs::convert::expect_assoc(v)?;
+    Ok(Config {
+      port: match assoc.get("port") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("port"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("port")),
+      },
+      host: match assoc.get("host") {
+        Some(v) => Some(<String as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("host"))?),
+        None => None,
+      },
+      tags: match assoc.get("tags") {
+        Some(v) => ::yojson_rs::convert::expect_array(v).and_then(|a| a.iter().enumerate().map(|(i, v)| <String as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at(i))).collect::<Result<Vec<_>, _>>()).map_err(|e| e.at("tags"))?,
+        None => Vec::new(),
+      },
+      retries: match assoc.get("retries") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("retries"))?,
+        None => 3,
+      },
+      mode: match assoc.get("mode") {
+        Some(v) => <Mode as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("mode"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("mode")),
+      },
+      range: match assoc.get("range") {
+        Some(v) => ::yojson_rs::convert::expect_tuple(v, 2).and_then(|t| Ok(({ let v = &t[0]; <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v) }.map_err(|e| e.at(0))?, { let v = &t[1]; <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v) }.map_err(|e| e.at(1))?,))).map_err(|e| e.at("range"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("range")),
+      },
+      env: match assoc.get("environment") {
+        Some(v) => ::yojson_rs::convert::expect_assoc(v).and_then(|a| a.iter().map(|(k, v)| <String as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(|x| (k.clone(), x)).map_err(|e| e.at(k))).collect::<Result<Vec<_>, _>>()).map_err(|e| e.at("environment"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("environment")),
+      },
+      type_: match assoc.get("type") {
+        Some(v) => match v { ::yojson_rs::value::Value::Null => Ok(None), v => <String as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(Some) }.map_err(|e| e.at("type"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("type")),
+      },
+      fallback: match assoc.get("fallback") {
+        Some(v) => ::yojson_rs::convert::read_variant(v).and_then(|c| match c { ("None", None) => Ok(None), ("Some", Some(v)) => <Box<Config> as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(Some).map_err(|e| e.at("-payload")), _ => Err(::yojson_rs::convert::ConvertError::expected("option", v)) }).map_err(|e| e.at("fallback"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("fallback")),
+      },
+    })
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tree<A> {
+  Leaf(A),
+  Node(Box<Tree<A>>, Box<Tree<A>>),
+}
+
+impl<A: ::yojson_rs::convert::ToYojson> ::yojson_rs::convert::ToYojson for Tree<A> {
+  fn to_yojson(&self) -> ::yojson_rs::value::Value {
+    match self {
+      Tree::Leaf(x0) => ::yojson_rs::convert::write_variant("Leaf", Some(::yojson_rs::convert::ToYojson::to_yojson(x0)), false),
+      Tree::Node(x0, x1) => ::yojson_rs::convert::write_variant("Node", Some(::yojson_rs::value::Value::Tuple(vec![::yojson_rs::convert::ToYojson::to_yojson(x0), ::yojson_rs::convert::ToYojson::to_yojson(x1)])), false),
+    }
+  }
+}
+
+impl<A: ::yojson_rs::convert::FromYojson> ::yojson_rs::convert::FromYojson for Tree<A> {
+  fn from_yojson(v: &::yojson_rs::value::Value) -> Result<Self, ::yojson_rs::convert::ConvertError> {
+    match ::yojson_rs::convert::read_variant(v)? {
+      ("Leaf", Some(v)) => <A as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(Tree::Leaf).map_err(|e| e.at("-payload")),
+      ("Node", Some(v)) => ::yojson_rs::convert::expect_tuple(v, 2).and_then(|t| Ok(Tree::Node({ let v = &t[0]; <Box<Tree<A>> as ::yojson_rs::convert::FromYojson>::from_yojson(v) }.map_err(|e| e.at(0))?, { let v = &t[1]; <Box<Tree<A>> as ::yojson_rs::convert::FromYojson>::from_yojson(v) }.map_err(|e| e.at(1))?))).map_err(|e| e.at("-payload")),
+      (name, _) => Err(::yojson_rs::convert::ConvertError::new(format!("unexpected constructor \"{}\"", name))),
+    }
+  }
+}
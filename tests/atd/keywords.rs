@@ -0,0 +1,160 @@
+// This file is generated by yojson_rs::codegen. Do not edit it by hand.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keywords {
+  pub self_: i64,
+  pub crate_: i64,
+  pub super_: i64,
+  pub r#try: i64,
+  pub r#yield: i64,
+  pub r#box: i64,
+  pub r#macro: i64,
+  pub r#abstract: i64,
+  pub r#final: i64,
+  pub r#override: i64,
+  pub r#priv: i64,
+  pub r#typeof: i64,
+  pub r#unsized: i64,
+  pub r#virtual: i64,
+  pub r#do: i64,
+  pub r#become: i64,
+  pub r#gen: i64,
+  pub r#async: i64,
+  pub state: State,
+}
+
+impl ::yojson_rs::convert::ToYojson for Keywords {
+  fn to_yojson(&self) -> ::yojson_rs::value::Value {
+    #[allow(unused_mut)]
+    let mut assoc = ::std::collections::HashMap::new();
+    assoc.insert("self".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.self_));
+    assoc.insert("crate".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.crate_));
+    assoc.insert("super".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.super_));
+    assoc.insert("try".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#try));
+    assoc.insert("yield".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#yield));
+    assoc.insert("box".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#box));
+    assoc.insert("macro".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#macro));
+    assoc.insert("abstract".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#abstract));
+    assoc.insert("final".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#final));
+    assoc.insert("override".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#override));
+    assoc.insert("priv".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#priv));
+    assoc.insert("typeof".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#typeof));
+    assoc.insert("unsized".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#unsized));
+    assoc.insert("virtual".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#virtual));
+    assoc.insert("do".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#do));
+    assoc.insert("become".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#become));
+    assoc.insert("gen".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#gen));
+    assoc.insert("async".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.r#async));
+    assoc.insert("state".to_string(), ::yojson_rs::convert::ToYojson::to_yojson(&self.state));
+    ::yojson_rs::value::Value::Assoc(assoc)
+  }
+}
+
+impl ::yojson_rs::convert::FromYojson for Keywords {
+  fn from_yojson(v: &::yojson_rs::value::Value) -> Result<Self, ::yojson_rs::convert::ConvertError> {
+    let assoc = ::yojson_rs::convert::expect_assoc(v)?;
+    Ok(Keywords {
+      self_: match assoc.get("self") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("self"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("self")),
+      },
+      crate_: match assoc.get("crate") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("crate"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("crate")),
+      },
+      super_: match assoc.get("super") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("super"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("super")),
+      },
+      r#try: match assoc.get("try") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("try"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("try")),
+      },
+      r#yield: match assoc.get("yield") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("yield"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("yield")),
+      },
+      r#box: match assoc.get("box") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("box"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("box")),
+      },
+      r#macro: match assoc.get("macro") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("macro"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("macro")),
+      },
+      r#abstract: match assoc.get("abstract") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("abstract"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("abstract")),
+      },
+      r#final: match assoc.get("final") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("final"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("final")),
+      },
+      r#override: match assoc.get("override") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("override"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("override")),
+      },
+      r#priv: match assoc.get("priv") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("priv"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("priv")),
+      },
+      r#typeof: match assoc.get("typeof") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("typeof"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("typeof")),
+      },
+      r#unsized: match assoc.get("unsized") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("unsized"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("unsized")),
+      },
+      r#virtual: match assoc.get("virtual") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("virtual"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("virtual")),
+      },
+      r#do: match assoc.get("do") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("do"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("do")),
+      },
+      r#become: match assoc.get("become") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("become"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("become")),
+      },
+      r#gen: match assoc.get("gen") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("gen"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("gen")),
+      },
+      r#async: match assoc.get("async") {
+        Some(v) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("async"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("async")),
+      },
+      state: match assoc.get("state") {
+        Some(v) => <State as ::yojson_rs::convert::FromYojson>::from_yojson(v).map_err(|e| e.at("state"))?,
+        None => return Err(::yojson_rs::convert::ConvertError::missing("state")),
+      },
+    })
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum State {
+  Self_,
+  Box(i64),
+}
+
+impl ::yojson_rs::convert::ToYojson for State {
+  fn to_yojson(&self) -> ::yojson_rs::value::Value {
+    match self {
+      State::Self_ => ::yojson_rs::convert::write_variant("Self", None, false),
+      State::Box(x0) => ::yojson_rs::convert::write_variant("Box", Some(::yojson_rs::convert::ToYojson::to_yojson(x0)), false),
+    }
+  }
+}
+
+impl ::yojson_rs::convert::FromYojson for State {
+  fn from_yojson(v: &::yojson_rs::value::Value) -> Result<Self, ::yojson_rs::convert::ConvertError> {
+    match ::yojson_rs::convert::read_variant(v)? {
+      ("Self", None) => Ok(State::Self_),
+      ("Box", Some(v)) => <i64 as ::yojson_rs::convert::FromYojson>::from_yojson(v).map(State::Box).map_err(|e| e.at("-payload")),
+      (name, _) => Err(::yojson_rs::convert::ConvertError::new(format!("unexpected constructor \"{}\"", name))),
+    }
+  }
+}
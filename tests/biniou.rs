@@ -0,0 +1,137 @@
+extern crate yojson_rs;
+
+// The fixtures in tests/biniou are written by tests/biniou/fixtures.ml with OCaml Biniou.
+// The committed fixtures were derived by hand from the format specification and have not been
+// regenerated yet; running the script overwrites them, and `git diff` shows any difference.
+// Each comment gives the value in `bdump` notation.
+#[cfg(test)]
+mod tests {
+  use yojson_rs::biniou::{from_biniou, hash_name, to_biniou, BiniouOptions};
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_hash_name() {
+    for line in include_str!("biniou/hashes.txt").lines() {
+      let (name, hash) = line.split_once(' ').unwrap();
+      assert_eq!(hash_name(name), hash.parse::<u32>().unwrap(), "{}", name);
+    }
+  }
+
+  #[test]
+  fn check_encode() {
+    // { #80005bdb "id": 1 }
+    let value = parse("{id : 1}").unwrap();
+    assert_eq!(to_biniou(&value).unwrap(), include_bytes!("biniou/id.bin"));
+    // { #fc1ce321 "shape": <#8aa1e630 "Circle": 1.5> }
+    let value = parse("{shape : <Circle: 1.5>}").unwrap();
+    assert_eq!(
+      to_biniou(&value).unwrap(),
+      include_bytes!("biniou/shape.bin")
+    );
+    // [ 1; -1; 300 ]
+    let value = Value::Array(vec![
+      Value::Integer(1),
+      Value::Integer(-1),
+      Value::Integer(300),
+    ]);
+    assert_eq!(
+      to_biniou(&value).unwrap(),
+      include_bytes!("biniou/svints.bin")
+    );
+    // (1, "a", unit, true, <#4adab5e6 "Active">)
+    let value = Value::Tuple(vec![
+      Value::Integer(1),
+      Value::String("a".to_string()),
+      Value::Null,
+      Value::Bool(true),
+      Value::Variant(("Active".to_string(), None)),
+    ]);
+    assert_eq!(
+      to_biniou(&value).unwrap(),
+      include_bytes!("biniou/tuple.bin")
+    );
+  }
+
+  #[test]
+  fn check_decode() {
+    let options = BiniouOptions::new().names(&["x", "y", "shape", "Circle"]);
+    // { #fc1ce321 "shape": <#8aa1e630 "Circle": 1.5> }
+    assert_eq!(
+      from_biniou(include_bytes!("biniou/shape.bin"), &options),
+      Ok(parse("{shape : <Circle: 1.5>}").unwrap())
+    );
+    // [| { x: 1, y: 2 }; { x: 3, y: 4 } |] as a table
+    assert_eq!(
+      from_biniou(include_bytes!("biniou/table.bin"), &options),
+      Ok(Value::Array(vec![
+        parse("{x : 1, y : 2}").unwrap(),
+        parse("{x : 3, y : 4}").unwrap(),
+      ]))
+    );
+    // (int32 -2, uvint 300, int8 -1, <1: svint 1>, { #80005bdb: unit })
+    let mut assoc = std::collections::HashMap::new();
+    assoc.insert("#00005bdb".to_string(), Value::Null);
+    assert_eq!(
+      from_biniou(include_bytes!("biniou/other_tags.bin"), &options),
+      Ok(Value::Tuple(vec![
+        Value::Integer(-2),
+        Value::Integer(300),
+        Value::Integer(-1),
+        Value::Variant(("#1".to_string(), Some(Box::new(Value::Integer(1))))),
+        Value::Assoc(assoc),
+      ]))
+    );
+  }
+
+  #[test]
+  fn check_errors() {
+    let value = parse("{a : [1, 2.0], b : [null, 3, -4, [], {c : <D: (NaN, \"e\")>}]}").unwrap();
+    let error = to_biniou(&value).unwrap_err();
+    assert!(
+      error.path() == "/a/1" || error.path() == "/b/1",
+      "{}",
+      error
+    );
+    assert_eq!(
+      error.message(),
+      "elements of an array must have the same kind"
+    );
+    let value =
+      parse("{a : [[1], [2.0]], b : {c : <D: (NaN, \"e\")>}, f : -9223372036854775808}").unwrap();
+    let bytes = to_biniou(&value).unwrap();
+    let options = BiniouOptions::new().names(&["a", "b", "c", "D", "f"]);
+    assert!(yojson_rs::ord::equal(
+      &from_biniou(&bytes, &options).unwrap(),
+      &value
+    ));
+    let error = from_biniou(b"\x15\x01\x80\x00\x5b\xdb\x11", &options).unwrap_err();
+    assert_eq!(error.message(), "unexpected end of input at byte 7");
+    let error = from_biniou(b"\x15\x01\x80\x00\x5b\xdb\x1a\x00", &options).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "unsupported tag 26 at byte 7 at \"/#00005bdb\""
+    );
+  }
+
+  #[test]
+  fn check_depth() {
+    let mut bytes = b"\x14\x01".repeat(200_000);
+    bytes.extend_from_slice(b"\x18\x00");
+    let error = from_biniou(&bytes, &BiniouOptions::new()).unwrap_err();
+    assert_eq!(
+      error.message(),
+      "nesting depth exceeds the limit at byte 257"
+    );
+    assert_eq!(error.path(), "/0".repeat(128));
+    let options = BiniouOptions::new().max_depth(Some(2));
+    // ((unit))
+    assert!(from_biniou(b"\x14\x01\x14\x01\x18\x00", &options).is_ok());
+    // (((unit)))
+    assert!(from_biniou(b"\x14\x01\x14\x01\x14\x01\x18\x00", &options).is_err());
+    let options = BiniouOptions::new().max_depth(None);
+    let mut bytes = b"\x14\x01".repeat(200);
+    bytes.extend_from_slice(b"\x18\x00");
+    assert!(from_biniou(&bytes, &options).is_ok());
+  }
+}
@@ -0,0 +1,46 @@
+(* Writes the Biniou fixtures of tests/biniou.rs with OCaml Biniou.
+
+   From this directory:
+
+     ocamlfind ocamlopt -package biniou -linkpkg fixtures.ml -o fixtures && ./fixtures
+
+   Each fixture is written to <name>.bin, and the hashes of the names to hashes.txt, as
+   "<name> <hash>" lines. `bdump -h <name>.bin` prints a fixture. *)
+
+let h = Bi_io.hash_name
+
+let hash31 name = h name land 0x7fffffff
+
+let fixtures : (string * Bi_io.tree) list = [
+  "id", `Record [| (Some "id", h "id", `Svint 1) |];
+  "shape", `Record [|
+    (Some "shape", h "shape", `Variant (Some "Circle", h "Circle", Some (`Float64 1.5)))
+  |];
+  "svints", `Array (Some (Bi_io.svint_tag, [| `Svint 1; `Svint (-1); `Svint 300 |]));
+  "tuple", `Tuple [|
+    `Svint 1; `String "a"; `Unit; `Bool true; `Variant (Some "Active", h "Active", None)
+  |];
+  "table", `Table (Some (
+    [| (Some "x", h "x", Bi_io.svint_tag); (Some "y", h "y", Bi_io.svint_tag) |],
+    [| [| `Svint 1; `Svint 2 |]; [| `Svint 3; `Svint 4 |] |]
+  ));
+  "other_tags", `Tuple [|
+    `Int32 (-2l);
+    `Uvint 300;
+    `Int8 '\xff';
+    `Num_variant (1, Some (`Svint 1));
+    `Record [| (None, 0x5bdb, `Unit) |];
+  |];
+]
+
+let names = [ "foo"; "id"; "shape"; "Circle"; "Active"; "x"; "y" ]
+
+let () =
+  List.iter (fun (name, tree) ->
+    let oc = open_out_bin (name ^ ".bin") in
+    output_string oc (Bi_io.string_of_tree tree);
+    close_out oc
+  ) fixtures;
+  let oc = open_out "hashes.txt" in
+  List.iter (fun name -> Printf.fprintf oc "%s %d\n" name (hash31 name)) names;
+  close_out oc
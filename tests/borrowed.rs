@@ -0,0 +1,49 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use std::borrow::Cow;
+  use yojson_rs::value::BorrowedValue;
+
+  #[test]
+  fn check_borrow() {
+    let text = r#"{x : "abc", "y" : [<Foo: "def">]}"#;
+    let value = yojson_rs::parser::parse_borrowed(text).unwrap();
+    match value.get("x") {
+      Some(BorrowedValue::String(Cow::Borrowed(s))) => assert_eq!(*s, "abc"),
+      v => panic!("{:?}", v),
+    }
+    match value.get("y") {
+      Some(BorrowedValue::Array(array)) => match &array[0] {
+        BorrowedValue::Variant((Cow::Borrowed("Foo"), Some(payload))) => {
+          assert!(matches!(
+            **payload,
+            BorrowedValue::String(Cow::Borrowed("def"))
+          ))
+        }
+        v => panic!("{:?}", v),
+      },
+      v => panic!("{:?}", v),
+    }
+  }
+
+  #[test]
+  fn check_escape() {
+    let value = yojson_rs::parser::parse_borrowed(r#"{"a\nb" : "c\u0021"}"#).unwrap();
+    match value.get("a\nb") {
+      Some(BorrowedValue::String(Cow::Owned(s))) => assert_eq!(s, "c!"),
+      v => panic!("{:?}", v),
+    }
+  }
+
+  #[test]
+  fn check_into_owned() {
+    let text = r#"{x : 123, y : {"y1" : "abc\ndef", "y2" : [null, 123.45, (12, "y3")]}, z : <A>}"#;
+    assert_eq!(
+      yojson_rs::parser::parse_borrowed(text)
+        .unwrap()
+        .into_owned(),
+      yojson_rs::parser::parse(text).unwrap()
+    );
+  }
+}
@@ -0,0 +1,74 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::canonical::to_string;
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  fn hex(digest: [u8; 32]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn check_sorted_and_compact() {
+    let a =
+      parse("{b : [1, (2, <C: {y : 1, x : 2}>)], a : \"\\u00e9\\t\", \"\\u00e9\" : <D>, z : null}")
+        .unwrap();
+    let b =
+      parse("{z : null, \"\\u00e9\" : <D>, a : \"\u{e9}\\t\", b : [1, (2, <C: {x : 2, y : 1}>)]}")
+        .unwrap();
+    let s = to_string(&a);
+    assert_eq!(
+      s,
+      "{\"a\":\"\u{e9}\\t\",\"b\":[1,(2,<\"C\":{\"x\":2,\"y\":1}>)],\"z\":null,\"\u{e9}\":<\"D\">}"
+    );
+    assert_eq!(to_string(&b), s);
+    assert_eq!(parse(&s), Ok(a));
+  }
+
+  #[test]
+  fn check_floats() {
+    let cases = [
+      (1.0, "1.0"),
+      (-0.0, "0.0"),
+      (0.5, "0.5"),
+      (123.456, "123.456"),
+      (1e21, "1.0e+21"),
+      (1e20, "100000000000000000000.0"),
+      (0.000001, "0.000001"),
+      (1e-7, "1.0e-7"),
+      (-1.5e300, "-1.5e+300"),
+      (5e-324, "5.0e-324"),
+      (f64::NAN, "NaN"),
+      (f64::NEG_INFINITY, "-Infinity"),
+    ];
+    for (f, s) in cases.iter() {
+      assert_eq!(to_string(&Value::Float(*f)), *s);
+      let value = parse(&format!("{{x : {}}}", s)).unwrap();
+      assert_eq!(to_string(&value), format!("{{\"x\":{}}}", s));
+    }
+    assert_eq!(to_string(&Value::Integer(1)), "1");
+  }
+
+  #[test]
+  fn check_content_hash() {
+    let value = Value::Assoc(Default::default());
+    assert_eq!(
+      hex(value.content_hash()),
+      "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+    );
+    let a = parse("{b : 1.0, a : [1.0e21, 0.5]}").unwrap();
+    let b = parse("{a : [1000000000000000000000.0, 5.0e-1], b : 1.0}").unwrap();
+    assert_eq!(
+      hex(a.content_hash()),
+      "677f72a564693fc27875891f77b6bd449f93756dc700c8e978e275df28ed8289"
+    );
+    assert_eq!(a.content_hash(), b.content_hash());
+    let long = Value::String("a".repeat(1000));
+    assert_eq!(
+      hex(long.content_hash()),
+      "955d04731f2adae1d94c940c2b9f72677431310d92f650dc581251045f4dbcdb"
+    );
+  }
+}
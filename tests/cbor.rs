@@ -0,0 +1,71 @@
+extern crate ciborium;
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use ciborium::value::Value as Cbor;
+  use yojson_rs::cbor::{from_cbor, to_cbor};
+  use yojson_rs::parser::parse;
+
+  #[test]
+  fn check_to_cbor() {
+    let value = parse("{shape : <Circle: 1.5>}").unwrap();
+    let cbor = to_cbor(&value);
+    assert!(cbor.is_lossless());
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(cbor.value(), &mut bytes).unwrap();
+    // {"shape": 27(["Circle", 1.5])}
+    assert_eq!(
+      bytes,
+      b"\xa1\x65shape\xd8\x1b\x82\x66Circle\xf9\x3e\x00".to_vec()
+    );
+  }
+
+  #[test]
+  fn check_round_trip() {
+    let value = parse(
+      "{a : [<Point>, <Line: [1.0, NaN]>], b : {c : null, d : \"x\"}, e : -9223372036854775808}",
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(to_cbor(&value).value(), &mut bytes).unwrap();
+    let cbor: Cbor = ciborium::de::from_reader(bytes.as_slice()).unwrap();
+    let back = from_cbor(&cbor);
+    assert!(back.is_lossless(), "{:?}", back.losses());
+    assert!(yojson_rs::ord::equal(back.value(), &value));
+  }
+
+  #[test]
+  fn check_losses() {
+    let value = parse("{a : (1, 2)}").unwrap();
+    let losses = to_cbor(&value).losses().to_vec();
+    assert_eq!(losses.len(), 1);
+    assert_eq!(
+      losses[0].to_string(),
+      "tuple is read back as an array at \"/a\""
+    );
+    let cbor = Cbor::Map(vec![
+      (Cbor::Integer(1.into()), Cbor::Bytes(vec![1, 2])),
+      (
+        Cbor::Text("t".to_string()),
+        Cbor::Tag(0, Box::new(Cbor::Text("2013-03-21T20:04:00Z".to_string()))),
+      ),
+      (Cbor::Text("u".to_string()), Cbor::Integer(u64::MAX.into())),
+    ]);
+    let (value, losses) = from_cbor(&cbor).into_parts();
+    assert_eq!(
+      value,
+      parse("{\"1\" : [1, 2], t : \"2013-03-21T20:04:00Z\", u : 18446744073709551615.0}").unwrap()
+    );
+    let messages = losses.iter().map(|loss| loss.message()).collect::<Vec<_>>();
+    assert_eq!(
+      messages,
+      vec![
+        "key 1 is read as a string",
+        "byte string is read as an array",
+        "tag 0 is dropped",
+        "integer 18446744073709551615 is out of the range of i64 and read as a float"
+      ]
+    );
+  }
+}
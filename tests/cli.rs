@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+  use std::io::Write;
+  use std::process::{Command, Output, Stdio};
+
+  fn run(args: &[&str], input: &str) -> Output {
+    run_bytes(args, input.as_bytes())
+  }
+
+  fn run_bytes(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yojson"))
+      .args(args)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+  }
+
+  fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+  }
+
+  #[test]
+  fn check_format() {
+    let output = run(&[], "{b : [1], a : <A>}");
+    assert_eq!(stdout(&output), "{\n  a: <A>,\n  b: [\n    1\n  ]\n}\n");
+    let output = run(&["--compact", "--std"], "{b : (1, 2), a : <A: 1>}");
+    assert_eq!(stdout(&output), "{\"a\":[\"A\",1],\"b\":[1,2]}\n");
+    let output = run(&["-s", "--compact", "-"], "{a : 1} {a : 2}");
+    assert_eq!(stdout(&output), "{a:1}\n{a:2}\n");
+    assert_eq!(output.status.code(), Some(0));
+  }
+
+  #[test]
+  fn check_check() {
+    let output = run(&["--check"], "{a : 1}");
+    assert_eq!(stdout(&output), "");
+    assert_eq!(output.status.code(), Some(0));
+    let output = run(&["--check"], "{a : 1,\n b : }");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("<stdin>"), "{}", stderr);
+    assert!(stderr.contains("line 2, column 6"), "{}", stderr);
+  }
+
+  #[test]
+  fn check_encodings() {
+    let mut utf16 = vec![0xff, 0xfe];
+    utf16.extend("{a : 1} {a : 2}".encode_utf16().flat_map(u16::to_le_bytes));
+    let output = run_bytes(&["-s", "--check"], &utf16);
+    assert_eq!(output.status.code(), Some(0));
+    let output = run_bytes(&["-s", "--compact"], &utf16);
+    assert_eq!(stdout(&output), "{a:1}\n{a:2}\n");
+    let output = run_bytes(&["--compact"], "\u{feff}{a : 1}".as_bytes());
+    assert_eq!(stdout(&output), "{a:1}\n");
+    let output = run_bytes(&["--check"], b"{a : \"\xff\"}");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid UTF-8"), "{}", stderr);
+  }
+
+  #[test]
+  fn check_usage() {
+    let output = run(&["--unknown"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["no/such/file.yojson"], "");
+    assert_eq!(output.status.code(), Some(2));
+  }
+
+  #[test]
+  fn check_get() {
+    let input = r#"{servers : [{host : "a", port : 80, state : <Up: (1, "x")>}]}"#;
+    let output = run(&["get", ".servers[0].port"], input);
+    assert_eq!(stdout(&output), "80\n");
+    let output = run(&["get", "-r", "/servers/0/host"], input);
+    assert_eq!(stdout(&output), "a\n");
+    let output = run(&["get", "--compact", ".servers[0].state<Up:>"], input);
+    assert_eq!(stdout(&output), "(1,\"x\")\n");
+    let output = run(&["get", "/servers/0/state/-payload/1"], input);
+    assert_eq!(stdout(&output), "\"x\"\n");
+    let output = run(&["get", ".servers[1]"], input);
+    assert_eq!((stdout(&output), output.status.code()), ("", Some(3)));
+    let output = run(&["get", ".servers["], input);
+    assert_eq!(output.status.code(), Some(2));
+  }
+}
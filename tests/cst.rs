@@ -0,0 +1,106 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::cst::{Document, EditError};
+  use yojson_rs::value::Value;
+
+  const CONFIG: &str = r#"{
+  // server settings
+  server : {
+    host : "localhost", /* default */
+    port : 80
+  },
+  "log-level" : <Info>
+}
+"#;
+
+  #[test]
+  fn check_round_trip() {
+    let doc = Document::parse(CONFIG).unwrap();
+    assert_eq!(doc.to_string(), CONFIG);
+    assert_eq!(doc.get(&["server", "port"]), Some(Value::Integer(80)));
+  }
+
+  #[test]
+  fn check_set() {
+    let mut doc = Document::parse(CONFIG).unwrap();
+    doc.set(&["server", "port"], &Value::Integer(8080)).unwrap();
+    doc.set(&["server", "timeout"], &Value::Float(1.5)).unwrap();
+    doc
+      .set(&["log-level"], &Value::Variant(("Debug".to_string(), None)))
+      .unwrap();
+    assert_eq!(
+      doc.to_string(),
+      r#"{
+  // server settings
+  server : {
+    host : "localhost", /* default */
+    port : 8080,
+    timeout : 1.5
+  },
+  "log-level" : <Debug>
+}
+"#
+    );
+  }
+
+  #[test]
+  fn check_remove() {
+    let mut doc = Document::parse(CONFIG).unwrap();
+    assert_eq!(
+      doc.remove(&["server", "host"]),
+      Ok(Value::String("localhost".to_string()))
+    );
+    assert_eq!(
+      doc.remove(&["server", "user"]),
+      Err(EditError::NotFound("user".to_string()))
+    );
+    assert_eq!(
+      doc.to_string(),
+      r#"{
+  // server settings
+  server : {
+    port : 80
+  },
+  "log-level" : <Info>
+}
+"#
+    );
+  }
+
+  #[test]
+  fn check_sort_keys() {
+    let mut doc = Document::parse(
+      r#"{
+  // server settings
+  server : {
+    port : 80,
+    host : "localhost" // default
+  },
+  "log-level" : <Info>,
+  cache : [{ttl : 60, size : 10}]
+}
+"#,
+    )
+    .unwrap();
+    doc.sort_keys();
+    assert_eq!(
+      doc.to_string(),
+      r#"{
+  // server settings
+  cache : [{size : 10, ttl : 60}],
+  "log-level" : <Info>,
+  server : {
+    host : "localhost", // default
+    port : 80
+  }
+}
+"#
+    );
+    let mut doc = Document::parse("{b : 1, /* b */ a : 2 // a\n}").unwrap();
+    doc.sort_keys();
+    assert_eq!(doc.to_string(), "{a : 2, // a\n b : 1 /* b */\n}");
+    assert!(Document::parse(&doc.to_string()).is_ok());
+  }
+}
@@ -0,0 +1,155 @@
+extern crate yojson_rs;
+extern crate yojson_rs_derive;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::convert::{FromYojson, ToYojson};
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+  use yojson_rs_derive::{YojsonDeserialize, YojsonSerialize};
+
+  #[derive(YojsonSerialize, YojsonDeserialize, PartialEq, Debug)]
+  enum Shape {
+    Point,
+    Circle(f64),
+    Rect(f64, f64),
+    #[yojson(name = "poly")]
+    Polygon {
+      points: Vec<(i64, i64)>,
+    },
+  }
+
+  #[derive(YojsonSerialize, YojsonDeserialize, PartialEq, Debug)]
+  struct Drawing<T> {
+    #[yojson(key = "type")]
+    kind: String,
+    shapes: Vec<T>,
+    label: Option<String>,
+    #[yojson(default)]
+    layer: i64,
+  }
+
+  #[derive(YojsonSerialize, YojsonDeserialize, PartialEq, Debug)]
+  #[yojson(encoding = "ppx")]
+  enum PpxShape {
+    Point,
+    Circle(f64),
+    Rect(f64, f64),
+    Polygon { points: Vec<(i64, i64)> },
+  }
+
+  #[derive(YojsonSerialize, YojsonDeserialize, PartialEq, Debug)]
+  #[yojson(encoding = "ppx")]
+  struct PpxDrawing {
+    shapes: Vec<PpxShape>,
+    origin: (i64, i64),
+    label: Option<String>,
+  }
+
+  /// Fields named as the locals of the derived code.
+  #[derive(YojsonSerialize, YojsonDeserialize, PartialEq, Debug)]
+  struct Locals {
+    assoc: i64,
+    v: i64,
+    t: i64,
+  }
+
+  #[derive(YojsonSerialize, YojsonDeserialize, PartialEq, Debug)]
+  enum LocalsShape {
+    Named {
+      assoc: i64,
+      name: String,
+      arguments: Vec<i64>,
+    },
+  }
+
+  fn round_trip<T: ToYojson + FromYojson + PartialEq + std::fmt::Debug>(x: T, text: &str) {
+    let value = x.to_yojson();
+    assert_eq!(yojson_rs::to_string(value.clone()), text);
+    assert_eq!(T::from_yojson(&value), Ok(x));
+  }
+
+  #[test]
+  fn check_yojson_encoding() {
+    round_trip(Shape::Point, "<Point>");
+    round_trip(Shape::Circle(1.5), "<Circle:1.5>");
+    round_trip(Shape::Rect(1.0, 2.0), "<Rect:(1.0,2.0)>");
+    round_trip(
+      Shape::Polygon {
+        points: vec![(0, 1)],
+      },
+      "<poly:{points:[(0,1)]}>",
+    );
+    let drawing = Drawing::<Shape>::from_yojson(
+      &parse(r#"{type : "sketch", shapes : [<Point>, <Circle: 2>], label : null}"#).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      drawing,
+      Drawing {
+        kind: "sketch".to_string(),
+        shapes: vec![Shape::Point, Shape::Circle(2.0)],
+        label: None,
+        layer: 0,
+      }
+    );
+    assert_eq!(Drawing::from_yojson(&drawing.to_yojson()), Ok(drawing));
+  }
+
+  #[test]
+  fn check_ppx_encoding() {
+    round_trip(PpxShape::Point, "[\"Point\"]");
+    round_trip(PpxShape::Circle(1.5), "[\"Circle\",1.5]");
+    round_trip(PpxShape::Rect(1.0, 2.0), "[\"Rect\",1.0,2.0]");
+    round_trip(
+      PpxShape::Polygon {
+        points: vec![(0, 1)],
+      },
+      "[\"Polygon\",{points:[[0,1]]}]",
+    );
+    let drawing = PpxDrawing::from_yojson(
+      &parse(r#"{"shapes": [["Point"], ["Rect", 1, 2]], "origin": [3, 4], "label": "a"}"#).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(drawing.shapes[1], PpxShape::Rect(1.0, 2.0));
+    assert_eq!(drawing.origin, (3, 4));
+    assert_eq!(drawing.label.as_deref(), Some("a"));
+  }
+
+  #[test]
+  fn check_field_names() {
+    let locals = Locals {
+      assoc: 1,
+      v: 2,
+      t: 3,
+    };
+    assert_eq!(
+      locals.to_yojson(),
+      parse("{assoc : 1, v : 2, t : 3}").unwrap()
+    );
+    assert_eq!(Locals::from_yojson(&locals.to_yojson()), Ok(locals));
+    let shape = LocalsShape::Named {
+      assoc: 1,
+      name: "a".to_string(),
+      arguments: vec![2],
+    };
+    assert_eq!(LocalsShape::from_yojson(&shape.to_yojson()), Ok(shape));
+  }
+
+  #[test]
+  fn check_errors() {
+    let error = Drawing::<Shape>::from_yojson(
+      &parse(r#"{type : "sketch", shapes : [<Rect: (1, "2")>], label : null}"#).unwrap(),
+    )
+    .unwrap_err();
+    assert_eq!(error.path(), "/shapes/0/-payload/1");
+    let rect = Value::Array(vec![Value::String("Rect".to_string()), Value::Integer(1)]);
+    let error = PpxShape::from_yojson(&rect).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "unexpected constructor \"Rect\" with 1 arguments"
+    );
+    let error = Drawing::<Shape>::from_yojson(&parse("{shapes : []}").unwrap()).unwrap_err();
+    assert_eq!(error.to_string(), "missing member \"type\"");
+  }
+}
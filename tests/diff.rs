@@ -0,0 +1,74 @@
+#[macro_use]
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::diff::{diff, render, Change};
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_diff() {
+    let left = parse(r#"{a : 1, b : [1, 2], c : (1, "x"), d : <A: 1>, e : <B>, f : NaN}"#).unwrap();
+    let right =
+      parse(r#"{f : NaN, e : <C>, d : <A: 2>, c : (1, "y"), b : [1, 2, 3], g : null}"#).unwrap();
+    assert_eq!(
+      diff(&left, &right),
+      vec![
+        Change::Removed {
+          path: "/a".to_string(),
+          value: Value::Integer(1)
+        },
+        Change::Added {
+          path: "/b/2".to_string(),
+          value: Value::Integer(3)
+        },
+        Change::Changed {
+          path: "/c/1".to_string(),
+          old: Value::String("x".to_string()),
+          new: Value::String("y".to_string())
+        },
+        Change::Changed {
+          path: "/d/-payload".to_string(),
+          old: Value::Integer(1),
+          new: Value::Integer(2)
+        },
+        Change::Changed {
+          path: "/e".to_string(),
+          old: Value::Variant(("B".to_string(), None)),
+          new: Value::Variant(("C".to_string(), None))
+        },
+        Change::Added {
+          path: "/g".to_string(),
+          value: Value::Null
+        },
+      ]
+    );
+    assert!(diff(&left, &left.clone()).is_empty());
+  }
+
+  #[test]
+  fn check_render() {
+    let left = parse(r#"{"a/b" : [1, 2], c : 1}"#).unwrap();
+    let right = parse(r#"{"a/b" : [1], c : "1"}"#).unwrap();
+    let changes = diff(&left, &right);
+    assert_eq!(render(&changes, false), "- /a~1b/1: 2\n~ /c: 1 -> \"1\"\n");
+    assert_eq!(
+      render(&changes[..1], true),
+      "\u{1b}[31m- /a~1b/1: 2\u{1b}[0m\n"
+    );
+    assert_eq!(
+      diff(&Value::Null, &Value::Bool(true))[0].to_string(),
+      "~ (root): null -> true"
+    );
+  }
+
+  #[test]
+  fn check_assert_macro() {
+    assert_value_eq!(parse("{a : [1]}").unwrap(), parse("{a : [1]}").unwrap());
+    let result = std::panic::catch_unwind(|| {
+      assert_value_eq!(parse("{a : [1]}").unwrap(), parse("{a : [2]}").unwrap())
+    });
+    assert!(result.is_err());
+  }
+}
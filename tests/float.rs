@@ -1,6 +1,7 @@
 extern crate yojson_rs;
 
 #[cfg(test)]
+#[allow(clippy::legacy_numeric_constants)]
 mod tests {
   #[test]
   fn check_parse_infinity() {
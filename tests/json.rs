@@ -0,0 +1,76 @@
+extern crate serde_json;
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use std::convert::TryFrom;
+  use yojson_rs::json::{from_json, to_json, IntegerOverflow, JsonOptions, NonFinite, VariantRepr};
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_to_json() {
+    let value = parse("{a : (1, 2.5), b : <A>, c : <B: [\"x\"]>, d : null}").unwrap();
+    assert_eq!(
+      serde_json::Value::try_from(value.clone()),
+      Ok(json!({"a": [1, 2.5], "b": "A", "c": ["B", ["x"]], "d": null}))
+    );
+    let options = JsonOptions::new().variants(VariantRepr::Array);
+    assert_eq!(
+      to_json(&value, &options),
+      Ok(json!({"a": [1, 2.5], "b": ["A"], "c": ["B", ["x"]], "d": null}))
+    );
+    let basic = yojson_rs::convert::to_std(value.clone());
+    assert_eq!(
+      serde_json::Value::try_from(basic),
+      serde_json::Value::try_from(value.clone())
+    );
+    let options = JsonOptions::new().variants(VariantRepr::Object);
+    assert_eq!(
+      to_json(&value, &options),
+      Ok(json!({"a": [1, 2.5], "b": "A", "c": {"B": ["x"]}, "d": null}))
+    );
+  }
+
+  #[test]
+  fn check_non_finite() {
+    let value = parse("{a : [1.0, NaN], b : <C: -Infinity>}").unwrap();
+    let error = serde_json::Value::try_from(value.clone()).unwrap_err();
+    assert!(
+      error.path() == "/a/1" || error.path() == "/b/-payload",
+      "{}",
+      error
+    );
+    let options = JsonOptions::new().non_finite(NonFinite::Null);
+    assert_eq!(
+      to_json(&value, &options),
+      Ok(json!({"a": [1.0, null], "b": ["C", null]}))
+    );
+    let options = JsonOptions::new().non_finite(NonFinite::String);
+    assert_eq!(
+      to_json(&value, &options),
+      Ok(json!({"a": [1.0, "NaN"], "b": ["C", "-Infinity"]}))
+    );
+  }
+
+  #[test]
+  fn check_from_json() {
+    let json = json!({"a": [1, 2.5, "x"], "b": {"c": null, "d": true}});
+    assert_eq!(
+      Value::try_from(json),
+      Ok(parse("{a : [1, 2.5, \"x\"], b : {c : null, d : true}}").unwrap())
+    );
+    let json = json!({"big": [u64::MAX]});
+    let error = Value::try_from(json.clone()).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "integer 18446744073709551615 is out of the range of i64 at \"/big/0\""
+    );
+    let options = JsonOptions::new().integer_overflow(IntegerOverflow::Float);
+    assert_eq!(
+      from_json(&json, &options),
+      Ok(parse("{big : [18446744073709551615.0]}").unwrap())
+    );
+  }
+}
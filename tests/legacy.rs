@@ -0,0 +1,100 @@
+extern crate yojson_rs;
+
+#[allow(dead_code)]
+#[path = "../benches/legacy/mod.rs"]
+mod legacy;
+
+#[cfg(test)]
+mod tests {
+  use super::legacy;
+  use std::panic::catch_unwind;
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  /// Parses a text with the pest parser, which panics on some input.
+  fn parse_legacy(text: &str) -> Option<Result<Value, ()>> {
+    catch_unwind(|| legacy::parse(text).map_err(|_| ())).ok()
+  }
+
+  #[test]
+  fn check_same_output() {
+    let cases = [
+      "{}",
+      "{a : 1} // end\n",
+      "{a : 1} /* end */",
+      "/* a */ {a : 1 /* b */} // c\n",
+      r#"{a : "\"\\\/\b\f\n\r\t", "b c" : "\u0041"}"#,
+      "{a : \"é\", \"\\u0041\" : 1}",
+      "{a : \"x y \", b : \"x\ty\"}",
+      "{a : (0, -0, 1.5e10, 1E+2, 1.0e-2, -0.0)}",
+      "{a : (9223372036854775807, -9223372036854775808)}",
+      "{a : [NaN, Infinity, -Infinity]}",
+      "{a : <A>, b : <\"A b\": 1>, c : <A: (1, 2)>}",
+      "{a : (1), b : (1, [2, (3, <B>)]), c : []}",
+      "{\"a\" : 1, a : 2, a_1 : 3}",
+      "",
+      "[1]",
+      "{a : 1",
+      "{a : 1} {}",
+      "{a : [1,]}",
+      "{a : 1,}",
+      "{a : ()}",
+      "{a : 01}",
+      "{a : 1.}",
+      "{a : .5}",
+      "{a : 1e10}",
+      "{a : nul}",
+      "{_a : 1}",
+      "{1a : 1}",
+      "{a : \"\\x\"}",
+    ];
+    for text in cases.iter() {
+      match (parse_legacy(text), parse(text)) {
+        (Some(Ok(legacy)), Ok(value)) => {
+          assert!(yojson_rs::ord::equal(&legacy, &value), "{:?}", text)
+        }
+        (Some(Err(())), Err(_)) => (),
+        (legacy, value) => panic!("{:?}: {:?} and {:?}", text, legacy, value),
+      }
+    }
+  }
+
+  // The following inputs are read differently by the pest parser, which had these bugs.
+
+  #[test]
+  fn check_comment_at_eof() {
+    // A `//` comment needed a newline after it.
+    assert_eq!(parse_legacy("{a : 1} // end"), Some(Err(())));
+    assert_eq!(parse("{a : 1} // end"), parse("{a : 1}"));
+  }
+
+  #[test]
+  fn check_unicode_escapes() {
+    // `\u` escapes were truncated to one byte, which panicked beyond ASCII, and surrogate pairs
+    // were not combined. A lone surrogate is now read as U+FFFD.
+    assert_eq!(parse_legacy(r#"{a : "\u00e9"}"#), None);
+    assert_eq!(parse(r#"{a : "\u00e9"}"#), parse("{a : \"é\"}"));
+    assert_eq!(parse(r#"{a : "\ud83d\ude00"}"#), parse("{a : \"😀\"}"));
+    assert_eq!(parse(r#"{a : "\ud83d"}"#), parse("{a : \"\u{fffd}\"}"));
+  }
+
+  #[test]
+  fn check_leading_whitespace() {
+    // Whitespace at the start of a string value was skipped.
+    assert_eq!(
+      parse_legacy("{a : \" x\"}"),
+      parse("{a : \"x\"}").ok().map(Ok)
+    );
+    assert_eq!(
+      parse("{a : \" x\"}").unwrap().as_assoc().unwrap()["a"],
+      Value::String(" x".to_string())
+    );
+  }
+
+  #[test]
+  fn check_integer_overflow() {
+    // Integers out of the range of `i64` panicked.
+    assert_eq!(parse_legacy("{a : 9223372036854775808}"), None);
+    assert!(parse("{a : 9223372036854775808}").is_err());
+  }
+}
@@ -0,0 +1,128 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::{parse, parse_with_options, ErrorKind, Limit, ParseOptions};
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_depth() {
+    let text = format!("{{a : {}{}}}", "[".repeat(100_000), "]".repeat(100_000));
+    let err = parse(&text).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::LimitExceeded(Limit::Depth));
+    assert_eq!(err.position().offset, 132);
+    let options = ParseOptions::new().max_depth(Some(3));
+    assert!(parse_with_options("{a : [(1)]}", &options).is_ok());
+    assert!(parse_with_options("{a : [(<A>)]}", &options).is_err());
+    let (_, errors) = yojson_rs::parser::parse_recovering(&text);
+    assert_eq!(errors.len(), 1);
+  }
+
+  fn kind(text: &str, options: &ParseOptions) -> ErrorKind {
+    parse_with_options(text, options)
+      .unwrap_err()
+      .kind()
+      .clone()
+  }
+
+  #[test]
+  fn check_string_length() {
+    let options = ParseOptions::new().max_string_length(Some(3));
+    assert!(parse_with_options(r#"{abc : "abc", b : <Abc>}"#, &options).is_ok());
+    let kind = |text: &str| kind(text, &options);
+    assert_eq!(
+      kind(r#"{a : "a\nbc"}"#),
+      ErrorKind::LimitExceeded(Limit::StringLength)
+    );
+    assert_eq!(
+      kind(r#"{"abcd" : 1}"#),
+      ErrorKind::LimitExceeded(Limit::StringLength)
+    );
+    assert_eq!(
+      kind("{abcdefgh : 1}"),
+      ErrorKind::LimitExceeded(Limit::StringLength)
+    );
+    assert_eq!(
+      kind("{a : <Abcdefgh>}"),
+      ErrorKind::LimitExceeded(Limit::StringLength)
+    );
+  }
+
+  #[test]
+  fn check_assoc_members() {
+    let options = ParseOptions::new().max_assoc_members(Some(2));
+    assert!(parse_with_options("{a : 1, b : {c : 2, d : 3}}", &options).is_ok());
+    assert_eq!(
+      kind("{a : 1, b : 2, c : 3}", &options),
+      ErrorKind::LimitExceeded(Limit::AssocMembers)
+    );
+  }
+
+  #[test]
+  fn check_nodes() {
+    let options = ParseOptions::new().max_nodes(Some(5));
+    assert!(parse_with_options("{a : [1, 2, 3]}", &options).is_ok());
+    assert_eq!(
+      kind("{a : [1, 2, 3, 4, 5]}", &options),
+      ErrorKind::LimitExceeded(Limit::Nodes)
+    );
+  }
+
+  #[test]
+  fn check_to_string_deep() {
+    let mut value = Value::Null;
+    for _ in 0..100_000 {
+      value = Value::Array(vec![value]);
+    }
+    let s = yojson_rs::to_string(value);
+    assert_eq!(s.len(), 200_004);
+    assert!(s.starts_with("[[[") && s.ends_with("]]]"));
+    assert_eq!(&s[99_998..100_006], "[[null]]");
+  }
+
+  /// Builds `{a : {a : ... leaf}}` with `depth` assocs.
+  fn deep_assoc(depth: usize, leaf: Value) -> Value {
+    let mut value = leaf;
+    for _ in 0..depth {
+      let mut assoc = std::collections::HashMap::new();
+      assoc.insert("a".to_string(), value);
+      value = Value::Assoc(assoc);
+    }
+    value
+  }
+
+  #[test]
+  fn check_to_string_with_options_deep() {
+    let mut value = Value::Integer(1);
+    for _ in 0..100_000 {
+      value = Value::Variant(("A".to_string(), Some(Box::new(Value::Tuple(vec![value])))));
+    }
+    let options = yojson_rs::PrintOptions::new().std(true);
+    let s = yojson_rs::to_string_with_options(value, &options).unwrap();
+    assert!(s.starts_with("[\"A\",[[\"A\",[["));
+    assert!(s.contains("[[\"A\",[1]]]]"));
+  }
+
+  #[test]
+  fn check_merge_patch_deep() {
+    let mut target = deep_assoc(100_000, Value::Integer(1));
+    let patch = deep_assoc(100_000, Value::Integer(2));
+    yojson_rs::patch::merge_patch(&mut target, &patch);
+    let s = yojson_rs::to_string(target);
+    assert_eq!(s.len(), 400_001);
+    assert_eq!(&s[299_997..300_005], "{a:2}}}}");
+    assert_eq!(yojson_rs::to_string(patch).len(), 400_001);
+  }
+
+  #[test]
+  fn check_merge_deep() {
+    let mut left = deep_assoc(20_000, Value::Integer(1));
+    let right = deep_assoc(20_000, Value::Integer(2));
+    let strategy = yojson_rs::merge::MergeStrategy::new();
+    let conflicts = left.merge(right, &strategy).unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].path.len(), 40_000);
+    let s = yojson_rs::to_string(left);
+    assert_eq!(&s[59_997..60_005], "{a:2}}}}");
+  }
+}
@@ -0,0 +1,90 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::merge::{ArrayStrategy, Conflict, ConflictStrategy, MergeStrategy};
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_merge_assoc() {
+    let mut base =
+      parse(r#"{server : {host : "a", port : 80}, tags : [1], mode : <Dev: {v : 1}>}"#).unwrap();
+    let local =
+      parse(r#"{server : {port : 8080, tls : true}, tags : [2], mode : <Dev: {w : 2}>}"#).unwrap();
+    let conflicts = base.merge(local, &MergeStrategy::new()).unwrap();
+    assert_eq!(
+      base,
+      parse(
+        r#"{server : {host : "a", port : 8080, tls : true}, tags : [2], mode : <Dev: {v : 1, w : 2}>}"#
+      )
+      .unwrap()
+    );
+    assert_eq!(
+      conflicts,
+      vec![Conflict {
+        path: "/server/port".to_string(),
+        left: Value::Integer(80),
+        right: Value::Integer(8080)
+      }]
+    );
+  }
+
+  #[test]
+  fn check_array_strategies() {
+    let left = parse(r#"{a : [{x : 1}, 2], t : (1, 2)}"#).unwrap();
+    let right = parse(r#"{a : [{y : 1}, 2, 3], t : (1, 3)}"#).unwrap();
+    let merged = |arrays, conflicts| {
+      let mut value = left.clone();
+      let strategy = MergeStrategy::new().arrays(arrays).conflicts(conflicts);
+      value.merge(right.clone(), &strategy).unwrap();
+      value
+    };
+    assert_eq!(
+      merged(ArrayStrategy::Append, ConflictStrategy::PreferLeft),
+      parse(r#"{a : [{x : 1}, 2, {y : 1}, 2, 3], t : (1, 2)}"#).unwrap()
+    );
+    assert_eq!(
+      merged(ArrayStrategy::ByIndex, ConflictStrategy::PreferRight),
+      parse(r#"{a : [{x : 1, y : 1}, 2, 3], t : (1, 3)}"#).unwrap()
+    );
+    assert_eq!(
+      merged(ArrayStrategy::Replace, ConflictStrategy::PreferLeft),
+      parse(r#"{a : [{y : 1}, 2, 3], t : (1, 2)}"#).unwrap()
+    );
+  }
+
+  #[test]
+  fn check_replace_arrays_without_conflicts() {
+    let mut value = parse(r#"{a : [1, 2], b : 1}"#).unwrap();
+    let strategy = MergeStrategy::new()
+      .arrays(ArrayStrategy::Replace)
+      .conflicts(ConflictStrategy::Error);
+    let conflicts = value
+      .merge(parse(r#"{a : [3], b : 1}"#).unwrap(), &strategy)
+      .unwrap();
+    assert!(conflicts.is_empty());
+    assert_eq!(value, parse(r#"{a : [3], b : 1}"#).unwrap());
+  }
+
+  #[test]
+  fn check_error_strategy() {
+    let mut value = parse(r#"{a : 1, b : <A>, c : 1.0}"#).unwrap();
+    let original = value.clone();
+    let strategy = MergeStrategy::new().conflicts(ConflictStrategy::Error);
+    let err = value
+      .merge(parse(r#"{a : 2, b : <B>, c : 1.0}"#).unwrap(), &strategy)
+      .unwrap_err();
+    let paths = err
+      .conflicts()
+      .iter()
+      .map(|c| c.path.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["/a", "/b"]);
+    assert_eq!(value, original);
+    assert!(value
+      .merge(parse(r#"{d : 1}"#).unwrap(), &strategy)
+      .unwrap()
+      .is_empty());
+  }
+}
@@ -0,0 +1,107 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::access::AccessError;
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_entry() {
+    let mut config = Value::Null;
+    config
+      .entry("server")
+      .or_insert_assoc()
+      .entry("port")
+      .or_insert(8080);
+    config
+      .entry("server")
+      .or_insert_assoc()
+      .entry("port")
+      .and_modify(|port| *port = Value::Integer(80))
+      .or_insert(8080);
+    config.entry("tags").or_insert_array().push("web");
+    config.entry("tags").or_insert_array().push("api");
+    assert_eq!(
+      config,
+      parse("{server : {port : 80}, tags : [\"web\", \"api\"]}").unwrap()
+    );
+  }
+
+  #[test]
+  fn check_take() {
+    let mut config = parse("{tags : [\"web\", \"api\"]}").unwrap();
+    let tags = config.get_path_mut("tags").unwrap().take();
+    assert_eq!(tags, Value::Array(vec!["web".into(), "api".into()]));
+    assert_eq!(config.get_path("tags"), Ok(&Value::Null));
+  }
+
+  #[test]
+  fn check_remove_path() {
+    let mut config =
+      parse("{servers : [{host : \"a\"}, {host : \"b\"}], size : (1, 2), state : <On: 1>}")
+        .unwrap();
+    assert_eq!(
+      config.remove_path(&["servers", "0", "host"]),
+      Ok(Value::String("a".to_string()))
+    );
+    assert_eq!(
+      config.remove_path(&["servers", "0"]),
+      Ok(parse("{}").unwrap())
+    );
+    assert_eq!(
+      config.remove_path(&["servers", "1"]),
+      Err(AccessError::Missing("/servers/1".to_string()))
+    );
+    assert_eq!(
+      config.remove_path(&["size", "0"]).unwrap_err().to_string(),
+      "cannot apply \"0\" to a value of type tuple at \"/size\""
+    );
+    assert_eq!(
+      config.remove_path(&["state", "-payload"]),
+      Ok(Value::Integer(1))
+    );
+    assert_eq!(
+      config,
+      parse("{servers : [{host : \"b\"}], size : (1, 2), state : <On>}").unwrap()
+    );
+  }
+
+  #[test]
+  fn check_retain() {
+    let mut value = parse("{a : [1, null, 2], b : null, c : (null, 1)}").unwrap();
+    value.retain(|value| !value.is_null());
+    value
+      .get_path_mut("a")
+      .unwrap()
+      .retain(|value| !value.is_null());
+    value
+      .get_path_mut("c")
+      .unwrap()
+      .retain(|value| !value.is_null());
+    assert_eq!(value, parse("{a : [1, 2], c : (null, 1)}").unwrap());
+    value.retain_members(|name, _| name != "c");
+    assert_eq!(value, parse("{a : [1, 2]}").unwrap());
+  }
+
+  #[test]
+  fn check_walk_mut() {
+    let mut value = parse("{a : [1, null], c : {d : 3, e : \"x\"}, f : <G: 4>}").unwrap();
+    let mut paths = Vec::new();
+    value.walk_mut(|path, value| {
+      paths.push(path.to_string());
+      value.retain(|value| !value.is_null());
+      if let Value::Integer(i) = *value {
+        *value = Value::Integer(i * 10);
+      }
+    });
+    assert_eq!(
+      paths,
+      vec!["", "/a", "/a/0", "/c", "/c/d", "/c/e", "/f", "/f/-payload"]
+    );
+    assert_eq!(
+      value,
+      parse("{a : [10], c : {d : 30, e : \"x\"}, f : <G: 40>}").unwrap()
+    );
+  }
+}
@@ -0,0 +1,64 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::{parse_with_options, ErrorKind, ParseOptions};
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_strict() {
+    let options = ParseOptions::new();
+    let err = parse_with_options("{a: [1, 2,]}", &options).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::TrailingComma);
+    assert_eq!(err.position().column, 10);
+    let err = parse_with_options("{a: 'b'}", &options).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::SingleQuotedString);
+    let err = parse_with_options("{a: 0x10}", &options).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidNumber("0x10".to_string()));
+  }
+
+  #[test]
+  fn check_relaxed() {
+    let options = ParseOptions::new()
+      .trailing_commas(true)
+      .single_quotes(true)
+      .hex_numbers(true)
+      .binary_numbers(true)
+      .leading_plus(true)
+      .leading_decimal_point(true);
+    let mut assoc = std::collections::HashMap::new();
+    assoc.insert(
+      "a".to_string(),
+      Value::Tuple(vec![
+        Value::Integer(31),
+        Value::Integer(-5),
+        Value::Integer(1),
+        Value::Float(0.5),
+        Value::Float(-0.25),
+      ]),
+    );
+    assoc.insert("b'c".to_string(), Value::String("d\"e'".to_string()));
+    assert_eq!(
+      parse_with_options(
+        r#"{a: (0x1F, -0b101, +1, .5, -.25,), 'b\'c': 'd"e\'',}"#,
+        &options
+      ),
+      Ok(Value::Assoc(assoc))
+    );
+  }
+
+  #[test]
+  fn check_independent() {
+    let options = ParseOptions::new().hex_numbers(true);
+    assert_eq!(
+      parse_with_options("{a: 0xff}", &options)
+        .unwrap()
+        .as_assoc()
+        .unwrap()["a"],
+      Value::Integer(255)
+    );
+    assert!(parse_with_options("{a: 0b1}", &options).is_err());
+    assert!(parse_with_options("{a: +1}", &options).is_err());
+    assert!(parse_with_options("{a: 0x8000000000000000}", &options).is_err());
+  }
+}
@@ -0,0 +1,84 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use std::cmp::Ordering;
+  use std::collections::{BTreeSet, HashSet};
+  use yojson_rs::ord::{compare, equal, OrdValue};
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_equal() {
+    let value = parse("{x : NaN, y : [-0.0, <A: (1, NaN)>], z : {b : 1, a : 2}}").unwrap();
+    assert_ne!(value, value);
+    assert!(equal(&value, &value));
+    let reparsed = parse(&yojson_rs::to_string(value.clone())).unwrap();
+    assert!(equal(&value, &reparsed));
+    let reordered = parse("{z : {a : 2, b : 1}, y : [0.0, <A: (1, NaN)>], x : NaN}").unwrap();
+    assert!(equal(&value, &reordered));
+    assert!(!equal(&Value::Integer(1), &Value::Float(1.0)));
+  }
+
+  #[test]
+  fn check_compare() {
+    let values = [
+      "{a : <B>}",
+      "{a : <A: 1>}",
+      "{a : <A>}",
+      "{a : (1, 2)}",
+      "{a : [1, 2]}",
+      "{a : [1]}",
+      "{a : {b : 0}}",
+      "{a : {a : 1}}",
+      "{a : \"b\"}",
+      "{a : 1.5}",
+      "{a : NaN}",
+      "{a : 2}",
+      "{a : true}",
+      "{a : null}",
+    ];
+    let mut sorted = values
+      .iter()
+      .map(|s| OrdValue(parse(s).unwrap()))
+      .collect::<Vec<_>>();
+    sorted.sort();
+    let sorted = sorted
+      .into_iter()
+      .map(|v| yojson_rs::to_string(v.0))
+      .collect::<Vec<_>>();
+    let expected = values
+      .iter()
+      .rev()
+      .map(|s| yojson_rs::to_string(parse(s).unwrap()))
+      .collect::<Vec<_>>();
+    assert_eq!(sorted, expected);
+    assert_eq!(
+      compare(
+        &parse("{a : 1}").unwrap(),
+        &parse("{a : 1, b : 0}").unwrap()
+      ),
+      Ordering::Less
+    );
+  }
+
+  #[test]
+  fn check_hash() {
+    let a = parse("{x : NaN, y : {p : 0.0, q : (1, <A>)}}").unwrap();
+    let mut b = parse("{y : {q : (1, <A>), p : -0.0}}").unwrap();
+    b.as_assoc_mut()
+      .unwrap()
+      .insert("x".to_string(), Value::Float(-f64::NAN));
+    let c = parse("{x : NaN, y : {p : 0.0, q : (1, <B>)}}").unwrap();
+    let set = vec![a.clone(), b, c.clone()]
+      .into_iter()
+      .map(OrdValue)
+      .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 2);
+    let tree = vec![c, a]
+      .into_iter()
+      .map(OrdValue::from)
+      .collect::<BTreeSet<_>>();
+    assert_eq!(tree.len(), 2);
+  }
+}
@@ -0,0 +1,132 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::patch::{
+    apply_patch, from_value, generate, merge_patch, to_value, Operation, PatchError,
+  };
+  use yojson_rs::pointer::PointerError;
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_apply_patch() {
+    let mut value = parse(r#"{a : [1, 2], t : (1, 2), s : <A>, o : {x : 1}}"#).unwrap();
+    let patch = from_value(
+      &parse(
+        r#"{p : [
+          {op : "add", path : "/a/-", value : 3},
+          {op : "remove", path : "/a/0"},
+          {op : "replace", path : "/t/1", value : "two"},
+          {op : "add", path : "/s/-payload", value : {v : 1}},
+          {op : "move", from : "/o/x", path : "/o/y"},
+          {op : "copy", from : "/t", path : "/u"},
+          {op : "test", path : "/a", value : [2, 3]}
+        ]}"#,
+      )
+      .unwrap()
+      .as_assoc()
+      .unwrap()["p"],
+    )
+    .unwrap();
+    apply_patch(&mut value, &patch).unwrap();
+    assert_eq!(
+      value,
+      parse(r#"{a : [2, 3], t : (1, "two"), s : <A: {v : 1}>, o : {y : 1}, u : (1, "two")}"#)
+        .unwrap()
+    );
+    assert_eq!(from_value(&to_value(&patch)).unwrap(), patch);
+  }
+
+  #[test]
+  fn check_patch_errors() {
+    let original = parse(r#"{t : (1, 2), a : [1]}"#).unwrap();
+    let mut value = original.clone();
+    let patch = from_value(
+      &parse(r#"{p : [{op : "add", path : "/a/0", value : 0}, {op : "remove", path : "/t/0"}]}"#)
+        .unwrap()
+        .as_assoc()
+        .unwrap()["p"],
+    )
+    .unwrap();
+    assert_eq!(
+      apply_patch(&mut value, &patch),
+      Err(PatchError::Pointer {
+        index: 1,
+        error: PointerError::FixedLength("/t/0".to_string())
+      })
+    );
+    assert_eq!(value, original);
+  }
+
+  #[test]
+  fn check_move_from_missing() {
+    let mut value = parse(r#"{a : 1}"#).unwrap();
+    let patch = vec![Operation::Move {
+      from: "/b".to_string(),
+      path: "/b".to_string(),
+    }];
+    assert_eq!(
+      apply_patch(&mut value, &patch),
+      Err(PatchError::Pointer {
+        index: 0,
+        error: PointerError::NotFound("/b".to_string())
+      })
+    );
+    let patch = vec![Operation::Move {
+      from: "/a".to_string(),
+      path: "/a".to_string(),
+    }];
+    assert_eq!(apply_patch(&mut value, &patch), Ok(()));
+  }
+
+  #[test]
+  fn check_merge_and_generate() {
+    let mut value =
+      parse(r#"{a : {b : 1, c : 2}, t : (1, 2), s : <A: {x : 1}>, u : (1, 2)}"#).unwrap();
+    let patch =
+      parse(r#"{a : {b : null, d : 3}, t : (null, 3), s : <A: {y : 2}>, u : (1, 2, 3)}"#).unwrap();
+    merge_patch(&mut value, &patch);
+    assert_eq!(
+      value,
+      parse(r#"{a : {c : 2, d : 3}, t : (null, 3), s : <A: {x : 1, y : 2}>, u : (1, 2, 3)}"#)
+        .unwrap()
+    );
+
+    let left = parse(r#"{a : [1, 2, 3, 4], b : <A>, c : <B: 1>, d : 1}"#).unwrap();
+    let right = parse(r#"{a : [0, 2], b : <A: 1>, c : <C>, e : 1}"#).unwrap();
+    let mut value = left.clone();
+    apply_patch(&mut value, &generate(&left, &right)).unwrap();
+    assert_eq!(value, right);
+  }
+
+  #[test]
+  fn check_generate_tuples() {
+    let left = parse(r#"{t : (1, 2), u : (1, 2)}"#).unwrap();
+    let right = parse(r#"{t : (1, 2, 3), u : (1, 3)}"#).unwrap();
+    let patch = generate(&left, &right);
+    assert_eq!(
+      patch,
+      vec![
+        Operation::Replace {
+          path: "/t".to_string(),
+          value: Value::Tuple(vec![
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(3)
+          ]),
+        },
+        Operation::Replace {
+          path: "/u/1".to_string(),
+          value: Value::Integer(3),
+        },
+      ]
+    );
+    let mut value = left.clone();
+    apply_patch(&mut value, &patch).unwrap();
+    assert_eq!(value, right);
+    let mut value = right.clone();
+    apply_patch(&mut value, &generate(&right, &left)).unwrap();
+    assert_eq!(value, left);
+  }
+}
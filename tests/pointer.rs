@@ -0,0 +1,52 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::pointer::{escape, join, tokens, PointerError};
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_lookup() {
+    let value =
+      parse(r#"{"a/b" : 1, "m~n" : 2, y : [null, (12, "y3")], s : <Active: {t : 1}>}"#).unwrap();
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/a~1b"), Some(&Value::Integer(1)));
+    assert_eq!(value.pointer("/m~0n"), Some(&Value::Integer(2)));
+    assert_eq!(value.pointer("/y/1/1").and_then(|v| v.as_str()), Some("y3"));
+    assert_eq!(value.pointer("/s/-payload/t"), Some(&Value::Integer(1)));
+    assert_eq!(value.pointer("/y/01"), None);
+    assert_eq!(value.pointer("/y/2"), None);
+    assert_eq!(value.pointer("y"), None);
+  }
+
+  #[test]
+  fn check_insert_remove() {
+    let mut value = parse(r#"{a : [1, 3], t : (1, 2), s : <None>}"#).unwrap();
+    assert_eq!(value.pointer_insert("/a/1", Value::Integer(2)), Ok(None));
+    assert_eq!(value.pointer_insert("/a/-", Value::Integer(4)), Ok(None));
+    assert_eq!(value.pointer("/a/3"), Some(&Value::Integer(4)));
+    assert_eq!(value.pointer_insert("/s/-payload", Value::Null), Ok(None));
+    assert_eq!(value.pointer("/s/-payload"), Some(&Value::Null));
+    assert_eq!(value.pointer_remove("/s/-payload"), Ok(Value::Null));
+    assert_eq!(value.pointer_remove("/a/0"), Ok(Value::Integer(1)));
+    assert_eq!(
+      value.pointer_insert("/t/0", Value::Null),
+      Err(PointerError::FixedLength("/t/0".to_string()))
+    );
+    assert_eq!(
+      value.pointer_remove("/x/y"),
+      Err(PointerError::NotFound("/x".to_string()))
+    );
+    *value.pointer_mut("/t/1").unwrap() = Value::Bool(true);
+    assert_eq!(value.pointer("/t/1"), Some(&Value::Bool(true)));
+  }
+
+  #[test]
+  fn check_tokens() {
+    assert_eq!(escape("a/b~c"), "a~1b~0c");
+    assert_eq!(tokens("/a~1b/~01").unwrap(), vec!["a/b", "~1"]);
+    assert_eq!(join(&["a/b", "~1"]), "/a~1b/~01");
+    assert!(tokens("a").is_err());
+  }
+}
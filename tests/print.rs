@@ -0,0 +1,43 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::{to_string_with_options, PrintOptions};
+
+  #[test]
+  fn check_pretty() {
+    let value = parse("{b : (1, []), a : <Foo: {x : \"y\"}>, c : {}}").unwrap();
+    let options = PrintOptions::new().pretty(true);
+    assert_eq!(
+      to_string_with_options(value.clone(), &options).unwrap(),
+      "{\n  a: <Foo: {\n    x: \"y\"\n  }>,\n  b: (\n    1,\n    []\n  ),\n  c: {}\n}"
+    );
+    let compact = to_string_with_options(value.clone(), &PrintOptions::new()).unwrap();
+    assert_eq!(compact, "{a:<Foo:{x:\"y\"}>,b:(1,[]),c:{}}");
+    assert_eq!(parse(&compact), Ok(value));
+  }
+
+  #[test]
+  fn check_std() {
+    let value = parse("{b : (1, <Bar>), \"a b\" : <Foo: (1, 2)>}").unwrap();
+    let options = PrintOptions::new().std(true);
+    assert_eq!(
+      to_string_with_options(value, &options).unwrap(),
+      r#"{"a b":["Foo",[1,2]],"b":[1,"Bar"]}"#
+    );
+    let value = parse("{x : NaN}").unwrap();
+    let error = to_string_with_options(value, &options).unwrap_err();
+    assert_eq!(error.to_string(), "NaN is not allowed in standard JSON");
+  }
+
+  #[test]
+  fn check_to_string() {
+    let value =
+      parse("{\"a b\" : [1.0, Infinity, -Infinity], c : <\"D e\": \"\\u001f\\n\">}").unwrap();
+    let s = yojson_rs::to_string(value.clone());
+    assert!(s.contains(r#""a b":[1.0,Infinity,-Infinity]"#), "{}", s);
+    assert!(s.contains(r#"c:<"D e":"\u001f\n">"#), "{}", s);
+    assert_eq!(parse(&s), Ok(value));
+  }
+}
@@ -0,0 +1,116 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::query::{select, Query};
+  use yojson_rs::value::Value;
+
+  fn document() -> Value {
+    parse(
+      r#"{
+        items : [
+          {id : 1, status : <Active>, score : 12.5, pos : (1, 2)},
+          {id : 2, status : <Closed: "done">, score : 3, pos : (3, 4)},
+          {id : 3, status : <Active>, score : 20, pos : (5, 6)}
+        ],
+        owner : {id : 0, name : "a"}
+      }"#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn check_paths() {
+    let value = document();
+    let ints = |expr: &str| {
+      select(&value, expr)
+        .unwrap()
+        .into_iter()
+        .map(|v| v.as_integer().unwrap())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(ints("$.items[0].id"), vec![1]);
+    assert_eq!(ints("items[-1].id"), vec![3]);
+    assert_eq!(ints(".items[*].id"), vec![1, 2, 3]);
+    assert_eq!(ints("$.items[0:2].id"), vec![1, 2]);
+    assert_eq!(ints("$.items[::-2].id"), vec![3, 1]);
+    assert_eq!(ints("$.items[0, 2]['id']"), vec![1, 3]);
+    assert_eq!(ints("$..id"), vec![1, 2, 3, 0]);
+    assert_eq!(ints("$.items[*].pos[1]"), vec![2, 4, 6]);
+  }
+
+  #[test]
+  fn check_filters() {
+    let value = document();
+    let ids = |expr: &str| {
+      Query::parse(expr)
+        .unwrap()
+        .select(&value)
+        .into_iter()
+        .map(|v| v.as_integer().unwrap())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(ids("$.items[?(@.status == <Active>)].id"), vec![1, 3]);
+    assert_eq!(ids("$.items[?(@.status<Closed>)].id"), vec![2]);
+    assert_eq!(ids("$.items[?(@.score >= 12 && !(@.id == 3))].id"), vec![1]);
+    assert_eq!(
+      ids("$.items[?(@.pos == (3, 4) || @.score > 15)].id"),
+      vec![2, 3]
+    );
+    assert_eq!(ids("$.items[?(@.id != $.owner.id)].id"), vec![1, 2, 3]);
+    assert_eq!(
+      select(&value, "$.items[*].status<Closed:>").unwrap(),
+      vec![&Value::String("done".to_string())]
+    );
+    assert_eq!(select(&value, "$.items[*].status<*>").unwrap().len(), 3);
+  }
+
+  #[test]
+  fn check_errors() {
+    let err = Query::parse("$.items[").unwrap_err();
+    assert_eq!(err.offset(), 8);
+    assert!(Query::parse("$.items[::0]").is_err());
+    assert!(Query::parse("$.items[?(@.a == {)]").is_err());
+    assert!(Query::parse("$.").is_err());
+    let err = Query::parse("$.items[?(@.id == 1}//)]").unwrap_err();
+    assert_eq!(err.to_string(), "invalid literal at offset 18");
+    assert!(Query::parse("$.items[?(@.id == 1})]").is_err());
+  }
+
+  #[test]
+  fn check_nesting() {
+    let value = document();
+    let grouped = format!(
+      "$.items[?({}@.id == 2{})].id",
+      "(".repeat(100),
+      ")".repeat(100)
+    );
+    assert_eq!(select(&value, &grouped).unwrap(), vec![&Value::Integer(2)]);
+    let deep = format!(
+      "$.items[?({}@.id{})]",
+      "(".repeat(100_000),
+      ")".repeat(100_000)
+    );
+    assert_eq!(
+      Query::parse(&deep).unwrap_err().message(),
+      "expression nested too deeply"
+    );
+    let negated = format!("$.items[?({}@.id)]", "!".repeat(100_000));
+    assert!(Query::parse(&negated).is_err());
+  }
+
+  #[test]
+  fn check_pointer() {
+    let value = parse("{servers : [{port : 80, state : <Up: (1, \"a\")>}]}").unwrap();
+    assert_eq!(
+      select(&value, "/servers/0/port").unwrap(),
+      vec![&Value::Integer(80)]
+    );
+    assert_eq!(
+      select(&value, "/servers/0/state/-payload/1").unwrap(),
+      select(&value, ".servers[0].state<Up:>[1]").unwrap()
+    );
+    assert!(select(&value, "/servers/1").unwrap().is_empty());
+  }
+}
@@ -0,0 +1,65 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::{parse_recovering, ErrorKind};
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_no_error() {
+    let (value, errors) = parse_recovering(r#"{x : 123, y : [null, (12, "y3"), <Foo: 1.5>]}"#);
+    assert!(errors.is_empty());
+    assert_eq!(
+      Ok(value),
+      yojson_rs::parser::parse(r#"{x : 123, y : [null, (12, "y3"), <Foo: 1.5>]}"#)
+    );
+  }
+
+  #[test]
+  fn check_multiple_errors() {
+    let (value, errors) = parse_recovering("{a: [1, 2,], b 3,\n c: @, d: true}");
+    let kinds = errors.iter().map(|e| e.kind().clone()).collect::<Vec<_>>();
+    assert_eq!(
+      kinds,
+      vec![
+        ErrorKind::TrailingComma,
+        ErrorKind::MissingColon,
+        ErrorKind::Expected {
+          expected: "value",
+          found: "@".to_string()
+        },
+      ]
+    );
+    assert_eq!(errors[2].position().line, 2);
+    assert_eq!(errors[2].position().column, 5);
+    let mut assoc = std::collections::HashMap::new();
+    assoc.insert(
+      "a".to_string(),
+      Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+    );
+    assoc.insert("b".to_string(), Value::Integer(3));
+    assoc.insert("c".to_string(), Value::Null);
+    assoc.insert("d".to_string(), Value::Bool(true));
+    assert_eq!(value, Value::Assoc(assoc));
+  }
+
+  #[test]
+  fn check_unclosed() {
+    let (value, errors) = parse_recovering(r#"{a: (1, 2], b: "abc"#);
+    let kinds = errors.iter().map(|e| e.kind().clone()).collect::<Vec<_>>();
+    assert_eq!(
+      kinds,
+      vec![
+        ErrorKind::Expected {
+          expected: "',' or ')'",
+          found: "]".to_string()
+        },
+        ErrorKind::UnterminatedString,
+      ]
+    );
+    assert_eq!(
+      value.as_assoc().unwrap().get("b"),
+      Some(&Value::String("abc".to_string()))
+    );
+  }
+}
@@ -0,0 +1,143 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::schema::{Schema, Violation};
+
+  fn schema(text: &str) -> Schema {
+    Schema::from_value(&parse(text).unwrap()).unwrap()
+  }
+
+  fn violations(schema: &Schema, text: &str) -> Vec<(String, String)> {
+    schema
+      .validate(&parse(text).unwrap())
+      .into_iter()
+      .map(|Violation { path, message }| (path, message))
+      .collect()
+  }
+
+  #[test]
+  fn check_assoc_and_scalars() {
+    let schema = schema(
+      r#"{
+        type : "object",
+        properties : {
+          port : {type : "integer", minimum : 1, maximum : 65535},
+          host : {type : "string", minLength : 1},
+          tags : {type : "array", items : {enum : ["a", "b"]}, maxItems : 2}
+        },
+        required : ["port", "host"],
+        additionalProperties : false
+      }"#,
+    );
+    assert!(schema.is_valid(&parse(r#"{port : 80, host : "x", tags : ["a"]}"#).unwrap()));
+    assert_eq!(
+      violations(&schema, r#"{port : 0, tags : ["a", "c", "b"], extra : 1}"#),
+      vec![
+        ("".to_string(), "missing member \"host\"".to_string()),
+        (
+          "/extra".to_string(),
+          "unexpected member \"extra\"".to_string()
+        ),
+        (
+          "/port".to_string(),
+          "value is less than the minimum 1".to_string()
+        ),
+        (
+          "/tags".to_string(),
+          "array has more than 2 elements".to_string()
+        ),
+        (
+          "/tags/1".to_string(),
+          "value is not one of the allowed values".to_string()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn check_tuple_and_variant() {
+    let schema = schema(
+      r##"{
+        properties : {
+          pos : {type : "tuple", prefixItems : [{type : "number"}, {type : "string"}]},
+          mode : {
+            type : "variant",
+            constructors : {Dev : null, Prod : {type : "string"}}
+          },
+          modes : {type : "array", items : {"$ref" : "#/definitions/mode"}}
+        },
+        definitions : {
+          mode : {type : "variant", constructors : {A : null}}
+        }
+      }"##,
+    );
+    assert!(
+      schema.is_valid(&parse(r#"{pos : (1.5, "a"), mode : <Prod: "x">, modes : [<A>]}"#).unwrap())
+    );
+    assert_eq!(
+      violations(
+        &schema,
+        r#"{pos : (1, 2, 3), mode : <Dev: 1>, modes : [<B>]}"#
+      ),
+      vec![
+        (
+          "/mode".to_string(),
+          "constructor \"Dev\" takes no argument".to_string()
+        ),
+        (
+          "/modes/0".to_string(),
+          "constructor \"B\" is not allowed".to_string()
+        ),
+        (
+          "/pos".to_string(),
+          "expected a tuple of 2 elements, found 3".to_string()
+        ),
+        (
+          "/pos/1".to_string(),
+          "expected string, found integer".to_string()
+        ),
+      ]
+    );
+    assert_eq!(
+      violations(&schema, r#"{mode : <Prod>, pos : [1, "a"]}"#),
+      vec![
+        (
+          "/mode".to_string(),
+          "constructor \"Prod\" requires an argument".to_string()
+        ),
+        (
+          "/pos".to_string(),
+          "expected tuple, found array".to_string()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn check_schema_errors() {
+    let error = |text: &str| {
+      Schema::from_value(&parse(text).unwrap())
+        .unwrap_err()
+        .path()
+        .to_string()
+    };
+    assert_eq!(
+      error(r#"{properties : {a : {type : "int"}}}"#),
+      "/properties/a/type"
+    );
+    assert_eq!(error(r##"{items : {"$ref" : "#/definitions/x"}}"##), "");
+    assert_eq!(error(r#"{anyOf : [1]}"#), "/anyOf/0");
+    assert_eq!(error(r#"{type : ["string", 1]}"#), "/type/1");
+    let schema = schema(r#"{anyOf : [{type : "null"}, {type : "tuple"}], not : {const : (1, 2)}}"#);
+    assert!(schema.is_valid(&parse("{a : null}").unwrap().as_assoc().unwrap()["a"]));
+    assert_eq!(
+      violations(&schema, "{a : 1}"),
+      vec![(
+        "".to_string(),
+        "value matches none of the schemas".to_string()
+      )]
+    );
+  }
+}
@@ -0,0 +1,60 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::{parse_slice, parse_slice_with_options, ErrorKind, ParseOptions};
+  use yojson_rs::value::Value;
+
+  fn expected() -> Value {
+    let mut assoc = std::collections::HashMap::new();
+    assoc.insert("hoge".to_string(), Value::String("ふが".to_string()));
+    Value::Assoc(assoc)
+  }
+
+  #[test]
+  fn check_utf8() {
+    assert_eq!(parse_slice("{hoge : \"ふが\"}".as_bytes()), Ok(expected()));
+    let err = parse_slice(b"{hoge :\n \"\xff\"}").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidEncoding("UTF-8"));
+    assert_eq!(err.position().offset, 10);
+    assert_eq!(err.position().line, 2);
+    assert_eq!(err.position().column, 3);
+  }
+
+  #[test]
+  fn check_bom() {
+    let bytes = "\u{feff}{hoge : \"ふが\"}".as_bytes();
+    let err = parse_slice(bytes).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ByteOrderMark);
+    let options = ParseOptions::new().allow_bom(true);
+    assert_eq!(parse_slice_with_options(bytes, &options), Ok(expected()));
+  }
+
+  #[test]
+  fn check_utf16_utf32() {
+    let text = "{hoge : \"ふが\"}";
+    let options = ParseOptions::new().detect_encoding(true);
+    let utf16le = text
+      .encode_utf16()
+      .flat_map(|u| u.to_le_bytes().to_vec())
+      .collect::<Vec<_>>();
+    assert_eq!(parse_slice_with_options(&utf16le, &options), Ok(expected()));
+    let mut utf16be = vec![0xfe, 0xff];
+    utf16be.extend(text.encode_utf16().flat_map(|u| u.to_be_bytes().to_vec()));
+    assert_eq!(
+      parse_slice_with_options(&utf16be, &options)
+        .unwrap_err()
+        .kind(),
+      &ErrorKind::ByteOrderMark
+    );
+    assert_eq!(
+      parse_slice_with_options(&utf16be, &options.clone().allow_bom(true)),
+      Ok(expected())
+    );
+    let utf32be = text
+      .chars()
+      .flat_map(|c| (c as u32).to_be_bytes().to_vec())
+      .collect::<Vec<_>>();
+    assert_eq!(parse_slice_with_options(&utf32be, &options), Ok(expected()));
+  }
+}
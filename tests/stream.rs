@@ -0,0 +1,46 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::{
+    parse, parse_stream, parse_stream_slice, parse_stream_slice_with_options, ErrorKind,
+    ParseOptions,
+  };
+  use yojson_rs::value::Value;
+
+  #[test]
+  fn check_stream() {
+    assert_eq!(
+      parse_stream("{a : 1}\n// comment\n{a : 2} {}"),
+      Ok(vec![
+        parse("{a : 1}").unwrap(),
+        parse("{a : 2}").unwrap(),
+        Value::Assoc(Default::default())
+      ])
+    );
+    assert_eq!(parse_stream("  "), Ok(Vec::new()));
+    let error = parse_stream("{a : 1} [1]").unwrap_err();
+    assert_eq!(error.position().column, 9);
+  }
+
+  #[test]
+  fn check_stream_slice() {
+    let text = "{hoge : \"ふが\"} {}";
+    let expected = vec![
+      parse("{hoge : \"ふが\"}").unwrap(),
+      Value::Assoc(Default::default()),
+    ];
+    assert_eq!(parse_stream_slice(text.as_bytes()), Ok(expected.clone()));
+    let utf16le = text
+      .encode_utf16()
+      .flat_map(u16::to_le_bytes)
+      .collect::<Vec<_>>();
+    let options = ParseOptions::new().detect_encoding(true);
+    assert_eq!(
+      parse_stream_slice_with_options(&utf16le, &options),
+      Ok(expected)
+    );
+    let error = parse_stream_slice(b"{a : 1} {a : \"\xff\"}").unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::InvalidEncoding("UTF-8"));
+  }
+}
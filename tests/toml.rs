@@ -0,0 +1,52 @@
+extern crate toml;
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::toml::{from_toml, to_toml};
+
+  #[test]
+  fn check_to_toml() {
+    let value = parse("{name : \"app\", port : 8080, ratio : 0.5, tags : [\"a\", \"b\"]}").unwrap();
+    let toml = to_toml(&value);
+    assert!(toml.is_lossless());
+    assert_eq!(
+      toml::to_string(toml.value()).unwrap(),
+      "name = \"app\"\nport = 8080\nratio = 0.5\ntags = [\"a\", \"b\"]\n"
+    );
+  }
+
+  #[test]
+  fn check_losses() {
+    let value =
+      parse("{a : null, b : [1, null], c : (1, 2), d : <Point>, e : <Circle: 1.5>}").unwrap();
+    let (toml, losses) = to_toml(&value).into_parts();
+    let mut paths = losses.iter().map(|loss| loss.path()).collect::<Vec<_>>();
+    paths.sort_unstable();
+    assert_eq!(paths, vec!["/a", "/b/1", "/c", "/d", "/e"]);
+    assert_eq!(
+      toml,
+      toml::from_str("b = [1]\nc = [1, 2]\nd = \"Point\"\ne = { Circle = 1.5 }").unwrap()
+    );
+  }
+
+  #[test]
+  fn check_from_toml() {
+    let toml = toml::from_str(
+      "[server]\nhost = \"localhost\"\nports = [80, 443]\nstarted = 1979-05-27T07:32:00Z",
+    )
+    .unwrap();
+    let (value, losses) = from_toml(&toml).into_parts();
+    assert_eq!(
+      value,
+      parse(
+        "{server : {host : \"localhost\", ports : [80, 443], started : \"1979-05-27T07:32:00Z\"}}"
+      )
+      .unwrap()
+    );
+    assert_eq!(losses.len(), 1);
+    assert_eq!(losses[0].path(), "/server/started");
+    assert_eq!(losses[0].message(), "datetime is read as a string");
+  }
+}
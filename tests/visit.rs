@@ -0,0 +1,124 @@
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::value::{Assoc, Value};
+  use yojson_rs::visit::{walk, walk_mut, Visitor, VisitorMut, Walk};
+
+  struct Constructors {
+    names: Vec<(String, String)>,
+  }
+
+  impl Visitor for Constructors {
+    fn visit_assoc(&mut self, path: &str, _: &Assoc) -> Walk {
+      if path == "/skipped" {
+        Walk::Skip
+      } else {
+        Walk::Continue
+      }
+    }
+
+    fn visit_variant(&mut self, path: &str, name: &str, _: Option<&Value>) -> Walk {
+      self.names.push((path.to_string(), name.to_string()));
+      if name == "Last" {
+        Walk::Stop
+      } else {
+        Walk::Continue
+      }
+    }
+  }
+
+  #[test]
+  fn check_walk() {
+    let value =
+      parse("{a : <A: [<B>, (<C: <D>>, 1)]>, skipped : {e : <E>}, z : [<Last: <F>>, <G>]}")
+        .unwrap();
+    let mut visitor = Constructors { names: Vec::new() };
+    assert_eq!(walk(&value, &mut visitor), Walk::Stop);
+    let names = visitor
+      .names
+      .iter()
+      .map(|(path, name)| format!("{} {}", path, name))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      names,
+      vec![
+        "/a A",
+        "/a/-payload/0 B",
+        "/a/-payload/1/0 C",
+        "/a/-payload/1/0/-payload D",
+        "/z/0 Last",
+      ]
+    );
+  }
+
+  struct Sanitize;
+
+  impl VisitorMut for Sanitize {
+    fn visit_value(&mut self, _: &str, value: &mut Value) -> Walk {
+      if value.as_float().is_some_and(f64::is_nan) {
+        *value = Value::Null;
+      }
+      Walk::Continue
+    }
+
+    fn visit_assoc(&mut self, _: &str, assoc: &mut Assoc) -> Walk {
+      for (name, value) in assoc.iter_mut() {
+        if name == "password" {
+          *value = Value::String("***".to_string());
+        }
+      }
+      Walk::Continue
+    }
+
+    fn visit_string(&mut self, path: &str, s: &mut String) -> Walk {
+      if path.ends_with("/password") {
+        assert_eq!(s, "***");
+      }
+      Walk::Continue
+    }
+  }
+
+  #[test]
+  fn check_walk_mut() {
+    let mut value = parse(
+      "{db : {password : \"secret\", ratio : NaN}, users : [{name : \"a\", password : 1}], x : (NaN, 1.5)}",
+    )
+    .unwrap();
+    assert_eq!(walk_mut(&mut value, &mut Sanitize), Walk::Continue);
+    assert_eq!(
+      value,
+      parse(
+        "{db : {password : \"***\", ratio : null}, users : [{name : \"a\", password : \"***\"}], x : (null, 1.5)}"
+      )
+      .unwrap()
+    );
+  }
+
+  #[test]
+  fn check_descendants() {
+    let value = parse("{b : [1, <C: {\"d/e\" : 2}>], a : null}").unwrap();
+    let descendants = value
+      .descendants()
+      .map(|(path, value)| format!("{} {}", path, value.type_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      descendants,
+      vec![
+        "/a null",
+        "/b array",
+        "/b/0 integer",
+        "/b/1 variant",
+        "/b/1/-payload assoc",
+        "/b/1/-payload/d~1e integer",
+      ]
+    );
+    let integers = value
+      .descendants()
+      .filter_map(|(_, value)| value.as_integer())
+      .sum::<i64>();
+    assert_eq!(integers, 3);
+    assert_eq!(Value::Integer(1).descendants().count(), 0);
+  }
+}
@@ -0,0 +1,76 @@
+extern crate serde_yaml;
+extern crate yojson_rs;
+
+#[cfg(test)]
+mod tests {
+  use yojson_rs::parser::parse;
+  use yojson_rs::yaml::{from_yaml, to_yaml};
+
+  #[test]
+  fn check_to_yaml() {
+    let value = parse("{shape : <Circle: 1.5>, size : (1, 2)}").unwrap();
+    let yaml = to_yaml(&value);
+    assert!(yaml.is_lossless());
+    let text = serde_yaml::to_string(yaml.value()).unwrap();
+    let expected = "{shape: !Circle 1.5, size: !tuple [1, 2]}";
+    assert_eq!(
+      serde_yaml::from_str::<serde_yaml::Value>(&text).unwrap(),
+      serde_yaml::from_str::<serde_yaml::Value>(expected).unwrap()
+    );
+  }
+
+  #[test]
+  fn check_round_trip() {
+    let value = parse(
+      "{a : [<Point>, <Line: [1.0, NaN]>, (<A>, 2)], b : {c : null, d : \"x\"}, e : -Infinity}",
+    )
+    .unwrap();
+    let text = serde_yaml::to_string(to_yaml(&value).value()).unwrap();
+    let yaml = serde_yaml::from_str(&text).unwrap();
+    let back = from_yaml(&yaml);
+    assert!(back.is_lossless(), "{:?}", back.losses());
+    assert!(yojson_rs::ord::equal(back.value(), &value));
+  }
+
+  #[test]
+  fn check_variant_arguments() {
+    let value = parse(
+      "{a : <Rect: (1.0, 2.0)>, b : <Some: <None>>, c : <A: [1, 2]>, d : <B: (<C>, (1, 2))>}",
+    )
+    .unwrap();
+    let yaml = to_yaml(&value);
+    assert!(yaml.is_lossless(), "{:?}", yaml.losses());
+    let text = serde_yaml::to_string(yaml.value()).unwrap();
+    let expected = "{a: !Rect [1.0, 2.0], b: !Some [!None null], c: !A [[1, 2]], \
+                    d: !B [!C null, !tuple [1, 2]]}";
+    let yaml = serde_yaml::from_str::<serde_yaml::Value>(&text).unwrap();
+    assert_eq!(
+      yaml,
+      serde_yaml::from_str::<serde_yaml::Value>(expected).unwrap()
+    );
+    assert_eq!(from_yaml(&yaml).into_value(), value);
+  }
+
+  #[test]
+  fn check_losses() {
+    let value = parse("{a : <A: null>}").unwrap();
+    let yaml = to_yaml(&value);
+    let messages = yaml
+      .losses()
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      messages,
+      vec!["variant A with the argument null is read back without argument at \"/a\""]
+    );
+    let yaml = serde_yaml::from_str("{1: x, \"1\": y, [2]: z, big: 18446744073709551615}").unwrap();
+    let (value, losses) = from_yaml(&yaml).into_parts();
+    let paths = losses.iter().map(|loss| loss.path()).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["/1", "/1", "", "/big"]);
+    assert_eq!(
+      value,
+      parse("{\"1\" : \"y\", big : 18446744073709551615.0}").unwrap()
+    );
+  }
+}